@@ -1,13 +1,25 @@
 //! The graphics module, containing all the essential GPU abstractions and functionality.
 //!
+/// Contains functionality related to batched GPU buffer writes.
+pub mod batch;
 /// Contains functionality related to GPU buffers.
 pub mod buffer;
 /// Contains functionality related to GPU colors.
 pub mod color;
+/// Contains functionality related to GPU initialization.
+pub mod context;
+/// Contains functionality related to editor gizmos.
+pub mod gizmo;
 /// Contains functionality related to GPU bind groups and layouts.
 pub mod group;
 /// Contains functionality related to GPU buffer layouts.
 pub mod layout;
+/// Contains functionality related to rendering lines of an arbitrary width.
+pub mod line;
+/// Contains functionality related to stencil masking.
+pub mod mask;
+/// Contains functionality related to GPU meshes.
+pub mod mesh;
 /// Contains functionality related to GPU render passes.
 pub mod pass;
 /// Contains functionality related to GPU pipelines.
@@ -16,5 +28,9 @@ pub mod pipeline;
 pub mod sampler;
 /// Contains functionality related to GPU shaders.
 pub mod shader;
+/// Contains functionality related to GPU storage arrays.
+pub mod storage;
 /// Contains functionality related to GPU textures.
 pub mod texture;
+/// Contains functionality related to GPU uniforms.
+pub mod uniforms;
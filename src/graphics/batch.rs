@@ -0,0 +1,117 @@
+use std::ops::Range;
+
+use bytemuck::Pod;
+
+use crate::graphics::buffer::AnyBufferHandle;
+
+/// A collection of pending buffer writes that get dispatched to the GPU together.
+///
+/// Applications that update many small uniform or instance buffers every frame
+/// pay a per-call overhead for every [`wgpu::Queue::write_buffer()`].
+/// A [`WriteBatch`] records writes on the CPU and then flushes them in one place,
+/// merging writes that are contiguous in the same buffer into a single call.
+///
+/// ```rust
+/// # use whirl::{graphics::{batch::WriteBatch, buffer::BufferHandle}, math::mat4::Mat4};
+/// # type Instance = [f32; 4];
+/// # fn frame(
+/// #     queue: &wgpu::Queue,
+/// #     camera_buffer: &BufferHandle<Mat4>,
+/// #     instance_buffer: &BufferHandle<Instance>,
+/// #     camera: Mat4,
+/// #     instances: &[Instance],
+/// # ) {
+/// let mut batch = WriteBatch::new();
+/// batch.write(camera_buffer, 0, &[camera]);
+/// batch.write(instance_buffer, 0, &instances[..64]);
+/// batch.write(instance_buffer, 64 * size_of::<Instance>() as u64, &instances[64..]);
+/// batch.flush(queue);
+/// # }
+/// ```
+///
+/// In this example, the two instance writes are contiguous, so only 2 writes are issued.
+///
+/// The writes are issued in the order they were recorded, so if the same range is written
+/// multiple times, the last write wins.
+///
+/// The buffers written to must be writable and large enough to hold the written data,
+/// which is validated by wgpu when the batch is flushed.
+///
+/// Keep in mind that a batch writes to the GPU buffer directly,
+/// so the CPU-side items of a [`crate::graphics::buffer::BufferHandle`] are not updated.
+#[derive(Debug, Default)]
+pub struct WriteBatch<'a> {
+    /// The bytes of all the recorded writes, laid out back to back
+    bytes: Vec<u8>,
+    /// The recorded writes, each pointing to a range of `bytes`
+    writes: Vec<PendingWrite<'a>>,
+}
+
+/// Describes a single recorded write in a [`WriteBatch`]
+#[derive(Debug)]
+struct PendingWrite<'a> {
+    /// The buffer that gets written to
+    buffer: &'a wgpu::Buffer,
+    /// The offset in bytes into the buffer
+    offset: u64,
+    /// The range of the batch's bytes that get written
+    range: Range<usize>,
+}
+
+impl<'a> WriteBatch<'a> {
+    /// Creates a new empty [`WriteBatch`].
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records a write of `data` into `buffer`, starting at `offset` bytes.
+    ///
+    /// The write is not dispatched until [`WriteBatch::flush()`] is called.
+    ///
+    /// # Panics:
+    /// - If `data` is empty.
+    pub fn write<T: Pod>(&mut self, buffer: &'a dyn AnyBufferHandle, offset: u64, data: &[T]) {
+        assert!(
+            !data.is_empty(),
+            "Cannot write an empty slice to the batch!"
+        );
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        let start = self.bytes.len();
+        self.bytes.extend_from_slice(bytes);
+        let end = self.bytes.len();
+
+        // Merge with the previous write if this one continues right where it ended
+        if let Some(last) = self.writes.last_mut()
+            && last.buffer == buffer.raw()
+            && last.offset + last.range.len() as u64 == offset
+        {
+            last.range.end = end;
+            return;
+        }
+
+        self.writes.push(PendingWrite {
+            buffer: buffer.raw(),
+            offset,
+            range: start..end,
+        });
+    }
+
+    /// Returns the amount of writes that will be issued when flushing.
+    pub fn write_count(&self) -> usize {
+        self.writes.len()
+    }
+
+    /// Returns whether the batch has no recorded writes.
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Dispatches all of the recorded writes and clears the batch so it can be reused.
+    /// - `queue` -> the [`wgpu::Queue`] used to write to the buffers
+    pub fn flush(&mut self, queue: &wgpu::Queue) {
+        for write in self.writes.drain(..) {
+            queue.write_buffer(write.buffer, write.offset, &self.bytes[write.range]);
+        }
+        self.bytes.clear();
+    }
+}
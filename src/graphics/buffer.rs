@@ -1,4 +1,7 @@
-use std::fmt::Debug;
+use std::{
+    fmt::{self, Debug},
+    ops::Range,
+};
 
 use bytemuck::Pod;
 use wgpu::{
@@ -17,10 +20,13 @@ use wgpu::{
 /// - [`BufferUsage::Vertex`]
 /// - [`BufferUsage::Uniform`]
 /// - [`BufferUsage::Storage`]
+/// - [`BufferUsage::Readback`]
 ///
 /// The buffer API is designed around the concepts of "items", where an item
 /// is an instance of the type the buffer has, for example:
 /// ```rust
+/// # use bytemuck::{Pod, Zeroable};
+/// # use whirl::graphics::buffer::{BufferHandle, BufferUsage};
 /// #[repr(C)]
 /// #[derive(Copy, Clone, Zeroable, Pod)]
 /// struct Vertex {
@@ -35,10 +41,10 @@ use wgpu::{
 ///             Vertex { position: [-0.5, -0.5, 0.0] },
 ///             Vertex { position: [ 0.5, -0.5, 0.0] },
 ///         ],
-///         BufferUsage::Vertex { is_writable: false, },
+///         BufferUsage::Vertex { is_writable: false, is_readable: false },
 ///         Some("Triangle geometry"),
 ///     );
-///     ...
+///     // ...
 /// }
 /// ```
 /// This basic example creates a vertex buffer which holds items of type `Vertex`,
@@ -109,6 +115,7 @@ pub struct BufferHandle<T: Pod> {
     item_list: Vec<T>,
     item_capacity: usize,
     usage: BufferUsage,
+    label: Option<String>,
     raw: wgpu::Buffer,
 }
 
@@ -127,6 +134,7 @@ impl<T: Pod> BufferHandle<T> {
         assert!(item_capacity > 0, "Item capacity cannot be zero!");
         Self {
             usage,
+            label: label.map(String::from),
             item_capacity,
             raw: device.create_buffer(&wgpu::BufferDescriptor {
                 label,
@@ -157,6 +165,7 @@ impl<T: Pod> BufferHandle<T> {
         );
         Self {
             usage,
+            label: label.map(String::from),
             item_capacity: item_list.len(),
             raw: device.create_buffer_init(&BufferInitDescriptor {
                 label,
@@ -167,6 +176,58 @@ impl<T: Pod> BufferHandle<T> {
         }
     }
 
+    /// Creates a new buffer that holds `item_count` items, filled in place by `fill`.
+    ///
+    /// The buffer is mapped at creation, so `fill` writes directly into the GPU-visible
+    /// memory instead of going through a staging upload, which is useful for large static data
+    /// such as geometry. The items passed to `fill` start out zeroed.
+    ///
+    /// `fill` runs exactly once, after which the buffer is unmapped and ready for use.
+    /// Mapping at creation does not require a mappable [`BufferUsage`], and the buffer
+    /// cannot be mapped again afterwards.
+    ///
+    /// The capacity of the buffer will initially be equal to `item_count`,
+    /// but can dynamically grow if needed.
+    ///
+    /// # Panics:
+    /// - If `item_count` is equal to zero.
+    /// - If the size of the buffer in bytes is not a multiple of [`wgpu::COPY_BUFFER_ALIGNMENT`].
+    pub fn with_mapped(
+        device: &Device,
+        item_count: usize,
+        usage: BufferUsage,
+        label: Option<&str>,
+        fill: impl FnOnce(&mut [T]),
+    ) -> Self {
+        assert!(item_count > 0, "Item count cannot be zero!");
+        let size = item_count as u64 * size_of::<T>() as u64;
+        assert!(
+            size.is_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT),
+            "Mapped buffer size must be a multiple of {} bytes!",
+            wgpu::COPY_BUFFER_ALIGNMENT
+        );
+        let raw = device.create_buffer(&BufferDescriptor {
+            label,
+            size,
+            usage: usage.raw(),
+            mapped_at_creation: true,
+        });
+        let item_list = {
+            let mut mapped_range = raw.slice(..).get_mapped_range_mut();
+            let mapped_items: &mut [T] = bytemuck::cast_slice_mut(&mut mapped_range);
+            fill(mapped_items);
+            Vec::from(&*mapped_items)
+        };
+        raw.unmap();
+        Self {
+            usage,
+            label: label.map(String::from),
+            item_capacity: item_count,
+            raw,
+            item_list,
+        }
+    }
+
     /// Skips `items_to_skip` items in the buffer and writes an item.
     ///
     /// If `items_to_skip` exceeds the item count of the buffer, the buffer is resized
@@ -341,6 +402,21 @@ impl<T: Pod> BufferHandle<T> {
         self.item_list.fill(T::zeroed());
     }
 
+    /// Records a command to zero the entire GPU buffer, without a render pass or a data upload.
+    ///
+    /// This is the cheapest way to reset a buffer between frames (e.g. a compute buffer),
+    /// the contents are zeroed as well to stay in sync with the GPU buffer.
+    ///
+    /// - `encoder` -> the [`wgpu::CommandEncoder`] to record the clear command into
+    ///
+    /// # Panics:
+    /// The clear requires the `COPY_DST` usage, if the buffer is not writable the caller thread panics.
+    pub fn clear(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        assert!(self.is_writable(), "Buffer must be writable to be cleared");
+        self.item_list.fill(T::zeroed());
+        encoder.clear_buffer(&self.raw, 0, None);
+    }
+
     /// Skips `items_to_skip` items and writes an item, then flushes immediately.
     ///
     /// If `items_to_skip` exceeds the item count of the buffer, the buffer is resized
@@ -373,8 +449,17 @@ impl<T: Pod> BufferHandle<T> {
         items_to_skip: usize,
         item_list: &[T],
     ) {
+        // Any zeroed item slots in between the old end and the new items get flushed as well,
+        // so the GPU buffer doesn't keep stale data from before a truncation in them,
+        // while the items after the written ones are left alone
+        let start_index = items_to_skip.min(self.item_list.len());
         self.skip_and_write_item_list(items_to_skip, item_list);
-        self.skip_and_flush_exact(device, queue, items_to_skip, item_list.len());
+        self.skip_and_flush_exact(
+            device,
+            queue,
+            start_index,
+            items_to_skip + item_list.len() - start_index,
+        );
     }
 
     /// Skips `items_to_skip` items and updates an existing item, then flushes immediately.
@@ -484,8 +569,10 @@ impl<T: Pod> BufferHandle<T> {
     /// # Panics:
     /// - If the buffer is not writable.
     /// - If item capacity of the buffer is zero.
+    /// - If `items_to_skip` exceeds the item count of the buffer.
     pub fn skip_and_flush(&mut self, device: &Device, queue: &Queue, items_to_skip: usize) {
-        self.skip_and_flush_exact(device, queue, items_to_skip, self.item_list.len());
+        let items_to_flush = self.item_list.len().saturating_sub(items_to_skip);
+        self.skip_and_flush_exact(device, queue, items_to_skip, items_to_flush);
     }
 
     /// Skips and flushes an exact amount of items.
@@ -513,9 +600,12 @@ impl<T: Pod> BufferHandle<T> {
             needs_resizing = true;
         }
 
+        // A recreated buffer starts out zeroed, so the whole item list has to be written to it,
+        // which already covers the requested range
         if needs_resizing {
             self.recreate_buffer(device);
             queue.write_buffer(&self.raw, 0, bytemuck::cast_slice(&self.item_list));
+            return;
         }
         let start_index = items_to_skip;
         let end_index = items_to_skip + items_to_flush;
@@ -526,6 +616,153 @@ impl<T: Pod> BufferHandle<T> {
         );
     }
 
+    /// Records a command to copy a region of this GPU buffer into another GPU buffer,
+    /// without going through the CPU.
+    ///
+    /// Only the GPU buffers are affected, the items of both handles are left untouched.
+    ///
+    /// - `encoder` -> the [`wgpu::CommandEncoder`] to record the copy command into
+    /// - `dst` -> the buffer to copy into
+    /// - `src_offset` -> the offset in bytes into this buffer to copy from
+    /// - `dst_offset` -> the offset in bytes into `dst` to copy to
+    /// - `size` -> the amount of bytes to copy
+    ///
+    /// The offsets and the size must be multiples of [`wgpu::COPY_BUFFER_ALIGNMENT`] (4 bytes).
+    ///
+    /// # Panics:
+    /// - If this buffer is not readable, since the copy requires the `COPY_SRC` usage.
+    /// - If `dst` is not writable, since the copy requires the `COPY_DST` usage.
+    /// - If the offsets or the size aren't multiples of [`wgpu::COPY_BUFFER_ALIGNMENT`].
+    pub fn copy_to<U: Pod>(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        dst: &BufferHandle<U>,
+        src_offset: u64,
+        dst_offset: u64,
+        size: u64,
+    ) {
+        assert!(
+            self.is_readable(),
+            "Source buffer must be readable to be copied from"
+        );
+        assert!(
+            dst.is_writable(),
+            "Destination buffer must be writable to be copied to"
+        );
+        assert!(
+            [src_offset, dst_offset, size]
+                .iter()
+                .all(|bytes| bytes.is_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT)),
+            "Buffer copy offsets and size must be multiples of {} bytes!",
+            wgpu::COPY_BUFFER_ALIGNMENT
+        );
+        encoder.copy_buffer_to_buffer(&self.raw, src_offset, &dst.raw, dst_offset, size);
+    }
+
+    /// Reads the contents of the whole GPU buffer back to the CPU, blocking until the GPU is done with it.
+    ///
+    /// This is useful for retrieving the results of compute shaders and for debugging,
+    /// the items of the handle itself aren't touched, so they may differ from the result
+    /// if the GPU wrote to the buffer.
+    ///
+    /// The readback spans the capacity of the buffer (see [`BufferHandle::item_capacity()`]),
+    /// not just the items of the handle, so a buffer that was only allocated and then filled
+    /// on the GPU is read back as a whole, use [`BufferHandle::read_range()`] to read fewer items.
+    ///
+    /// - `device` -> the [`wgpu::Device`] needed to wait for the GPU
+    /// - `queue` -> the [`wgpu::Queue`] needed to submit the copy
+    ///
+    /// Returns a [`BufferError`] if the buffer isn't readable or couldn't be mapped.
+    pub fn read(&self, device: &Device, queue: &Queue) -> Result<Vec<T>, BufferError> {
+        self.read_range(device, queue, 0..self.item_capacity)
+    }
+
+    /// Reads a range of items of the GPU buffer back to the CPU, blocking until the GPU is done with it.
+    ///
+    /// A [`BufferUsage::Readback`] buffer is mapped directly, any other buffer is copied
+    /// into a temporary [`BufferUsage::Readback`] staging buffer first, which requires it to be readable.
+    ///
+    /// - `device` -> the [`wgpu::Device`] needed to wait for the GPU
+    /// - `queue` -> the [`wgpu::Queue`] needed to submit the copy
+    /// - `range` -> the range of items to read back
+    ///
+    /// Returns a [`BufferError`] if the buffer isn't readable or couldn't be mapped.
+    ///
+    /// # Panics:
+    /// - If the range is reversed or reaches past the capacity of the buffer.
+    pub fn read_range(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        range: Range<usize>,
+    ) -> Result<Vec<T>, BufferError> {
+        assert!(
+            range.start <= range.end && range.end <= self.item_capacity,
+            "Read range {:?} is out of bounds for a buffer of capacity {}",
+            range,
+            self.item_capacity
+        );
+        let start = range.start as u64 * size_of::<T>() as u64;
+        let end = range.end as u64 * size_of::<T>() as u64;
+        if start == end {
+            return Ok(Vec::new());
+        }
+        // Mappings must start at a multiple of 8 bytes and copies must cover a multiple of 4 bytes
+        let aligned_start = start - start % wgpu::MAP_ALIGNMENT;
+        let aligned_end = end
+            .next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT)
+            .min(self.raw.size());
+        let aligned_size = aligned_end - aligned_start;
+
+        let staging = match self.usage {
+            BufferUsage::Readback => {
+                // Writes are only performed on the next submission, which has to happen before mapping
+                queue.submit([]);
+                None
+            }
+            _ if self.is_readable() => {
+                let staging = device.create_buffer(&BufferDescriptor {
+                    label: Some("buffer readback buffer"),
+                    size: aligned_size,
+                    usage: BufferUsage::Readback.raw(),
+                    mapped_at_creation: false,
+                });
+                let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("buffer readback encoder"),
+                });
+                encoder.copy_buffer_to_buffer(&self.raw, aligned_start, &staging, 0, aligned_size);
+                queue.submit([encoder.finish()]);
+                Some(staging)
+            }
+            _ => return Err(BufferError::NotReadable { usage: self.usage }),
+        };
+        let (mappable, mapped_start) = match &staging {
+            Some(staging) => (staging, 0),
+            None => (&self.raw, aligned_start),
+        };
+
+        let slice = mappable.slice(mapped_start..mapped_start + aligned_size);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(|cause| BufferError::PollFailure { cause })?;
+        receiver
+            .recv()
+            .expect("The buffer mapping callback must run after waiting for the device")
+            .map_err(|cause| BufferError::MapFailure { cause })?;
+
+        let mapped_range = slice.get_mapped_range();
+        let offset = (start - aligned_start) as usize;
+        let items =
+            bytemuck::pod_collect_to_vec(&mapped_range[offset..offset + (end - start) as usize]);
+        drop(mapped_range);
+        mappable.unmap();
+        Ok(items)
+    }
+
     /// Converts the item count to bytes.
     pub fn item_count_to_bytes(&self) -> u64 {
         self.item_list.len() as u64 * size_of::<T>() as u64
@@ -544,10 +781,22 @@ impl<T: Pod> BufferHandle<T> {
     /// Returns whether the buffer is writable or not
     pub fn is_writable(&self) -> bool {
         match self.usage {
-            BufferUsage::Index { is_writable } => is_writable,
-            BufferUsage::Vertex { is_writable } => is_writable,
-            BufferUsage::Uniform { is_writable } => is_writable,
-            BufferUsage::Storage { is_writable } => is_writable,
+            BufferUsage::Index { is_writable, .. } => is_writable,
+            BufferUsage::Vertex { is_writable, .. } => is_writable,
+            BufferUsage::Uniform { is_writable, .. } => is_writable,
+            BufferUsage::Storage { is_writable, .. } => is_writable,
+            BufferUsage::Readback => true,
+        }
+    }
+
+    /// Returns whether the buffer is readable (can be copied from) or not
+    pub fn is_readable(&self) -> bool {
+        match self.usage {
+            BufferUsage::Index { is_readable, .. } => is_readable,
+            BufferUsage::Vertex { is_readable, .. } => is_readable,
+            BufferUsage::Uniform { is_readable, .. } => is_readable,
+            BufferUsage::Storage { is_readable, .. } => is_readable,
+            BufferUsage::Readback => false,
         }
     }
 
@@ -556,6 +805,13 @@ impl<T: Pod> BufferHandle<T> {
         self.usage
     }
 
+    /// Returns the debugging label the buffer was created with.
+    ///
+    /// The label is kept when the buffer grows and its GPU buffer gets recreated.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
     /// Returns a slice of the contents.
     pub fn items(&self) -> &[T] {
         &self.item_list
@@ -577,6 +833,8 @@ impl<T: Pod> BufferHandle<T> {
     }
 
     /// Returns the raw [`wgpu::Buffer`].
+    ///
+    /// Writes made through the raw buffer bypass the handle, which won't reflect them in its items.
     pub fn raw(&self) -> &wgpu::Buffer {
         &self.raw
     }
@@ -589,7 +847,7 @@ impl<T: Pod> BufferHandle<T> {
     /// Recreates the GPU buffer internally.
     fn recreate_buffer(&mut self, device: &Device) {
         self.raw = device.create_buffer(&BufferDescriptor {
-            label: None,
+            label: self.label.as_deref(),
             size: self.item_capacity_to_bytes(),
             usage: self.usage.raw(),
             mapped_at_creation: false,
@@ -599,50 +857,106 @@ impl<T: Pod> BufferHandle<T> {
 
 /// Specifies the usage of the buffer on the GPU
 ///
-/// All variants specify whether the buffer can be written to after creation
+/// The GPU usage variants specify whether the buffer can be written to after creation,
+/// and whether it can be read back (copied from) after creation.
+///
+/// Reading a buffer back to the CPU takes 2 buffers, a readable source buffer,
+/// and a [`BufferUsage::Readback`] staging buffer the source gets copied into,
+/// which can then be mapped.
 #[derive(Debug, Clone, Copy)]
 pub enum BufferUsage {
     /// Specifies that the buffer will be used for index data
-    Index { is_writable: bool },
+    Index {
+        is_writable: bool,
+        is_readable: bool,
+    },
     /// Specifies that the buffer will be used for vertex data
-    Vertex { is_writable: bool },
+    Vertex {
+        is_writable: bool,
+        is_readable: bool,
+    },
     /// Specifies that the buffer will be used for small amounts of data in shaders
-    Uniform { is_writable: bool },
+    Uniform {
+        is_writable: bool,
+        is_readable: bool,
+    },
     /// Specifies that the buffer will be used for large amounts of data in shaders
-    Storage { is_writable: bool },
+    Storage {
+        is_writable: bool,
+        is_readable: bool,
+    },
+    /// Specifies that the buffer will be used as a staging buffer for reading data back to the CPU,
+    /// it can be copied into and mapped for reading, but can't be used in shaders
+    Readback,
 }
 
 impl BufferUsage {
     /// Maps the [`BufferUsage`] to the internal [`wgpu::BufferUsages`]
     pub fn raw(self) -> wgpu::BufferUsages {
-        match self {
-            Self::Index { is_writable } => {
-                if is_writable {
-                    wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST
-                } else {
-                    wgpu::BufferUsages::INDEX
-                }
+        let (usage, is_writable, is_readable) = match self {
+            Self::Index {
+                is_writable,
+                is_readable,
+            } => (wgpu::BufferUsages::INDEX, is_writable, is_readable),
+            Self::Vertex {
+                is_writable,
+                is_readable,
+            } => (wgpu::BufferUsages::VERTEX, is_writable, is_readable),
+            Self::Uniform {
+                is_writable,
+                is_readable,
+            } => (wgpu::BufferUsages::UNIFORM, is_writable, is_readable),
+            Self::Storage {
+                is_writable,
+                is_readable,
+            } => (wgpu::BufferUsages::STORAGE, is_writable, is_readable),
+            Self::Readback => {
+                return wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST;
             }
-            Self::Vertex { is_writable } => {
-                if is_writable {
-                    wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST
-                } else {
-                    wgpu::BufferUsages::VERTEX
-                }
+        };
+
+        let mut usage = usage;
+        if is_writable {
+            usage |= wgpu::BufferUsages::COPY_DST;
+        }
+        if is_readable {
+            usage |= wgpu::BufferUsages::COPY_SRC;
+        }
+        usage
+    }
+}
+
+/// Specifies a buffer error that may have occurred.
+#[derive(Debug)]
+pub enum BufferError {
+    /// The buffer can't be read back, since it's neither readable nor a [`BufferUsage::Readback`] buffer
+    NotReadable {
+        /// The usage of the buffer
+        usage: BufferUsage,
+    },
+    /// Waiting for the GPU to finish with the buffer failed
+    PollFailure {
+        /// The underlying cause of the failure
+        cause: wgpu::PollError,
+    },
+    /// The buffer couldn't be mapped for reading
+    MapFailure {
+        /// The underlying cause of the failure
+        cause: wgpu::BufferAsyncError,
+    },
+}
+
+impl fmt::Display for BufferError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BufferError::NotReadable { usage } => {
+                write!(f, "Couldn't read back a buffer of usage:\n\t{:?}", usage)
             }
-            Self::Uniform { is_writable } => {
-                if is_writable {
-                    wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST
-                } else {
-                    wgpu::BufferUsages::UNIFORM
-                }
+            BufferError::PollFailure { cause } => {
+                write!(f, "Couldn't wait for the buffer readback:\n\t{}", cause)
             }
-            Self::Storage { is_writable } => {
-                if is_writable {
-                    wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST
-                } else {
-                    wgpu::BufferUsages::STORAGE
-                }
+            BufferError::MapFailure { cause } => {
+                write!(f, "Couldn't map the buffer for reading:\n\t{}", cause)
             }
         }
     }
@@ -650,6 +964,22 @@ impl BufferUsage {
 
 /// A trait that is used to erase the generic type of a buffer,
 /// so it can be used in context where the type of the buffer is not known in advance.
+///
+/// The trait is object safe, which is what lets the render pass accept geometry, index
+/// and instance buffers of any item type as a `&dyn AnyBufferHandle`:
+/// ```rust
+/// # use whirl::graphics::{buffer::BufferHandle, pass::RenderPass};
+/// # type MeshVertex = [f32; 3];
+/// # fn draw(pass: &mut RenderPass, vertices: &BufferHandle<MeshVertex>, indices: &BufferHandle<u32>) {
+/// pass.use_geometry_buffer(0, vertices); // BufferHandle<MeshVertex>
+/// pass.use_index_buffer(indices); // BufferHandle<u32>
+/// # }
+/// ```
+///
+/// Besides [`BufferHandle`], the typed wrappers built on top of it, such as
+/// [`Uniform`](crate::graphics::uniforms::Uniform) and
+/// [`StorageArray`](crate::graphics::storage::StorageArray), implement it as well,
+/// so they can be bound directly without reaching for the inner handle.
 pub trait AnyBufferHandle: Debug {
     /// Functionally the same as [`BufferHandle::raw()`].
     fn raw(&self) -> &wgpu::Buffer;
@@ -666,3 +996,35 @@ impl<T: Debug + Pod> AnyBufferHandle for BufferHandle<T> {
         self.as_slice()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage() {
+        {
+            let usage = BufferUsage::Storage {
+                is_writable: true,
+                is_readable: true,
+            };
+            let expected = wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_DST
+                | wgpu::BufferUsages::COPY_SRC;
+            assert_eq!(usage.raw(), expected);
+        }
+
+        {
+            let usage = BufferUsage::Vertex {
+                is_writable: false,
+                is_readable: false,
+            };
+            assert_eq!(usage.raw(), wgpu::BufferUsages::VERTEX);
+        }
+
+        {
+            let expected = wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST;
+            assert_eq!(BufferUsage::Readback.raw(), expected);
+        }
+    }
+}
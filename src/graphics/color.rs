@@ -1,4 +1,4 @@
-use crate::math::vec4::Vec4;
+use crate::math::{self, vec4::Vec4};
 
 /// Specifies a color in the RGBA color space.
 ///
@@ -21,6 +21,7 @@ use crate::math::vec4::Vec4;
 ///
 /// # Examples:
 /// ```rust
+/// # use whirl::graphics::color::Color;
 /// const RED: Color = Color::opaque(
 ///     1.0, // Red channel (full)
 ///     0.0, // Green channel (no value)
@@ -37,7 +38,7 @@ use crate::math::vec4::Vec4;
 ///     0.0, // Red channel (no value)
 ///     0.0, // Green channel (no value)
 ///     1.0, // Blue channel (full)
-/// )
+/// );
 /// ```
 #[derive(Debug, Clone, Copy)]
 pub struct Color {
@@ -83,12 +84,54 @@ impl Color {
         b: 1.0,
         a: 1.0,
     };
+    /// An opaque yellow color.
+    pub const YELLOW: Self = Self {
+        r: 1.0,
+        g: 1.0,
+        b: 0.0,
+        a: 1.0,
+    };
+    /// An opaque cyan color.
+    pub const CYAN: Self = Self {
+        r: 0.0,
+        g: 1.0,
+        b: 1.0,
+        a: 1.0,
+    };
+    /// An opaque magenta color.
+    pub const MAGENTA: Self = Self {
+        r: 1.0,
+        g: 0.0,
+        b: 1.0,
+        a: 1.0,
+    };
+    /// An opaque orange color.
+    pub const ORANGE: Self = Self {
+        r: 1.0,
+        g: 0.5,
+        b: 0.0,
+        a: 1.0,
+    };
+    /// An opaque gray color, halfway between black and white.
+    pub const GRAY: Self = Self {
+        r: 0.5,
+        g: 0.5,
+        b: 0.5,
+        a: 1.0,
+    };
+    /// A fully transparent color, useful for clearing to nothing.
+    pub const TRANSPARENT: Self = Self {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 0.0,
+    };
 
     /// Creates a new [`Color`].
     ///
     /// If the value of any channel is beyond the normalized range (`0.0 - 1.0`),
     /// the value of the channel is clamped to the normalized range.
-    pub fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+    pub const fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
         Self {
             r: red.clamp(0.0, 1.0),
             g: green.clamp(0.0, 1.0),
@@ -101,10 +144,103 @@ impl Color {
     ///
     /// An opaque color is simply a color with the alpha value set to `1.0`,
     /// meaning the color is fully opaque.
-    pub fn opaque(red: f32, green: f32, blue: f32) -> Self {
+    pub const fn opaque(red: f32, green: f32, blue: f32) -> Self {
         Self::new(red, green, blue, 1.0)
     }
 
+    /// Creates a new opaque gray [`Color`].
+    ///
+    /// All color channels are set to `value`, which is clamped to the normalized range.
+    pub const fn gray(value: f32) -> Self {
+        Self::opaque(value, value, value)
+    }
+
+    /// Creates a new [`Color`] from the HSV (hue, saturation, value) color space.
+    ///
+    /// The hue is an angle in degrees, which wraps around to the range of `0.0 - 360.0`,
+    /// the saturation, value and alpha are clamped to the normalized range.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self {
+        let hue = hue.rem_euclid(360.0);
+        let saturation = saturation.clamp(0.0, 1.0);
+        let value = value.clamp(0.0, 1.0);
+
+        let chroma = value * saturation;
+        let sector = hue / 60.0;
+        let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = value - chroma;
+        Self::new(r + m, g + m, b + m, alpha)
+    }
+
+    /// Maps the color to the HSV (hue, saturation, value) color space.
+    ///
+    /// The hue is in degrees (`0.0 - 360.0`), it's `0.0` for grays, which have no hue.
+    /// The alpha channel is left out.
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let chroma = max - min;
+
+        let hue = if chroma < f32::EPSILON {
+            0.0
+        } else if max == self.r {
+            60.0 * ((self.g - self.b) / chroma).rem_euclid(6.0)
+        } else if max == self.g {
+            60.0 * ((self.b - self.r) / chroma + 2.0)
+        } else {
+            60.0 * ((self.r - self.g) / chroma + 4.0)
+        };
+        let saturation = if max < f32::EPSILON {
+            0.0
+        } else {
+            chroma / max
+        };
+        (hue, saturation, max)
+    }
+
+    /// Linearly interpolates between 2 colors in the HSV color space.
+    /// - `self` -> the color at `t = 0.0`
+    /// - `other` -> the color at `t = 1.0`
+    /// - `t` -> the interpolation factor, clamped to the range of `0.0 - 1.0`
+    ///
+    /// Unlike interpolating the RGB channels, which produces muddy mid-tones (red to green passes through brown),
+    /// this keeps the intermediate colors saturated (red to green passes through yellow).
+    /// The hue takes the shortest arc around the color wheel, so it wraps around `0.0 / 360.0` when that's closer,
+    /// easing functions can be applied to `t` beforehand for non-linear ramps.
+    pub fn lerp_hsv(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let (mut from_hue, from_saturation, from_value) = self.to_hsv();
+        let (mut to_hue, to_saturation, to_value) = other.to_hsv();
+        // Grays have no hue, so they take the hue of the other color instead of drifting through red
+        if from_saturation < f32::EPSILON {
+            from_hue = to_hue;
+        }
+        if to_saturation < f32::EPSILON {
+            to_hue = from_hue;
+        }
+
+        let mut delta = to_hue - from_hue;
+        if delta > 180.0 {
+            delta -= 360.0;
+        } else if delta < -180.0 {
+            delta += 360.0;
+        }
+
+        Self::from_hsv(
+            from_hue + delta * t,
+            math::lerp(from_saturation, to_saturation, t),
+            math::lerp(from_value, to_value, t),
+            math::lerp(self.a, other.a, t),
+        )
+    }
+
     /// Darkens the color by a factor ranging from `0.0` to `1.0`
     ///
     /// If the factor would exceed the channel's min/max value,
@@ -165,6 +301,17 @@ impl Color {
     pub fn as_vec(self) -> Vec4 {
         Vec4::new(self.r, self.g, self.b, self.a)
     }
+
+    /// Maps the color to 8-bit RGBA channels, as laid out in an RGBA8 texture.
+    pub fn as_bytes(self) -> [u8; 4] {
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        [
+            channel(self.r),
+            channel(self.g),
+            channel(self.b),
+            channel(self.a),
+        ]
+    }
 }
 
 impl Default for Color {
@@ -172,3 +319,65 @@ impl Default for Color {
         Self::BLACK
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{EPSILON, cmp_f32};
+
+    fn cmp(a: Color, b: Color) -> bool {
+        cmp_f32(a.r, b.r, EPSILON)
+            && cmp_f32(a.g, b.g, EPSILON)
+            && cmp_f32(a.b, b.b, EPSILON)
+            && cmp_f32(a.a, b.a, EPSILON)
+    }
+
+    #[test]
+    fn hsv() {
+        {
+            assert!(cmp(Color::from_hsv(0.0, 1.0, 1.0, 1.0), Color::RED));
+            assert!(cmp(Color::from_hsv(120.0, 1.0, 1.0, 1.0), Color::GREEN));
+            assert!(cmp(Color::from_hsv(600.0, 1.0, 1.0, 1.0), Color::BLUE));
+        }
+
+        {
+            let (hue, saturation, value) = Color::MAGENTA.to_hsv();
+            assert!(cmp_f32(hue, 300.0, EPSILON));
+            assert!(cmp_f32(saturation, 1.0, EPSILON));
+            assert!(cmp_f32(value, 1.0, EPSILON));
+        }
+
+        {
+            let (hue, saturation, value) = Color::GRAY.to_hsv();
+            assert!(cmp_f32(hue, 0.0, EPSILON));
+            assert!(cmp_f32(saturation, 0.0, EPSILON));
+            assert!(cmp_f32(value, 0.5, EPSILON));
+        }
+    }
+
+    #[test]
+    fn lerp_hsv() {
+        {
+            // Red to green passes through yellow rather than a muddy brown
+            let mid = Color::RED.lerp_hsv(Color::GREEN, 0.5);
+            assert!(cmp(mid, Color::YELLOW));
+        }
+
+        {
+            // The hue wraps around 360 degrees, magenta (300) to red (0/360) doesn't pass through green
+            let mid = Color::MAGENTA.lerp_hsv(Color::RED, 0.5);
+            assert!(cmp(mid, Color::from_hsv(330.0, 1.0, 1.0, 1.0)));
+        }
+
+        {
+            assert!(cmp(Color::RED.lerp_hsv(Color::BLUE, 0.0), Color::RED));
+            assert!(cmp(Color::RED.lerp_hsv(Color::BLUE, 1.0), Color::BLUE));
+        }
+
+        {
+            // Black has no hue, so it takes the hue of blue instead of drifting through other hues
+            let mid = Color::BLACK.lerp_hsv(Color::BLUE, 0.5);
+            assert!(cmp(mid, Color::opaque(0.25, 0.25, 0.5)));
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::{fmt, num::NonZeroUsize, sync::Mutex, thread};
+
+/// Describes the core GPU objects every application needs, created together by a [`ContextDescriptor`]
+///
+/// The raw wgpu handles are all exposed, so wgpu functionality the toolkit doesn't wrap stays reachable
+#[derive(Debug)]
+pub struct Context {
+    /// The internal [`wgpu::Instance`]
+    instance: wgpu::Instance,
+    /// The internal [`wgpu::Adapter`]
+    adapter: wgpu::Adapter,
+    /// The internal [`wgpu::Device`]
+    device: wgpu::Device,
+    /// The internal [`wgpu::Queue`]
+    queue: wgpu::Queue,
+}
+
+/// Describes a [`Context`]
+#[derive(Debug)]
+pub struct ContextDescriptor<'a> {
+    /// The optional debugging label of the device
+    pub label: Option<&'a str>,
+    /// The graphics backend to use
+    pub backend: Backend,
+    /// The features the device must support, wgpu's validation rejects using any other optional feature
+    pub features: wgpu::Features,
+}
+
+/// Specifies the graphics backend wgpu runs on
+///
+/// Forcing a specific backend is useful for working around driver bugs,
+/// reproducing backend-specific issues, or running on a software adapter in CI
+/// ([`Backend::Gl`] with a software OpenGL implementation such as llvmpipe)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Backend {
+    /// The first-class backends of the platform (Vulkan, Metal, DX12 or WebGPU)
+    #[default]
+    Primary,
+    /// Vulkan, available on Windows, Linux and Android
+    Vulkan,
+    /// Metal, available on macOS and iOS
+    Metal,
+    /// DirectX 12, available on Windows
+    Dx12,
+    /// OpenGL (or OpenGL ES / WebGL), available almost everywhere, but with fewer capabilities
+    Gl,
+    /// Any backend that's available
+    All,
+}
+
+/// Specifies a context error that may have occurred
+#[derive(Debug)]
+pub enum ContextError {
+    /// No adapter is available on the requested backend
+    NoAdapter {
+        /// The requested backend
+        backend: Backend,
+        /// The underlying cause of the failure
+        cause: wgpu::RequestAdapterError,
+    },
+    /// The adapter couldn't create a device
+    NoDevice {
+        /// The underlying cause of the failure
+        cause: wgpu::RequestDeviceError,
+    },
+}
+
+impl Context {
+    /// Returns a reference to the raw [`wgpu::Instance`], e.g. to create a surface
+    pub fn instance(&self) -> &wgpu::Instance {
+        &self.instance
+    }
+
+    /// Returns a reference to the raw [`wgpu::Adapter`]
+    pub fn adapter(&self) -> &wgpu::Adapter {
+        &self.adapter
+    }
+
+    /// Returns a reference to the raw [`wgpu::Device`]
+    pub fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    /// Returns a reference to the raw [`wgpu::Queue`]
+    pub fn queue(&self) -> &wgpu::Queue {
+        &self.queue
+    }
+
+    /// Records command buffers on multiple threads and submits them together, in order
+    /// - `passes` -> the functions recording the commands, each one gets its own [`wgpu::CommandEncoder`]
+    ///
+    /// The passes are recorded by a pool of scoped worker threads, one per available core
+    /// (see [`std::thread::available_parallelism()`]) but never more than there are passes,
+    /// each worker takes the next unrecorded pass until none are left. So recording many draw calls
+    /// (e.g. a large scene split into chunks) doesn't bottleneck on a single core, without spawning
+    /// a thread for every pass. This function blocks until all passes have been recorded.
+    ///
+    /// # Ordering:
+    /// The passes may be recorded in any order and concurrently, but the resulting command buffers
+    /// are always submitted in the order of `passes`, in a single submission.
+    /// So a pass can rely on the GPU work of the passes before it, but not on their CPU side effects.
+    ///
+    /// # Thread safety:
+    /// [`wgpu::Device`], [`wgpu::Queue`] and resources such as buffers, textures, bind groups
+    /// and pipelines are `Send + Sync`, so they can be shared by reference across the passes.
+    /// A [`wgpu::CommandEncoder`] (and any render pass recorded on it) is `Send` but not `Sync`,
+    /// so each pass exclusively owns its encoder.
+    ///
+    /// # Panics:
+    /// If any of the passes panics, the panic is propagated to the caller thread and nothing is submitted.
+    pub fn record_parallel<F>(&self, passes: Vec<F>)
+    where
+        F: FnOnce(&mut wgpu::CommandEncoder) + Send,
+    {
+        let pass_count = passes.len();
+        let worker_count = thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(pass_count);
+        let passes = Mutex::new(passes.into_iter().enumerate());
+
+        let mut command_buffers: Vec<Option<wgpu::CommandBuffer>> =
+            std::iter::repeat_with(|| None).take(pass_count).collect();
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..worker_count)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut recorded = Vec::new();
+                        loop {
+                            // The lock is released before recording, so the passes record concurrently
+                            let next = passes.lock().unwrap().next();
+                            let Some((index, pass)) = next else {
+                                break recorded;
+                            };
+                            let mut encoder = self.device.create_command_encoder(
+                                &wgpu::CommandEncoderDescriptor {
+                                    label: Some("parallel pass encoder"),
+                                },
+                            );
+                            pass(&mut encoder);
+                            recorded.push((index, encoder.finish()));
+                        }
+                    })
+                })
+                .collect();
+
+            for worker in workers {
+                match worker.join() {
+                    Ok(recorded) => {
+                        for (index, command_buffer) in recorded {
+                            command_buffers[index] = Some(command_buffer);
+                        }
+                    }
+                    Err(cause) => std::panic::resume_unwind(cause),
+                }
+            }
+        });
+
+        self.queue.submit(command_buffers.into_iter().flatten());
+    }
+}
+
+impl<'a> ContextDescriptor<'a> {
+    /// Attempts to build a [`Context`] from this descriptor, returns a [`ContextError`] upon failure
+    /// - `compatible_surface` -> the surface the adapter must be able to present to,
+    ///   `None` for applications that only render offscreen
+    ///
+    /// There is no fallback to other backends, if the requested backend isn't available
+    /// (or has no adapter), [`ContextError::NoAdapter`] is returned so the caller can decide
+    /// whether to retry with another backend (e.g. [`Backend::All`])
+    ///
+    /// The device is created with the highest limits the adapter supports
+    pub async fn build(
+        self,
+        compatible_surface: Option<&wgpu::Surface<'_>>,
+    ) -> Result<Context, ContextError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: self.backend.raw(),
+            ..Default::default()
+        });
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                force_fallback_adapter: false,
+                compatible_surface,
+            })
+            .await
+            .map_err(|cause| ContextError::NoAdapter {
+                backend: self.backend,
+                cause,
+            })?;
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: self.label,
+                required_features: self.features,
+                required_limits: adapter.limits(),
+                ..Default::default()
+            })
+            .await
+            .map_err(|cause| ContextError::NoDevice { cause })?;
+
+        Ok(Context {
+            instance,
+            adapter,
+            device,
+            queue,
+        })
+    }
+}
+
+impl Backend {
+    /// Maps the [`Backend`] to the internal [`wgpu::Backends`]
+    pub fn raw(self) -> wgpu::Backends {
+        match self {
+            Backend::Primary => wgpu::Backends::PRIMARY,
+            Backend::Vulkan => wgpu::Backends::VULKAN,
+            Backend::Metal => wgpu::Backends::METAL,
+            Backend::Dx12 => wgpu::Backends::DX12,
+            Backend::Gl => wgpu::Backends::GL,
+            Backend::All => wgpu::Backends::all(),
+        }
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ContextError::NoAdapter { backend, cause } => {
+                write!(
+                    f,
+                    "Couldn't find an adapter on the {:?} backend:\n\t{}",
+                    backend, cause
+                )
+            }
+            ContextError::NoDevice { cause } => {
+                write!(f, "Couldn't create a device:\n\t{}", cause)
+            }
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::f32::consts::TAU;
+
+use crate::{
+    graphics::{color::Color, line::LineRenderer},
+    math::{mat4::Mat4, ray::Ray, vec3::Vec3},
+};
+
+/// The amount of line segments a rotation ring is made of
+const RING_SEGMENTS: usize = 48;
+
+/// An editor handle for translating, rotating or scaling an object along its 3 axes
+///
+/// A gizmo is drawn with a [`LineRenderer`], at the position and orientation of a transformation matrix,
+/// the scale of the matrix is ignored so the gizmo keeps the same size no matter how the object is scaled.
+///
+/// ```rust
+/// # use whirl::{
+/// #     graphics::{gizmo::{Gizmo, GizmoMode}, line::LineRenderer},
+/// #     math::{mat4::Mat4, ray::Ray},
+/// # };
+/// # fn run(line_renderer: &mut LineRenderer, transform: Mat4, cursor_ray: Ray) {
+/// let mut gizmo = Gizmo::new(GizmoMode::Translate, 1.0);
+///
+/// // When the cursor moves
+/// gizmo.highlighted = gizmo.hit_test(&transform, cursor_ray);
+///
+/// // Every frame
+/// gizmo.draw(line_renderer, &transform);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Gizmo {
+    /// The kind of handles the gizmo shows
+    pub mode: GizmoMode,
+    /// The length of the axis handles (and the radius of the rotation rings) in world units
+    pub size: f32,
+    /// The width of the gizmo lines in pixels
+    pub width: f32,
+    /// The axis that's highlighted, usually the one under the cursor or the one being dragged
+    pub highlighted: Option<GizmoAxis>,
+}
+
+/// Specifies the kind of handles a [`Gizmo`] shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GizmoMode {
+    /// An arrow along each axis
+    Translate,
+    /// A ring around each axis
+    Rotate,
+    /// A line ending with a box along each axis
+    Scale,
+}
+
+/// Specifies an axis of a [`Gizmo`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GizmoAxis {
+    /// The X axis, drawn in red
+    X,
+    /// The Y axis, drawn in green
+    Y,
+    /// The Z axis, drawn in blue
+    Z,
+}
+
+impl Gizmo {
+    /// Creates a new gizmo with 3 pixel wide lines and no highlighted axis
+    /// - `mode` -> the kind of handles the gizmo shows
+    /// - `size` -> the length of the axis handles (and the radius of the rotation rings) in world units
+    pub fn new(mode: GizmoMode, size: f32) -> Self {
+        Self {
+            mode,
+            size,
+            width: 3.0,
+            highlighted: None,
+        }
+    }
+
+    /// Adds the lines of the gizmo to a [`LineRenderer`]
+    /// - `lines` -> the line renderer to add the lines to
+    /// - `transform` -> the transformation matrix of the object the gizmo belongs to
+    pub fn draw(&self, lines: &mut LineRenderer, transform: &Mat4) {
+        let origin = transform.translation();
+        for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
+            let color = if self.highlighted == Some(axis) {
+                Color::YELLOW
+            } else {
+                axis.color()
+            };
+            let (direction, side, up) = Self::frame(transform, axis);
+            let tip = origin + direction * self.size;
+
+            match self.mode {
+                GizmoMode::Translate => {
+                    let head = self.size * 0.15;
+                    let base = tip - direction * head;
+                    lines.add_line(origin, tip, color, self.width);
+                    for offset in [side, side * -1.0, up, up * -1.0] {
+                        lines.add_line(tip, base + offset * (head * 0.5), color, self.width);
+                    }
+                }
+                GizmoMode::Rotate => {
+                    let point = |i: usize| {
+                        let angle = i as f32 / RING_SEGMENTS as f32 * TAU;
+                        origin + (side * angle.cos() + up * angle.sin()) * self.size
+                    };
+                    for i in 0..RING_SEGMENTS {
+                        lines.add_line(point(i), point(i + 1), color, self.width);
+                    }
+                }
+                GizmoMode::Scale => {
+                    let half = self.size * 0.06;
+                    let corners = [
+                        tip + (side + up) * half,
+                        tip + (side - up) * half,
+                        tip - (side + up) * half,
+                        tip - (side - up) * half,
+                    ];
+                    lines.add_line(origin, tip, color, self.width);
+                    for i in 0..corners.len() {
+                        let next = corners[(i + 1) % corners.len()];
+                        lines.add_line(corners[i], next, color, self.width);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the axis of the gizmo the ray hits, if any
+    /// - `transform` -> the transformation matrix of the object the gizmo belongs to
+    /// - `ray` -> the ray to test, usually cast from the camera through the cursor
+    ///
+    /// A handle is hit if the ray passes within a tenth of the gizmo's size of it,
+    /// if multiple handles are hit, the one closest to the ray's origin wins
+    pub fn hit_test(&self, transform: &Mat4, ray: Ray) -> Option<GizmoAxis> {
+        let origin = transform.translation();
+        let tolerance = self.size * 0.1;
+
+        let mut closest: Option<(GizmoAxis, f32)> = None;
+        for axis in [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z] {
+            let (direction, _, _) = Self::frame(transform, axis);
+            let hit = match self.mode {
+                GizmoMode::Translate | GizmoMode::Scale => {
+                    let tip = origin + direction * self.size;
+                    let (distance, t) = ray.distance_to_segment(origin, tip);
+                    (distance <= tolerance).then_some(t)
+                }
+                GizmoMode::Rotate => ray
+                    .intersect_plane(origin, direction)
+                    .filter(|&t| (ray.at(t).dist(origin) - self.size).abs() <= tolerance),
+            };
+
+            if let Some(t) = hit
+                && closest.is_none_or(|(_, closest_t)| t < closest_t)
+            {
+                closest = Some((axis, t));
+            }
+        }
+
+        closest.map(|(axis, _)| axis)
+    }
+
+    /// Returns the normalized direction of an axis of the transformation matrix,
+    /// along with 2 normalized directions perpendicular to it
+    fn frame(transform: &Mat4, axis: GizmoAxis) -> (Vec3, Vec3, Vec3) {
+        let x = Vec3::new(transform.x_axis.x, transform.x_axis.y, transform.x_axis.z).normalize();
+        let y = Vec3::new(transform.y_axis.x, transform.y_axis.y, transform.y_axis.z).normalize();
+        let z = Vec3::new(transform.z_axis.x, transform.z_axis.y, transform.z_axis.z).normalize();
+        match axis {
+            GizmoAxis::X => (x, y, z),
+            GizmoAxis::Y => (y, z, x),
+            GizmoAxis::Z => (z, x, y),
+        }
+    }
+}
+
+impl GizmoAxis {
+    /// Returns the conventional color of the axis
+    pub fn color(self) -> Color {
+        match self {
+            GizmoAxis::X => Color::RED,
+            GizmoAxis::Y => Color::GREEN,
+            GizmoAxis::Z => Color::BLUE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hit_test() {
+        let transform = Mat4::translate(Vec3::new(1.0, 0.0, 0.0));
+
+        {
+            let gizmo = Gizmo::new(GizmoMode::Translate, 1.0);
+            let ray = Ray::new(Vec3::new(1.5, 0.0, 10.0), Vec3::new(0.0, 0.0, -1.0));
+            assert_eq!(gizmo.hit_test(&transform, ray), Some(GizmoAxis::X));
+        }
+
+        {
+            let gizmo = Gizmo::new(GizmoMode::Scale, 1.0);
+            let ray = Ray::new(Vec3::new(1.0, 0.8, 10.0), Vec3::new(0.0, 0.0, -1.0));
+            assert_eq!(gizmo.hit_test(&transform, ray), Some(GizmoAxis::Y));
+        }
+
+        {
+            let gizmo = Gizmo::new(GizmoMode::Translate, 1.0);
+            let ray = Ray::new(Vec3::new(1.5, 0.5, 10.0), Vec3::new(0.0, 0.0, -1.0));
+            assert_eq!(gizmo.hit_test(&transform, ray), None);
+        }
+
+        {
+            // The ray is parallel to the X and Y rings, and crosses the Z ring on its edge
+            let gizmo = Gizmo::new(GizmoMode::Rotate, 1.0);
+            let ray = Ray::new(Vec3::new(2.0, 0.0, 10.0), Vec3::new(0.0, 0.0, -1.0));
+            assert_eq!(gizmo.hit_test(&transform, ray), Some(GizmoAxis::Z));
+        }
+
+        {
+            let gizmo = Gizmo::new(GizmoMode::Rotate, 1.0);
+            let ray = Ray::new(Vec3::new(1.0, 0.0, 10.0), Vec3::new(0.0, 0.0, -1.0));
+            assert_eq!(gizmo.hit_test(&transform, ray), None);
+        }
+    }
+}
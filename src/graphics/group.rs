@@ -1,3 +1,5 @@
+use std::num::NonZeroU64;
+
 use crate::graphics::{buffer::AnyBufferHandle, sampler::Sampler, texture::Texture};
 
 /// Describes a wrapper around the raw [`wgpu::BindGroup`]
@@ -69,6 +71,11 @@ pub struct BindGroupLayoutEntry {
     pub resource: LayoutResource,
     /// The shader stage the resource should be visible in
     pub access: ResourceAccess,
+    /// The minimum size in bytes a buffer resource must have, which is validated when the
+    /// bind group is created rather than on every draw call, ignored for other resources
+    ///
+    /// This should match the size of the buffer's type in the shader
+    pub min_binding_size: Option<NonZeroU64>,
 }
 
 /// Describes a wrapper around the actual GPU resource (buffer, sampler, texture)
@@ -76,6 +83,14 @@ pub struct BindGroupLayoutEntry {
 pub enum Resource<'a> {
     /// A buffer resource, holding a reference to an [`AnyBufferHandle`] trait object.
     Buffer(&'a dyn AnyBufferHandle),
+    /// A buffer resource bound with a dynamic offset, where only a window of `size` bytes
+    /// is visible to the shader, starting at the offset given when the bind group is used
+    DynamicBuffer {
+        /// The buffer, holding a reference to an [`AnyBufferHandle`] trait object
+        buffer: &'a dyn AnyBufferHandle,
+        /// The size of the window in bytes, usually the size of the uniform struct
+        size: u64,
+    },
     /// A sampler resource, holding a reference to a [`Sampler`]
     Sampler(&'a Sampler),
     /// A texture resource, holding a reference to a [`Texture`]
@@ -102,6 +117,23 @@ pub enum BufferConfig {
     /// The expected resource is a storage buffer,
     /// which is meant for large amounts of data
     Storage,
+    /// The expected resource is a storage buffer that shaders can write to as well,
+    /// e.g. to output the results of a compute shader
+    ///
+    /// Read-write storage buffers aren't allowed in vertex shaders, so they're only valid
+    /// with a [`ResourceAccess::Fragment`] access
+    StorageReadWrite,
+    /// The expected resource is a uniform buffer bound with a dynamic offset,
+    /// so a single buffer can hold the uniforms of many draws (e.g. per-object transforms)
+    ///
+    /// The offsets are passed to [`crate::graphics::pass::RenderPass::use_bind_group_with_offsets()`]
+    /// and must be multiples of the `min_uniform_buffer_offset_alignment` limit (usually 256 bytes)
+    UniformDynamic,
+    /// The expected resource is a storage buffer bound with a dynamic offset
+    ///
+    /// The offsets are passed to [`crate::graphics::pass::RenderPass::use_bind_group_with_offsets()`]
+    /// and must be multiples of the `min_storage_buffer_offset_alignment` limit (usually 256 bytes)
+    StorageDynamic,
 }
 
 /// Describes the configuration of an expected sampler resource
@@ -129,10 +161,15 @@ pub enum TextureConfig {
     D1(TextureKind),
     /// The expected resource is a 2D texture of any kind
     D2(TextureKind),
+    /// The expected resource is an array of 2D textures of any kind, a texture with multiple layers
+    D2Array(TextureKind),
     /// The expected resource is a 3D texture of any kind
     D3(TextureKind),
     /// The expected resource is a cubemap, which is formed by 6 textures
     Cubemap(TextureKind),
+    /// The expected resource is a multisampled 2D texture of any kind,
+    /// whose samples are read individually with `textureLoad` rather than filtered
+    D2Multisampled(TextureKind),
 }
 
 /// Describes the kind of the texture
@@ -201,7 +238,10 @@ impl<'a> BindGroupLayoutDescriptor<'a> {
             .map(|entry| wgpu::BindGroupLayoutEntry {
                 binding: entry.binding,
                 visibility: entry.access.raw(),
-                ty: entry.resource.raw(),
+                ty: match entry.resource {
+                    LayoutResource::Buffer(config) => config.raw_sized(entry.min_binding_size),
+                    resource => resource.raw(),
+                },
                 count: None,
             })
             .collect();
@@ -219,6 +259,15 @@ impl<'a> Resource<'a> {
     pub fn raw(&self) -> wgpu::BindingResource<'a> {
         match self {
             Resource::Buffer(buffer) => buffer.raw().as_entire_binding(),
+            Resource::DynamicBuffer { buffer, size } => {
+                wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: buffer.raw(),
+                    offset: 0,
+                    size: Some(
+                        wgpu::BufferSize::new(*size).expect("Dynamic buffer size cannot be zero!"),
+                    ),
+                })
+            }
             Resource::Sampler(sampler) => wgpu::BindingResource::Sampler(sampler.raw()),
             Resource::Texture(texture) => wgpu::BindingResource::TextureView(texture.view()),
         }
@@ -239,17 +288,28 @@ impl LayoutResource {
 impl BufferConfig {
     /// Maps the [`BufferConfig`] to the internal [`wgpu::BindingType::Buffer`]
     pub fn raw(&self) -> wgpu::BindingType {
-        match self {
-            BufferConfig::Uniform => wgpu::BindingType::Buffer {
-                ty: wgpu::BufferBindingType::Uniform,
-                has_dynamic_offset: false,
-                min_binding_size: None,
-            },
-            BufferConfig::Storage => wgpu::BindingType::Buffer {
-                ty: wgpu::BufferBindingType::Storage { read_only: true },
-                has_dynamic_offset: false,
-                min_binding_size: None,
-            },
+        self.raw_sized(None)
+    }
+
+    /// Maps the [`BufferConfig`] to the internal [`wgpu::BindingType::Buffer`],
+    /// with the minimum size in bytes a buffer must have to be bound
+    /// - `min_binding_size` -> the minimum size, `None` to defer the check until the draw call
+    pub fn raw_sized(&self, min_binding_size: Option<NonZeroU64>) -> wgpu::BindingType {
+        let (ty, has_dynamic_offset) = match self {
+            BufferConfig::Uniform => (wgpu::BufferBindingType::Uniform, false),
+            BufferConfig::Storage => (wgpu::BufferBindingType::Storage { read_only: true }, false),
+            BufferConfig::StorageReadWrite => {
+                (wgpu::BufferBindingType::Storage { read_only: false }, false)
+            }
+            BufferConfig::UniformDynamic => (wgpu::BufferBindingType::Uniform, true),
+            BufferConfig::StorageDynamic => {
+                (wgpu::BufferBindingType::Storage { read_only: true }, true)
+            }
+        };
+        wgpu::BindingType::Buffer {
+            ty,
+            has_dynamic_offset,
+            min_binding_size,
         }
     }
 }
@@ -278,22 +338,29 @@ impl TextureConfig {
         let sample_type = match self {
             TextureConfig::D1(kind) |
             TextureConfig::D2(kind) |
+            TextureConfig::D2Array(kind) |
             TextureConfig::D3(kind) |
             TextureConfig::Cubemap(kind) => match kind {
                 TextureKind::Image => wgpu::TextureSampleType::Float { filterable: true },
                 TextureKind::Depth => wgpu::TextureSampleType::Depth,
             },
+            TextureConfig::D2Multisampled(kind) => match kind {
+                TextureKind::Image => wgpu::TextureSampleType::Float { filterable: false },
+                TextureKind::Depth => wgpu::TextureSampleType::Depth,
+            },
         };
         let view_dimension = match self {
             TextureConfig::D1(_) => wgpu::TextureViewDimension::D1,
-            TextureConfig::D2(_) => wgpu::TextureViewDimension::D2,
+            TextureConfig::D2(_) |
+            TextureConfig::D2Multisampled(_) => wgpu::TextureViewDimension::D2,
+            TextureConfig::D2Array(_) => wgpu::TextureViewDimension::D2Array,
             TextureConfig::D3(_) => wgpu::TextureViewDimension::D3,
             TextureConfig::Cubemap(_) => wgpu::TextureViewDimension::Cube,
         };
         wgpu::BindingType::Texture {
             sample_type,
             view_dimension,
-            multisampled: false,
+            multisampled: matches!(self, TextureConfig::D2Multisampled(_)),
         }
     }
 }
@@ -310,12 +377,16 @@ impl ResourceAccess {
 }
 
 /// A builder utility for creating a [`BindGroup`] in a more ergonomic way.
+///
+/// Resources are assigned binding indices automatically in the order they're added,
+/// starting from `0`. If the shader skips indices, use [`BindGroupBuilder::binding()`]
+/// to place the next resource at an explicit index.
 #[derive(Debug)]
 pub struct BindGroupBuilder<'a> {
     /// The optional debugging label of this bind group
     label: Option<&'a str>,
-    /// The current location of a resource
-    cursor: u32,
+    /// The current location of a resource, `None` once the indices ran past `u32::MAX`
+    cursor: Option<u32>,
     /// The list of resources
     entries: Vec<BindGroupEntry<'a>>,
 }
@@ -325,7 +396,7 @@ impl<'a> BindGroupBuilder<'a> {
     pub fn new() -> Self {
         Self {
             label: None,
-            cursor: 0,
+            cursor: Some(0),
             entries: Vec::with_capacity(4),
         }
     }
@@ -337,34 +408,29 @@ impl<'a> BindGroupBuilder<'a> {
         self
     }
 
-    /// Adds a buffer resource.
-    pub fn add_buffer(mut self, buffer: &'a dyn AnyBufferHandle) -> Self {
-        self.entries.push(BindGroupEntry {
-            binding: self.cursor,
-            resource: Resource::Buffer(buffer),
-        });
-        self.cursor += 1;
+    /// Sets the binding index of the next resource.
+    ///
+    /// Resources added afterwards continue to be assigned indices automatically,
+    /// starting from `binding + 1`.
+    /// - `binding` -> the index matching the `@binding` attribute in the shader
+    pub fn binding(mut self, binding: u32) -> Self {
+        self.cursor = Some(binding);
         self
     }
 
+    /// Adds a buffer resource.
+    pub fn add_buffer(self, buffer: &'a dyn AnyBufferHandle) -> Self {
+        self.push(Resource::Buffer(buffer))
+    }
+
     /// Adds a sampler resource.
-    pub fn add_sampler(mut self, sampler: &'a Sampler) -> Self {
-        self.entries.push(BindGroupEntry {
-            binding: self.cursor,
-            resource: Resource::Sampler(sampler),
-        });
-        self.cursor += 1;
-        self
+    pub fn add_sampler(self, sampler: &'a Sampler) -> Self {
+        self.push(Resource::Sampler(sampler))
     }
 
     /// Adds a texture resource.
-    pub fn add_texture(mut self, texture: &'a Texture) -> Self {
-        self.entries.push(BindGroupEntry {
-            binding: self.cursor,
-            resource: Resource::Texture(texture),
-        });
-        self.cursor += 1;
-        self
+    pub fn add_texture(self, texture: &'a Texture) -> Self {
+        self.push(Resource::Texture(texture))
     }
 
     /// Builds a [`BindGroup`] and consumes this [`BindGroupBuilder`].
@@ -384,15 +450,43 @@ impl<'a> BindGroupBuilder<'a> {
         }
         .build(device)
     }
+
+    /// Adds a resource at the current cursor and advances the cursor.
+    ///
+    /// If the binding index is already taken or past `u32::MAX`, the caller thread panics.
+    fn push(mut self, resource: Resource<'a>) -> Self {
+        let Some(binding) = self.cursor else {
+            panic!(
+                "Couldn't add a resource to a `BindGroup`, binding index overflowed past u32::MAX!"
+            );
+        };
+        if self.entries.iter().any(|entry| entry.binding == binding) {
+            panic!("Couldn't add a resource to a `BindGroup`, binding {binding} is already taken!");
+        }
+
+        self.entries.push(BindGroupEntry { binding, resource });
+        self.cursor = binding.checked_add(1);
+        self
+    }
+}
+
+impl<'a> Default for BindGroupBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// A builder utility for creating a [`BindGroupLayout`] in a more ergonomic way.
+///
+/// Layout resources are assigned binding indices automatically in the order they're added,
+/// starting from `0`. If the shader skips indices, use [`BindGroupLayoutBuilder::binding()`]
+/// to place the next layout resource at an explicit index.
 #[derive(Debug)]
 pub struct BindGroupLayoutBuilder<'a> {
     /// The optional debugging label of this bind group layout
     label: Option<&'a str>,
-    /// The current location of a layout resource
-    cursor: u32,
+    /// The current location of a layout resource, `None` once the indices ran past `u32::MAX`
+    cursor: Option<u32>,
     /// The list of layout resources
     entries: Vec<BindGroupLayoutEntry>,
 }
@@ -402,7 +496,7 @@ impl<'a> BindGroupLayoutBuilder<'a> {
     pub fn new() -> Self {
         Self {
             label: None,
-            cursor: 0,
+            cursor: Some(0),
             entries: Vec::with_capacity(4),
         }
     }
@@ -414,124 +508,157 @@ impl<'a> BindGroupLayoutBuilder<'a> {
         self
     }
 
+    /// Sets the binding index of the next layout resource.
+    ///
+    /// Layout resources added afterwards continue to be assigned indices automatically,
+    /// starting from `binding + 1`.
+    /// - `binding` -> the index matching the `@binding` attribute in the shader
+    pub fn binding(mut self, binding: u32) -> Self {
+        self.cursor = Some(binding);
+        self
+    }
+
     /// Adds a uniform buffer layout resource.
     /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
-    pub fn add_uniform_buffer(mut self, access: ResourceAccess) -> Self {
-        self.entries.push(BindGroupLayoutEntry {
-            binding: self.cursor,
-            resource: LayoutResource::Buffer(BufferConfig::Uniform),
-            access,
-        });
-        self.cursor += 1;
-        self
+    pub fn add_uniform_buffer(self, access: ResourceAccess) -> Self {
+        self.push(LayoutResource::Buffer(BufferConfig::Uniform), access)
     }
 
     /// Adds a storage buffer layout resource.
     /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
-    pub fn add_storage_buffer(mut self, access: ResourceAccess) -> Self {
-        self.entries.push(BindGroupLayoutEntry {
-            binding: self.cursor,
-            resource: LayoutResource::Buffer(BufferConfig::Storage),
+    pub fn add_storage_buffer(self, access: ResourceAccess) -> Self {
+        self.push(LayoutResource::Buffer(BufferConfig::Storage), access)
+    }
+
+    /// Adds a uniform buffer layout resource that requires bound buffers to be at least `size` bytes,
+    /// see [`BindGroupLayoutEntry::min_binding_size`].
+    /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
+    /// - `size` -> the minimum size in bytes, usually the size of the uniform struct
+    pub fn add_uniform_buffer_sized(self, access: ResourceAccess, size: NonZeroU64) -> Self {
+        self.push_sized(
+            LayoutResource::Buffer(BufferConfig::Uniform),
             access,
-        });
-        self.cursor += 1;
-        self
+            Some(size),
+        )
     }
 
-    /// Adds a nearest sampler layout resource.
+    /// Adds a storage buffer layout resource that requires bound buffers to be at least `size` bytes,
+    /// see [`BindGroupLayoutEntry::min_binding_size`].
     /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
-    pub fn add_nearest_sampler(mut self, access: ResourceAccess) -> Self {
-        self.entries.push(BindGroupLayoutEntry {
-            binding: self.cursor,
-            resource: LayoutResource::Sampler(SamplerConfig::Nearest),
+    /// - `size` -> the minimum size in bytes
+    pub fn add_storage_buffer_sized(self, access: ResourceAccess, size: NonZeroU64) -> Self {
+        self.push_sized(
+            LayoutResource::Buffer(BufferConfig::Storage),
             access,
-        });
-        self.cursor += 1;
-        self
+            Some(size),
+        )
+    }
+
+    /// Adds a read-write storage buffer layout resource, see [`BufferConfig::StorageReadWrite`].
+    /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource,
+    ///   which can't include the vertex shader
+    ///
+    /// If `access` isn't [`ResourceAccess::Fragment`], the caller thread panics.
+    pub fn add_storage_buffer_rw(self, access: ResourceAccess) -> Self {
+        self.push(
+            LayoutResource::Buffer(BufferConfig::StorageReadWrite),
+            access,
+        )
+    }
+
+    /// Adds a uniform buffer layout resource that is bound with a dynamic offset,
+    /// see [`BufferConfig::UniformDynamic`].
+    /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
+    pub fn add_dynamic_uniform_buffer(self, access: ResourceAccess) -> Self {
+        self.push(LayoutResource::Buffer(BufferConfig::UniformDynamic), access)
+    }
+
+    /// Adds a storage buffer layout resource that is bound with a dynamic offset,
+    /// see [`BufferConfig::StorageDynamic`].
+    /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
+    pub fn add_dynamic_storage_buffer(self, access: ResourceAccess) -> Self {
+        self.push(LayoutResource::Buffer(BufferConfig::StorageDynamic), access)
+    }
+
+    /// Adds a nearest sampler layout resource.
+    /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
+    pub fn add_nearest_sampler(self, access: ResourceAccess) -> Self {
+        self.push(LayoutResource::Sampler(SamplerConfig::Nearest), access)
     }
 
     /// Adds a linear sampler layout resource.
     /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
-    pub fn add_linear_sampler(mut self, access: ResourceAccess) -> Self {
-        self.entries.push(BindGroupLayoutEntry {
-            binding: self.cursor,
-            resource: LayoutResource::Sampler(SamplerConfig::Linear),
-            access,
-        });
-        self.cursor += 1;
-        self
+    pub fn add_linear_sampler(self, access: ResourceAccess) -> Self {
+        self.push(LayoutResource::Sampler(SamplerConfig::Linear), access)
     }
 
     /// Adds a compare sampler layout resource.
     /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
-    pub fn add_compare_sampler(mut self, access: ResourceAccess) -> Self {
-        self.entries.push(BindGroupLayoutEntry {
-            binding: self.cursor,
-            resource: LayoutResource::Sampler(SamplerConfig::Compare),
-            access,
-        });
-        self.cursor += 1;
-        self
+    pub fn add_compare_sampler(self, access: ResourceAccess) -> Self {
+        self.push(LayoutResource::Sampler(SamplerConfig::Compare), access)
     }
 
     /// Adds a depth texture layout resource.
     /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
-    pub fn add_depth_texture(mut self, access: ResourceAccess) -> Self {
-        self.entries.push(BindGroupLayoutEntry {
-            binding: self.cursor,
-            resource: LayoutResource::Texture(TextureConfig::D2(TextureKind::Depth)),
+    pub fn add_depth_texture(self, access: ResourceAccess) -> Self {
+        self.push(
+            LayoutResource::Texture(TextureConfig::D2(TextureKind::Depth)),
             access,
-        });
-        self.cursor += 1;
-        self
+        )
     }
 
     /// Adds a 1D texture layout resource.
     /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
-    pub fn add_texture_1d(mut self, access: ResourceAccess) -> Self {
-        self.entries.push(BindGroupLayoutEntry {
-            binding: self.cursor,
-            resource: LayoutResource::Texture(TextureConfig::D1(TextureKind::Image)),
+    pub fn add_texture_1d(self, access: ResourceAccess) -> Self {
+        self.push(
+            LayoutResource::Texture(TextureConfig::D1(TextureKind::Image)),
             access,
-        });
-        self.cursor += 1;
-        self
+        )
     }
 
     /// Adds a 2D texture layout resource.
     /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
-    pub fn add_texture_2d(mut self, access: ResourceAccess) -> Self {
-        self.entries.push(BindGroupLayoutEntry {
-            binding: self.cursor,
-            resource: LayoutResource::Texture(TextureConfig::D2(TextureKind::Image)),
+    pub fn add_texture_2d(self, access: ResourceAccess) -> Self {
+        self.push(
+            LayoutResource::Texture(TextureConfig::D2(TextureKind::Image)),
             access,
-        });
-        self.cursor += 1;
-        self
+        )
+    }
+
+    /// Adds a 2D texture array layout resource.
+    /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
+    pub fn add_texture_2d_array(self, access: ResourceAccess) -> Self {
+        self.push(
+            LayoutResource::Texture(TextureConfig::D2Array(TextureKind::Image)),
+            access,
+        )
+    }
+
+    /// Adds a multisampled 2D texture layout resource.
+    /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
+    pub fn add_texture_2d_multisampled(self, access: ResourceAccess) -> Self {
+        self.push(
+            LayoutResource::Texture(TextureConfig::D2Multisampled(TextureKind::Image)),
+            access,
+        )
     }
 
     /// Adds a 3D texture layout resource.
     /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
-    pub fn add_texture_3d(mut self, access: ResourceAccess) -> Self {
-        self.entries.push(BindGroupLayoutEntry {
-            binding: self.cursor,
-            resource: LayoutResource::Texture(TextureConfig::D3(TextureKind::Image)),
+    pub fn add_texture_3d(self, access: ResourceAccess) -> Self {
+        self.push(
+            LayoutResource::Texture(TextureConfig::D3(TextureKind::Image)),
             access,
-        });
-        self.cursor += 1;
-        self
+        )
     }
 
     /// Adds a cubemap texture layout resource.
     /// - `access` -> the [`ResourceAccess`] specifying the shader accessibility of the resource
-    pub fn add_texture_cubemap(mut self, access: ResourceAccess) -> Self {
-        self.entries.push(BindGroupLayoutEntry {
-            binding: self.cursor,
-            resource: LayoutResource::Texture(TextureConfig::Cubemap(TextureKind::Image)),
+    pub fn add_texture_cubemap(self, access: ResourceAccess) -> Self {
+        self.push(
+            LayoutResource::Texture(TextureConfig::Cubemap(TextureKind::Image)),
             access,
-        });
-        self.cursor += 1;
-        self
+        )
     }
 
     /// Builds a [`BindGroupLayout`] and consumes this [`BindGroupLayoutBuilder`].
@@ -549,4 +676,173 @@ impl<'a> BindGroupLayoutBuilder<'a> {
         }
         .build(device)
     }
+
+    /// Adds a layout resource at the current cursor and advances the cursor.
+    ///
+    /// If the binding index is already taken, the caller thread panics.
+    fn push(self, resource: LayoutResource, access: ResourceAccess) -> Self {
+        self.push_sized(resource, access, None)
+    }
+
+    /// Adds a layout resource with a minimum binding size at the current cursor and advances the cursor.
+    ///
+    /// If the binding index is already taken or past `u32::MAX`,
+    /// or if a read-write resource is visible to the vertex shader, the caller thread panics.
+    fn push_sized(
+        mut self,
+        resource: LayoutResource,
+        access: ResourceAccess,
+        min_binding_size: Option<NonZeroU64>,
+    ) -> Self {
+        let Some(binding) = self.cursor else {
+            panic!(
+                "Couldn't add a layout resource to a `BindGroupLayout`, binding index overflowed past u32::MAX!"
+            );
+        };
+        if matches!(
+            resource,
+            LayoutResource::Buffer(BufferConfig::StorageReadWrite)
+        ) && !matches!(access, ResourceAccess::Fragment)
+        {
+            panic!(
+                "Couldn't add a layout resource to a `BindGroupLayout`, read-write storage at binding {binding} must only be accessible in the fragment shader, got {access:?}!"
+            );
+        }
+        if self.entries.iter().any(|entry| entry.binding == binding) {
+            panic!(
+                "Couldn't add a layout resource to a `BindGroupLayout`, binding {binding} is already taken!"
+            );
+        }
+
+        self.entries.push(BindGroupLayoutEntry {
+            binding,
+            resource,
+            access,
+            min_binding_size,
+        });
+        self.cursor = binding.checked_add(1);
+        self
+    }
+}
+
+impl<'a> Default for BindGroupLayoutBuilder<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binding() {
+        {
+            let builder = BindGroupLayoutBuilder::new()
+                .add_uniform_buffer(ResourceAccess::Vertex)
+                .binding(3)
+                .add_texture_2d(ResourceAccess::Fragment)
+                .add_linear_sampler(ResourceAccess::Fragment);
+            let bindings: Vec<_> = builder.entries.iter().map(|entry| entry.binding).collect();
+            assert_eq!(bindings, [0, 3, 4]);
+        }
+
+        {
+            let builder = BindGroupLayoutBuilder::new()
+                .binding(2)
+                .add_storage_buffer(ResourceAccess::Either)
+                .binding(0)
+                .add_uniform_buffer(ResourceAccess::Either)
+                .add_uniform_buffer(ResourceAccess::Either);
+            let bindings: Vec<_> = builder.entries.iter().map(|entry| entry.binding).collect();
+            assert_eq!(bindings, [2, 0, 1]);
+        }
+    }
+
+    #[test]
+    fn storage_access() {
+        let builder = BindGroupLayoutBuilder::new()
+            .add_storage_buffer(ResourceAccess::Either)
+            .add_storage_buffer_rw(ResourceAccess::Fragment);
+        let read_only: Vec<_> = builder
+            .entries
+            .iter()
+            .map(|entry| match entry.resource.raw() {
+                wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only },
+                    ..
+                } => read_only,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(read_only, [true, false]);
+    }
+
+    #[test]
+    fn dynamic_offset() {
+        let has_dynamic_offset = |config: BufferConfig| match config.raw() {
+            wgpu::BindingType::Buffer {
+                has_dynamic_offset, ..
+            } => has_dynamic_offset,
+            _ => unreachable!(),
+        };
+        assert!(!has_dynamic_offset(BufferConfig::Uniform));
+        assert!(!has_dynamic_offset(BufferConfig::Storage));
+        assert!(has_dynamic_offset(BufferConfig::UniformDynamic));
+        assert!(has_dynamic_offset(BufferConfig::StorageDynamic));
+    }
+
+    #[test]
+    fn multisampled() {
+        let builder = BindGroupLayoutBuilder::new()
+            .add_texture_2d(ResourceAccess::Fragment)
+            .add_texture_2d_multisampled(ResourceAccess::Fragment);
+        let multisampled: Vec<_> = builder
+            .entries
+            .iter()
+            .map(|entry| match entry.resource.raw() {
+                wgpu::BindingType::Texture { multisampled, .. } => multisampled,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(multisampled, [false, true]);
+    }
+
+    #[test]
+    #[should_panic(expected = "binding 1 is already taken")]
+    fn binding_collision() {
+        let _ = BindGroupLayoutBuilder::new()
+            .binding(1)
+            .add_uniform_buffer(ResourceAccess::Vertex)
+            .binding(0)
+            .add_texture_2d(ResourceAccess::Fragment)
+            .add_linear_sampler(ResourceAccess::Fragment);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "read-write storage at binding 1 must only be accessible in the fragment shader, got Vertex"
+    )]
+    fn read_write_vertex() {
+        let _ = BindGroupLayoutBuilder::new()
+            .add_storage_buffer(ResourceAccess::Vertex)
+            .add_storage_buffer_rw(ResourceAccess::Vertex);
+    }
+
+    #[test]
+    #[should_panic(expected = "must only be accessible in the fragment shader, got Either")]
+    fn read_write_either() {
+        let _ = BindGroupLayoutBuilder::new().add_storage_buffer_rw(ResourceAccess::Either);
+    }
+
+    #[test]
+    #[should_panic(expected = "binding index overflowed past u32::MAX")]
+    fn binding_overflow() {
+        // The last index is still usable, only the one after it overflows
+        let builder = BindGroupLayoutBuilder::new()
+            .binding(u32::MAX)
+            .add_uniform_buffer(ResourceAccess::Vertex);
+        assert_eq!(builder.entries[0].binding, u32::MAX);
+        let _ = builder.add_uniform_buffer(ResourceAccess::Vertex);
+    }
 }
@@ -1,3 +1,12 @@
+use std::sync::OnceLock;
+
+use bytemuck::Pod;
+
+use crate::math::{vec2::Vec2, vec3::Vec3, vec4::Vec4};
+
+#[cfg(feature = "derive")]
+pub use whirl_derive::Vertex;
+
 /// Describes a wrapper around the internal [`wgpu::VertexBufferLayout`]
 #[derive(Debug, Clone)]
 pub struct BufferLayout {
@@ -47,7 +56,76 @@ pub enum BufferAttributeFormat {
     U32,
 }
 
+/// Describes a vertex type whose buffer layout is known at compile time
+///
+/// Rather than implementing this by hand, enable the `derive` feature and use `#[derive(Vertex)]`,
+/// which keeps the layout in sync with the struct, the format of each attribute is inferred
+/// from the type of its field through [`VertexAttribute`]:
+/// ```rust
+/// # #[cfg(feature = "derive")]
+/// # mod example {
+/// # use bytemuck::{Pod, Zeroable};
+/// # use whirl::{graphics::layout::Vertex, math::{vec2::Vec2, vec3::Vec3}};
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Zeroable, Pod, Vertex)]
+/// struct MeshVertex {
+///     #[location(0)]
+///     position: Vec3,
+///     #[location(1)]
+///     uv: Vec2,
+/// }
+///
+/// # fn layout() {
+/// let layout = MeshVertex::geometry_layout();
+/// # }
+/// # }
+/// ```
+pub trait Vertex: Pod {
+    /// The attributes of the vertex, in the order of its fields
+    const ATTRIBUTES: &'static [BufferAttribute];
+
+    /// Builds a geometry [`BufferLayout`] of this vertex
+    ///
+    /// # Panics:
+    /// - if the attributes don't add up to the size of the vertex
+    fn geometry_layout() -> BufferLayout {
+        Self::checked_layout(create_geometry_layout(Self::ATTRIBUTES))
+    }
+
+    /// Builds an instance [`BufferLayout`] of this vertex
+    ///
+    /// # Panics:
+    /// - if the attributes don't add up to the size of the vertex
+    fn instance_layout() -> BufferLayout {
+        Self::checked_layout(create_instance_layout(Self::ATTRIBUTES))
+    }
+
+    #[doc(hidden)]
+    fn checked_layout(layout: BufferLayout) -> BufferLayout {
+        assert_eq!(
+            layout.stride(),
+            size_of::<Self>() as u64,
+            "The attributes of {} don't add up to its size",
+            std::any::type_name::<Self>()
+        );
+        layout
+    }
+}
+
+/// Describes a type that can be the field of a [`Vertex`], mapping it to the attribute it's uploaded as
+pub trait VertexAttribute {
+    /// The number of components of the attribute
+    const SIZE: u32;
+    /// The data type of the components of the attribute
+    const FORMAT: BufferAttributeFormat;
+}
+
 impl BufferLayout {
+    /// Returns the stride of the layout (the size of one item in bytes)
+    pub fn stride(&self) -> u64 {
+        self.raw.array_stride
+    }
+
     /// Consumes self and returns the raw [`wgpu::VertexBufferLayout`]
     pub fn raw(self) -> wgpu::VertexBufferLayout<'static> {
         self.raw
@@ -123,6 +201,21 @@ impl BufferLayoutKind {
     }
 }
 
+macro_rules! impl_vertex_attribute {
+    ($format:ident: $($ty:ty => $size:literal),* $(,)?) => {
+        $(
+            impl VertexAttribute for $ty {
+                const SIZE: u32 = $size;
+                const FORMAT: BufferAttributeFormat = BufferAttributeFormat::$format;
+            }
+        )*
+    };
+}
+
+impl_vertex_attribute!(F32: f32 => 1, [f32; 2] => 2, [f32; 3] => 3, [f32; 4] => 4, Vec2 => 2, Vec3 => 3, Vec4 => 4);
+impl_vertex_attribute!(I32: i32 => 1, [i32; 2] => 2, [i32; 3] => 3, [i32; 4] => 4);
+impl_vertex_attribute!(U32: u32 => 1, [u32; 2] => 2, [u32; 3] => 3, [u32; 4] => 4);
+
 impl BufferAttributeFormat {
     /// Returns the raw amount of bytes for the underlying attribute format
     ///
@@ -156,3 +249,136 @@ pub fn create_instance_layout(attributes: &'static [BufferAttribute]) -> BufferL
     }
     .build()
 }
+
+/// The attributes of a vertex with a position and a color
+///
+/// - location 0 -> the position (F32 x 3)
+/// - location 1 -> the color (F32 x 4)
+pub const POSITION_COLOR_ATTRIBUTES: &[BufferAttribute] = &[
+    BufferAttribute {
+        location: 0,
+        size: 3,
+        format: BufferAttributeFormat::F32,
+    },
+    BufferAttribute {
+        location: 1,
+        size: 4,
+        format: BufferAttributeFormat::F32,
+    },
+];
+
+/// The attributes of a vertex with a position and texture coordinates
+///
+/// - location 0 -> the position (F32 x 3)
+/// - location 1 -> the texture coordinates (F32 x 2)
+pub const POSITION_UV_ATTRIBUTES: &[BufferAttribute] = &[
+    BufferAttribute {
+        location: 0,
+        size: 3,
+        format: BufferAttributeFormat::F32,
+    },
+    BufferAttribute {
+        location: 1,
+        size: 2,
+        format: BufferAttributeFormat::F32,
+    },
+];
+
+/// The attributes of a vertex with a position, a normal and texture coordinates
+///
+/// - location 0 -> the position (F32 x 3)
+/// - location 1 -> the normal (F32 x 3)
+/// - location 2 -> the texture coordinates (F32 x 2)
+pub const POSITION_NORMAL_UV_ATTRIBUTES: &[BufferAttribute] = &[
+    BufferAttribute {
+        location: 0,
+        size: 3,
+        format: BufferAttributeFormat::F32,
+    },
+    BufferAttribute {
+        location: 1,
+        size: 3,
+        format: BufferAttributeFormat::F32,
+    },
+    BufferAttribute {
+        location: 2,
+        size: 2,
+        format: BufferAttributeFormat::F32,
+    },
+];
+
+/// Returns the geometry [`BufferLayout`] of [`POSITION_COLOR_ATTRIBUTES`], matching this WGSL input:
+/// ```wgsl
+/// struct VertexInput {
+///     @location(0) position: vec3<f32>,
+///     @location(1) color: vec4<f32>,
+/// }
+/// ```
+///
+/// The layout is built once and shared, clone it to hand it to a pipeline
+pub fn position_color_layout() -> &'static BufferLayout {
+    static LAYOUT: OnceLock<BufferLayout> = OnceLock::new();
+    LAYOUT.get_or_init(|| create_geometry_layout(POSITION_COLOR_ATTRIBUTES))
+}
+
+/// Returns the geometry [`BufferLayout`] of [`POSITION_UV_ATTRIBUTES`], matching this WGSL input:
+/// ```wgsl
+/// struct VertexInput {
+///     @location(0) position: vec3<f32>,
+///     @location(1) uv: vec2<f32>,
+/// }
+/// ```
+///
+/// The layout is built once and shared, clone it to hand it to a pipeline
+pub fn position_uv_layout() -> &'static BufferLayout {
+    static LAYOUT: OnceLock<BufferLayout> = OnceLock::new();
+    LAYOUT.get_or_init(|| create_geometry_layout(POSITION_UV_ATTRIBUTES))
+}
+
+/// Returns the geometry [`BufferLayout`] of [`POSITION_NORMAL_UV_ATTRIBUTES`], matching this WGSL input:
+/// ```wgsl
+/// struct VertexInput {
+///     @location(0) position: vec3<f32>,
+///     @location(1) normal: vec3<f32>,
+///     @location(2) uv: vec2<f32>,
+/// }
+/// ```
+///
+/// The layout is built once and shared, clone it to hand it to a pipeline
+pub fn position_normal_uv_layout() -> &'static BufferLayout {
+    static LAYOUT: OnceLock<BufferLayout> = OnceLock::new();
+    LAYOUT.get_or_init(|| create_geometry_layout(POSITION_NORMAL_UV_ATTRIBUTES))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_layouts() {
+        {
+            let layout = position_color_layout().clone().raw();
+            assert_eq!(layout.array_stride, 28);
+            assert_eq!(layout.attributes[1].offset, 12);
+            assert_eq!(layout.attributes[1].format, wgpu::VertexFormat::Float32x4);
+        }
+
+        {
+            let layout = position_uv_layout().clone().raw();
+            assert_eq!(layout.array_stride, 20);
+            assert_eq!(layout.attributes[1].format, wgpu::VertexFormat::Float32x2);
+        }
+
+        {
+            let layout = position_normal_uv_layout().clone().raw();
+            assert_eq!(layout.array_stride, 32);
+            assert_eq!(layout.attributes[2].shader_location, 2);
+            assert_eq!(layout.attributes[2].offset, 24);
+        }
+
+        {
+            // The layouts are built once
+            assert!(std::ptr::eq(position_uv_layout(), position_uv_layout()));
+        }
+    }
+}
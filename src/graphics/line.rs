@@ -0,0 +1,241 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    graphics::{
+        buffer::{BufferHandle, BufferUsage},
+        color::Color,
+        group::{BindGroup, BindGroupBuilder, BindGroupLayoutBuilder, ResourceAccess},
+        layout::{BufferAttribute, BufferAttributeFormat, create_instance_layout},
+        pass::RenderPass,
+        pipeline::{
+            Blend, CompareFunction, Cull, Draw, Pipeline, PipelineBuilder, PipelineLayoutBuilder,
+            Primitive,
+        },
+        shader::Shader,
+    },
+    math::{mat4::Mat4, vec2::Vec2, vec3::Vec3, vec4::Vec4},
+};
+
+/// The amount of line segments the instance buffer can initially hold
+const INITIAL_LINE_CAPACITY: usize = 64;
+
+/// The attributes of a [`LineSegment`] as seen by the line shader
+const LINE_ATTRIBUTES: &[BufferAttribute] = &[
+    BufferAttribute {
+        location: 0,
+        size: 3,
+        format: BufferAttributeFormat::F32,
+    },
+    BufferAttribute {
+        location: 1,
+        size: 3,
+        format: BufferAttributeFormat::F32,
+    },
+    BufferAttribute {
+        location: 2,
+        size: 4,
+        format: BufferAttributeFormat::F32,
+    },
+    BufferAttribute {
+        location: 3,
+        size: 1,
+        format: BufferAttributeFormat::F32,
+    },
+];
+
+/// Renders line segments of an arbitrary width in pixels.
+///
+/// Native line rendering ([`Primitive::LineList`], [`Draw::Wireframe`]) always produces lines
+/// that are 1 pixel wide, because setting the line width isn't supported portably across
+/// the backends wgpu targets.
+///
+/// Instead, the [`LineRenderer`] draws every segment as an instance of a quad, which gets
+/// expanded in screen space by the vertex shader so it faces the camera and keeps
+/// the same width in pixels regardless of its distance.
+///
+/// ```rust
+/// # use whirl::{
+/// #     graphics::{color::Color, line::LineRenderer, pass::RenderPass, pipeline::CompareFunction},
+/// #     math::{mat4::Mat4, vec2::Vec2, vec3::Vec3},
+/// # };
+/// # fn frame(
+/// #     device: &wgpu::Device,
+/// #     queue: &wgpu::Queue,
+/// #     pass: &mut RenderPass,
+/// #     projection: Mat4,
+/// #     view: Mat4,
+/// # ) {
+/// let mut lines = LineRenderer::new(device, Some(CompareFunction::Less));
+/// lines.add_line(Vec3::ZERO, Vec3::RIGHT, Color::RED, 3.0);
+/// lines.add_line(Vec3::ZERO, Vec3::UP, Color::GREEN, 3.0);
+/// lines.prepare(device, queue, projection * view, Vec2::new(1280.0, 720.0));
+/// // ...
+/// lines.draw(pass);
+/// # }
+/// ```
+///
+/// Lines are kept until [`LineRenderer::clear()`] is called, so debug visualizations
+/// usually clear and re-add their lines every frame.
+///
+/// Segments that cross behind the camera aren't clipped, so they may render incorrectly.
+#[derive(Debug)]
+pub struct LineRenderer {
+    lines: Vec<LineSegment>,
+    prepared_count: u32,
+    pipeline: Pipeline,
+    bind_group: BindGroup,
+    uniform_buffer: BufferHandle<LineUniform>,
+    instance_buffer: BufferHandle<LineSegment>,
+}
+
+/// Describes a single line segment drawn by a [`LineRenderer`]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+pub struct LineSegment {
+    /// The start point of the segment in world space
+    pub start: Vec3,
+    /// The end point of the segment in world space
+    pub end: Vec3,
+    /// The color of the segment
+    pub color: Vec4,
+    /// The width of the segment in pixels
+    pub width: f32,
+}
+
+/// The uniform data consumed by the line shader
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+struct LineUniform {
+    view_projection: Mat4,
+    viewport: Vec2,
+    _padding: Vec2,
+}
+
+impl LineRenderer {
+    /// Creates a new [`LineRenderer`] along with its internal pipeline.
+    /// - `device` -> the [`wgpu::Device`] needed to create the GPU resources
+    /// - `depth_function` -> the depth function to use, which has to be specified
+    ///   if the render pass the lines are drawn in has a depth attachment
+    pub fn new(device: &wgpu::Device, depth_function: Option<CompareFunction>) -> Self {
+        let shader = Shader::from_source(
+            device,
+            include_str!("shaders/line.wgsl"),
+            Some("Line shader"),
+        );
+        let bind_group_layout = BindGroupLayoutBuilder::new()
+            .label("Line bind group layout")
+            .add_uniform_buffer(ResourceAccess::Vertex)
+            .build(device);
+        let pipeline_layout = PipelineLayoutBuilder::new()
+            .label("Line pipeline layout")
+            .layout(&bind_group_layout)
+            .build(device);
+        let mut pipeline = PipelineBuilder::new()
+            .label("Line pipeline")
+            .shader(&shader)
+            .layout(&pipeline_layout)
+            .draw(Draw::Fill)
+            .cull(Cull::None)
+            .blend(Blend::Alpha)
+            .primitive(Primitive::TriangleList)
+            .instance_layout(create_instance_layout(LINE_ATTRIBUTES));
+        if let Some(depth_function) = depth_function {
+            pipeline = pipeline.depth_function(depth_function);
+        }
+
+        let uniform_buffer = BufferHandle::create(
+            device,
+            &[LineUniform::zeroed()],
+            BufferUsage::Uniform {
+                is_writable: true,
+                is_readable: false,
+            },
+            Some("Line uniform buffer"),
+        );
+        let bind_group = BindGroupBuilder::new()
+            .label("Line bind group")
+            .add_buffer(&uniform_buffer)
+            .build(&bind_group_layout, device);
+
+        Self {
+            lines: Vec::with_capacity(INITIAL_LINE_CAPACITY),
+            prepared_count: 0,
+            pipeline: pipeline.build(device),
+            bind_group,
+            uniform_buffer,
+            instance_buffer: BufferHandle::allocate(
+                device,
+                INITIAL_LINE_CAPACITY,
+                BufferUsage::Vertex {
+                    is_writable: true,
+                    is_readable: false,
+                },
+                Some("Line instance buffer"),
+            ),
+        }
+    }
+
+    /// Adds a line segment.
+    /// - `start` -> the start point of the segment in world space
+    /// - `end` -> the end point of the segment in world space
+    /// - `color` -> the color of the segment
+    /// - `width` -> the width of the segment in pixels
+    pub fn add_line(&mut self, start: Vec3, end: Vec3, color: Color, width: f32) {
+        self.lines.push(LineSegment {
+            start,
+            end,
+            color: color.as_vec(),
+            width,
+        });
+    }
+
+    /// Removes all of the line segments.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// Returns the line segments that will be drawn.
+    pub fn lines(&self) -> &[LineSegment] {
+        &self.lines
+    }
+
+    /// Uploads the line segments and the camera to the GPU, must be called before drawing.
+    /// - `view_projection` -> the combined view and projection matrix of the camera
+    /// - `viewport` -> the size of the render target in pixels
+    pub fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        view_projection: Mat4,
+        viewport: Vec2,
+    ) {
+        self.uniform_buffer.overwrite_and_flush(
+            device,
+            queue,
+            &[LineUniform {
+                view_projection,
+                viewport,
+                _padding: Vec2::ZERO,
+            }],
+        );
+        if !self.lines.is_empty() {
+            self.instance_buffer
+                .overwrite_and_flush(device, queue, &self.lines);
+        }
+        self.prepared_count = self.lines.len() as u32;
+    }
+
+    /// Draws the line segments that were uploaded by the last [`LineRenderer::prepare()`].
+    ///
+    /// This sets the pipeline, the bind group at slot 0 and the instance buffer at slot 0.
+    pub fn draw(&self, pass: &mut RenderPass) {
+        if self.prepared_count == 0 {
+            return;
+        }
+
+        pass.use_pipeline(&self.pipeline);
+        pass.use_bind_group(&self.bind_group);
+        pass.use_instance_buffer(0, &self.instance_buffer);
+        pass.draw(6, self.prepared_count);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::graphics::{
+    pass::RenderPass,
+    pipeline::{Pipeline, Stencil},
+};
+
+/// A stencil mask, which clips drawing to (or out of) an arbitrary shape such as a rounded rectangle
+///
+/// Masking takes 2 kinds of pipelines, both rendering to a pass with a depth/stencil attachment:
+/// - a mask pipeline built with [`crate::graphics::pipeline::Stencil::Write`], which draws the mask shape
+/// - masked pipelines built with [`crate::graphics::pipeline::Stencil::Inside`]
+///   (or [`crate::graphics::pipeline::Stencil::Outside`]), which draw the clipped content
+///
+/// ```rust
+/// # use whirl::graphics::{mask::StencilMask, pass::RenderPass, pipeline::Pipeline};
+/// # fn draw(
+/// #     mut pass: RenderPass<'_>,
+/// #     mask_pipeline: Pipeline,
+/// #     content_pipeline: Pipeline,
+/// #     content_vertex_count: u32,
+/// # ) {
+/// let mask = StencilMask::new(1);
+///
+/// mask.begin_mask(&mut pass, &mask_pipeline);
+/// pass.draw(6, 1); // The rounded rectangle
+///
+/// mask.draw_masked(&mut pass, &content_pipeline);
+/// pass.draw(content_vertex_count, 1); // The content, clipped to the rounded rectangle
+/// # }
+/// ```
+///
+/// Different masks in the same pass need different references, the stencil buffer is cleared
+/// to `0` when the pass begins, so `0` can't be used as a reference for [`crate::graphics::pipeline::Stencil::Inside`].
+#[derive(Debug, Clone, Copy)]
+pub struct StencilMask {
+    /// The value written to the stencil buffer by the mask shape, and tested by the masked draws
+    pub reference: u32,
+}
+
+impl StencilMask {
+    /// Creates a new stencil mask
+    /// - `reference` -> the value written to the stencil buffer by the mask shape, ranging from `1` to `255`
+    ///
+    /// # Panics:
+    /// If the reference is `0` (the cleared stencil buffer) or doesn't fit the 8 stencil bits.
+    pub fn new(reference: u32) -> Self {
+        assert!(
+            (1..=255).contains(&reference),
+            "Stencil mask reference must range from 1 to 255, got {}",
+            reference
+        );
+        Self { reference }
+    }
+
+    /// Prepares the pass for drawing the mask shape
+    /// - `pass` -> the render pass to draw the mask in
+    /// - `pipeline` -> the mask pipeline, built with [`crate::graphics::pipeline::Stencil::Write`]
+    ///
+    /// Every draw call after this one writes the mask reference to the stencil buffer
+    /// until another pipeline is set
+    ///
+    /// # Panics:
+    /// If the pipeline wasn't built with [`Stencil::Write`], since it wouldn't draw the mask.
+    pub fn begin_mask(&self, pass: &mut RenderPass, pipeline: &Pipeline) {
+        assert_eq!(
+            pipeline.stencil(),
+            Stencil::Write,
+            "A mask shape must be drawn with a `Stencil::Write` pipeline"
+        );
+        pass.use_pipeline(pipeline);
+        pass.use_stencil_reference(self.reference);
+    }
+
+    /// Prepares the pass for drawing content clipped by the mask
+    /// - `pass` -> the render pass the mask was drawn in
+    /// - `pipeline` -> the masked pipeline, built with [`crate::graphics::pipeline::Stencil::Inside`]
+    ///   or [`crate::graphics::pipeline::Stencil::Outside`]
+    ///
+    /// # Panics:
+    /// If the pipeline wasn't built with [`Stencil::Inside`] or [`Stencil::Outside`],
+    /// since it wouldn't test against the mask.
+    pub fn draw_masked(&self, pass: &mut RenderPass, pipeline: &Pipeline) {
+        assert!(
+            matches!(pipeline.stencil(), Stencil::Inside | Stencil::Outside),
+            "Masked content must be drawn with a `Stencil::Inside` or `Stencil::Outside` pipeline, got `Stencil::{:?}`",
+            pipeline.stencil()
+        );
+        pass.use_pipeline(pipeline);
+        pass.use_stencil_reference(self.reference);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_range() {
+        assert_eq!(StencilMask::new(1).reference, 1);
+        assert_eq!(StencilMask::new(255).reference, 255);
+    }
+
+    #[test]
+    #[should_panic(expected = "must range from 1 to 255, got 0")]
+    fn zero_reference() {
+        StencilMask::new(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "must range from 1 to 255, got 256")]
+    fn wide_reference() {
+        StencilMask::new(256);
+    }
+}
@@ -0,0 +1,103 @@
+use bytemuck::Pod;
+use wgpu::Device;
+
+use crate::graphics::buffer::{BufferHandle, BufferUsage};
+
+/// A piece of geometry, made of a vertex buffer and an optional index buffer
+///
+/// The vertex and index counts are taken from the buffers themselves,
+/// so they can't go out of sync with the data, see [`crate::graphics::pass::RenderPass::draw_mesh()`]
+#[derive(Debug)]
+pub struct Mesh<V: Pod> {
+    /// The vertex buffer of the mesh
+    vertices: BufferHandle<V>,
+    /// The optional index buffer of the mesh
+    indices: Option<BufferHandle<u32>>,
+}
+
+impl<V: Pod> Mesh<V> {
+    /// Creates a new non-indexed mesh
+    /// - `device` -> the [`wgpu::Device`] needed to create the buffers
+    /// - `vertices` -> the vertices of the mesh
+    /// - `label` -> an optional debugging label which is assigned to the buffers
+    ///
+    /// # Panics:
+    /// - If `vertices` is an empty slice.
+    pub fn new(device: &Device, vertices: &[V], label: Option<&str>) -> Self {
+        Self {
+            vertices: BufferHandle::create(
+                device,
+                vertices,
+                BufferUsage::Vertex {
+                    is_writable: true,
+                    is_readable: false,
+                },
+                label,
+            ),
+            indices: None,
+        }
+    }
+
+    /// Creates a new indexed mesh
+    /// - `device` -> the [`wgpu::Device`] needed to create the buffers
+    /// - `vertices` -> the vertices of the mesh
+    /// - `indices` -> the indices of the mesh, pointing into `vertices`
+    /// - `label` -> an optional debugging label which is assigned to the buffers
+    ///
+    /// # Panics:
+    /// - If `vertices` or `indices` is an empty slice.
+    pub fn indexed(device: &Device, vertices: &[V], indices: &[u32], label: Option<&str>) -> Self {
+        Self {
+            vertices: BufferHandle::create(
+                device,
+                vertices,
+                BufferUsage::Vertex {
+                    is_writable: true,
+                    is_readable: false,
+                },
+                label,
+            ),
+            indices: Some(BufferHandle::create(
+                device,
+                indices,
+                BufferUsage::Index {
+                    is_writable: true,
+                    is_readable: false,
+                },
+                label,
+            )),
+        }
+    }
+
+    /// Returns the vertex buffer
+    pub fn vertices(&self) -> &BufferHandle<V> {
+        &self.vertices
+    }
+
+    /// Returns the vertex buffer mutably, so the vertices can be updated
+    pub fn vertices_mut(&mut self) -> &mut BufferHandle<V> {
+        &mut self.vertices
+    }
+
+    /// Returns the index buffer, if the mesh is indexed
+    pub fn indices(&self) -> Option<&BufferHandle<u32>> {
+        self.indices.as_ref()
+    }
+
+    /// Returns the index buffer mutably, if the mesh is indexed
+    pub fn indices_mut(&mut self) -> Option<&mut BufferHandle<u32>> {
+        self.indices.as_mut()
+    }
+
+    /// Returns the vertex count
+    pub fn vertex_count(&self) -> u32 {
+        self.vertices.item_count() as u32
+    }
+
+    /// Returns the index count, if the mesh is indexed
+    pub fn index_count(&self) -> Option<u32> {
+        self.indices
+            .as_ref()
+            .map(|indices| indices.item_count() as u32)
+    }
+}
@@ -1,5 +1,14 @@
+use std::fmt::Debug;
+
+use bytemuck::Pod;
+
 use crate::graphics::{
-    buffer::AnyBufferHandle, color::Color, group::BindGroup, pipeline::Pipeline, texture::Texture,
+    buffer::AnyBufferHandle,
+    color::Color,
+    group::BindGroup,
+    mesh::Mesh,
+    pipeline::{IndexFormat, Pipeline},
+    texture::Texture,
 };
 
 /// Describes a wrapper around the raw [`wgpu::RenderPass`]
@@ -11,10 +20,57 @@ pub struct RenderPass<'a> {
 pub struct RenderPassDescriptor<'a> {
     /// The optional debugging label of this render pass
     pub label: Option<&'a str>,
-    /// The color attachment of this render pass
-    pub color_attachment: Option<Color>,
+    /// The operation performed on the color attachment when the render pass begins
+    pub color_load: LoadOp,
+    /// The texture view the frame is resolved into when the render pass ends
+    ///
+    /// This is only used for multisample anti-aliasing, where the frame the pass renders to
+    /// is a multisampled texture (see [`crate::graphics::texture::TextureDescriptor::samples`])
+    /// and the resolve target is the regular texture (e.g. the surface) that ends up being shown
+    pub resolve_target: Option<&'a wgpu::TextureView>,
     /// The depth/stencil attachment of this render pass
     pub depth_stencil_attachment: Option<&'a Texture>,
+    /// The operation performed on the depth attachment when the render pass begins
+    ///
+    /// A depth prepass followed by a color pass that reuses its depth buffer needs
+    /// [`DepthLoadOp::Load`] on the color pass, usually paired with a
+    /// [`crate::graphics::pipeline::CompareFunction::Equal`] (or `LessEqual`) depth function,
+    /// so only the fragments that won the prepass get shaded
+    pub depth_load: DepthLoadOp,
+    /// The operation performed on the depth attachment when the render pass ends
+    pub depth_store: StoreOp,
+}
+
+/// Specifies what happens to the contents of an attachment when a render pass begins
+#[derive(Debug, Clone, Copy)]
+pub enum LoadOp {
+    /// Clears the attachment to the specified color
+    Clear(Color),
+    /// Preserves the existing contents of the attachment,
+    /// which is needed when a pass has to build on the output of a previous pass
+    Load,
+}
+
+/// Specifies what happens to the contents of a depth attachment when a render pass begins
+#[derive(Debug, Clone, Copy)]
+pub enum DepthLoadOp {
+    /// Clears the depth attachment to the specified value,
+    /// usually `1.0`, or `0.0` for reversed-Z
+    Clear(f32),
+    /// Preserves the existing contents of the depth attachment,
+    /// which is needed when a pass reuses the depth of a previous pass
+    Load,
+}
+
+/// Specifies what happens to the contents of an attachment when a render pass ends
+#[derive(Debug, Clone, Copy, Default)]
+pub enum StoreOp {
+    /// Stores the contents of the attachment, so later passes can use them
+    #[default]
+    Store,
+    /// Discards the contents of the attachment, which can save bandwidth
+    /// if no later pass needs them
+    Discard,
 }
 
 impl<'a> RenderPass<'a> {
@@ -23,6 +79,15 @@ impl<'a> RenderPass<'a> {
         &self.raw
     }
 
+    /// Returns a mutable reference to the raw [`wgpu::RenderPass`],
+    /// an escape hatch for recording commands this wrapper doesn't provide (yet)
+    ///
+    /// Commands recorded through the raw pass bypass the wrapper entirely,
+    /// so keeping them consistent with the state set through the wrapper is up to the caller
+    pub fn raw_mut(&mut self) -> &mut wgpu::RenderPass<'a> {
+        &mut self.raw
+    }
+
     /// Sets a geometry buffer in a specific slot
     /// - `slot` -> the slot to use for this buffer
     /// - `buffer` -> the geometry buffer to set
@@ -30,11 +95,22 @@ impl<'a> RenderPass<'a> {
         self.raw.set_vertex_buffer(slot, buffer.as_slice());
     }
 
-    /// Sets an index buffer to the render pass
+    /// Sets an index buffer of 32-bit indices to the render pass
     /// - `buffer` -> the index buffer to set
     pub fn use_index_buffer(&mut self, buffer: &dyn AnyBufferHandle) {
-        self.raw
-            .set_index_buffer(buffer.as_slice(), wgpu::IndexFormat::Uint32);
+        self.use_index_buffer_with_format(buffer, IndexFormat::U32);
+    }
+
+    /// Sets an index buffer with indices of a specific format to the render pass
+    /// - `buffer` -> the index buffer to set
+    /// - `format` -> the format of the indices, which must match the `index_format`
+    ///   of the pipeline if it draws strips
+    pub fn use_index_buffer_with_format(
+        &mut self,
+        buffer: &dyn AnyBufferHandle,
+        format: IndexFormat,
+    ) {
+        self.raw.set_index_buffer(buffer.as_slice(), format.raw());
     }
 
     /// Sets an instance buffer in a specific slot
@@ -59,6 +135,30 @@ impl<'a> RenderPass<'a> {
         }
     }
 
+    /// Sets a [`BindGroup`] with dynamic buffers to a specific slot of the render pass
+    /// - `slot` -> the slot to use for this bind group, matching the `@group` attribute in the shader
+    /// - `bind_group` -> the bind group
+    /// - `offsets` -> the offsets in bytes of the dynamic buffers of the bind group, in binding order,
+    ///   see [`crate::graphics::group::BufferConfig::UniformDynamic`]
+    ///
+    /// Setting the same bind group with different offsets between draws lets a single
+    /// buffer hold the uniforms of every draw
+    pub fn use_bind_group_with_offsets(
+        &mut self,
+        slot: u32,
+        bind_group: &BindGroup,
+        offsets: &[u32],
+    ) {
+        self.raw.set_bind_group(slot, bind_group.raw(), offsets);
+    }
+
+    /// Sets the stencil reference value, which pipelines with a [`crate::graphics::pipeline::Stencil`] mode
+    /// test against and write to the stencil buffer
+    /// - `reference` -> the stencil reference value
+    pub fn use_stencil_reference(&mut self, reference: u32) {
+        self.raw.set_stencil_reference(reference);
+    }
+
     /// Sets a pipeline to the render pass
     /// - `pipeline` -> the pipeline to set
     pub fn use_pipeline(&mut self, pipeline: &Pipeline) {
@@ -92,6 +192,23 @@ impl<'a> RenderPass<'a> {
 
         self.raw.draw_indexed(0..index_count, 0, 0..instance_count);
     }
+
+    /// Binds the buffers of a [`Mesh`] and issues the draw call matching it
+    /// - `mesh` -> the mesh to draw, its vertex buffer is bound to slot `0`
+    /// - `instance_count` -> how many instances of the mesh to draw
+    ///
+    /// If the mesh is indexed, an indexed draw call is issued with the mesh's index count,
+    /// otherwise a regular draw call is issued with the mesh's vertex count
+    pub fn draw_mesh<V: Debug + Pod>(&mut self, mesh: &Mesh<V>, instance_count: u32) {
+        self.use_geometry_buffer(0, mesh.vertices());
+        match mesh.indices() {
+            Some(indices) => {
+                self.use_index_buffer(indices);
+                self.draw_indexed(indices.item_count() as u32, instance_count);
+            }
+            None => self.draw(mesh.vertex_count(), instance_count),
+        }
+    }
 }
 
 impl<'a> RenderPassDescriptor<'a> {
@@ -109,21 +226,18 @@ impl<'a> RenderPassDescriptor<'a> {
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                     view: frame,
                     ops: wgpu::Operations {
-                        load: match self.color_attachment {
-                            Some(color) => wgpu::LoadOp::Clear(color.raw()),
-                            None => wgpu::LoadOp::Load,
-                        },
+                        load: self.color_load.raw(),
                         store: wgpu::StoreOp::Store,
                     },
                     depth_slice: None,
-                    resolve_target: None,
+                    resolve_target: self.resolve_target,
                 })],
                 depth_stencil_attachment: self.depth_stencil_attachment.map(|depth_stencil| {
                     wgpu::RenderPassDepthStencilAttachment {
                         view: depth_stencil.view(),
                         depth_ops: Some(wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(1.0),
-                            store: wgpu::StoreOp::Store,
+                            load: self.depth_load.raw(),
+                            store: self.depth_store.raw(),
                         }),
                         stencil_ops: Some(wgpu::Operations {
                             load: wgpu::LoadOp::Clear(0),
@@ -137,3 +251,39 @@ impl<'a> RenderPassDescriptor<'a> {
         }
     }
 }
+
+impl LoadOp {
+    /// Maps the [`LoadOp`] to the internal [`wgpu::LoadOp`]
+    pub fn raw(self) -> wgpu::LoadOp<wgpu::Color> {
+        match self {
+            LoadOp::Clear(color) => wgpu::LoadOp::Clear(color.raw()),
+            LoadOp::Load => wgpu::LoadOp::Load,
+        }
+    }
+}
+
+impl DepthLoadOp {
+    /// Maps the [`DepthLoadOp`] to the internal [`wgpu::LoadOp`]
+    pub fn raw(self) -> wgpu::LoadOp<f32> {
+        match self {
+            DepthLoadOp::Clear(value) => wgpu::LoadOp::Clear(value),
+            DepthLoadOp::Load => wgpu::LoadOp::Load,
+        }
+    }
+}
+
+impl Default for DepthLoadOp {
+    fn default() -> Self {
+        Self::Clear(1.0)
+    }
+}
+
+impl StoreOp {
+    /// Maps the [`StoreOp`] to the internal [`wgpu::StoreOp`]
+    pub fn raw(self) -> wgpu::StoreOp {
+        match self {
+            StoreOp::Store => wgpu::StoreOp::Store,
+            StoreOp::Discard => wgpu::StoreOp::Discard,
+        }
+    }
+}
@@ -29,11 +29,20 @@ pub enum Cull {
 
 /// Specifies the winding order when drawing geometry which then determines
 /// if a face is in the front or in the back
-#[derive(Debug, Clone, Copy)]
+///
+/// The winding order is evaluated after projection, in normalized device coordinates
+/// where the Y axis points upwards.
+///
+/// The math module uses right-handed conventions ([`crate::math::mat4::Mat4::look_at()`]
+/// looks down the negative Z axis), so a mesh whose triangles are counter-clockwise
+/// when seen from the outside keeps them counter-clockwise on screen,
+/// which is why [`Winding::Reverse`] is the default.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum Winding {
     /// The face is considered front-facing if its indices are clockwise
     Clockwise,
     /// The face is considered front-facing if its indices are counter-clockwise
+    #[default]
     Reverse,
 }
 
@@ -48,6 +57,27 @@ pub enum Primitive {
     LineList,
     /// The most standard option, the geometry primitive is a triangle.
     TriangleList,
+    /// The geometry primitive is a line connected to the previous line.
+    ///
+    /// When drawing with an index buffer, the maximum index value of the [`IndexFormat`]
+    /// restarts the strip (primitive restart).
+    LineStrip,
+    /// The geometry primitive is a triangle sharing an edge with the previous triangle.
+    ///
+    /// When drawing with an index buffer, the maximum index value of the [`IndexFormat`]
+    /// restarts the strip (primitive restart).
+    TriangleStrip,
+}
+
+/// Specifies the format of the indices in an index buffer
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum IndexFormat {
+    /// 16-bit indices, which take half the memory but can only address 65535 vertices,
+    /// `0xFFFF` restarts a strip
+    U16,
+    /// 32-bit indices, `0xFFFFFFFF` restarts a strip
+    #[default]
+    U32,
 }
 
 /// Specifies the blending mode for the GPU during the rasterization stage
@@ -65,16 +95,37 @@ pub enum CompareFunction {
     Less,
     LessEqual,
     Equal,
+    NotEqual,
     GreaterEqual,
     Greater,
     Always,
     Never,
 }
 
+/// Specifies how a pipeline uses the stencil buffer of the depth/stencil attachment
+///
+/// The value compared against and written to the stencil buffer is the stencil reference
+/// of the render pass, see [`crate::graphics::pass::RenderPass::use_stencil_reference()`]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Stencil {
+    /// The stencil buffer is neither tested nor written
+    #[default]
+    Disabled,
+    /// The stencil reference is written to the stencil buffer wherever geometry is drawn,
+    /// no color is written, which is how a mask shape is drawn
+    Write,
+    /// Geometry is only drawn where the stencil buffer equals the stencil reference
+    Inside,
+    /// Geometry is only drawn where the stencil buffer doesn't equal the stencil reference
+    Outside,
+}
+
 /// Describes a wrapper around the raw [`wgpu::RenderPipeline`]
 #[derive(Debug)]
 pub struct Pipeline {
     raw: wgpu::RenderPipeline,
+    /// The stencil mode the pipeline was built with
+    stencil: Stencil,
 }
 
 /// Describes a wrapper around the raw `wgpu::PipelineLayout`
@@ -95,6 +146,9 @@ pub struct PipelineDescriptor<'a> {
     /// The pipeline layout specifying pipeline resources
     pub pipeline_layout: &'a PipelineLayout,
     /// The geometry layout, can be optional for procedurally generated geometry
+    ///
+    /// If both the geometry and the instance layouts are `None`, the pipeline takes no vertex buffers at all,
+    /// which is what full-screen passes driven by `@builtin(vertex_index)` need
     pub geometry_layout: Option<BufferLayout>,
     /// The instance layout, can be optional for cases where instanced rendering isn't needed
     pub instance_layout: Option<BufferLayout>,
@@ -106,10 +160,18 @@ pub struct PipelineDescriptor<'a> {
     pub winding: Winding,
     /// The geometry primitive
     pub primitive: Primitive,
+    /// The format of the index buffers drawn with this pipeline,
+    /// which strip primitives need to know to recognize the primitive restart index
+    pub index_format: IndexFormat,
     /// The blending mode of fragments
     pub blend: Blend,
     /// The depth function to enable depth testing
     pub depth_function: Option<CompareFunction>,
+    /// The stencil mode, which requires a depth/stencil attachment unless disabled
+    pub stencil: Stencil,
+    /// The amount of samples per pixel, which must match the sample count of the attachments
+    /// the pipeline renders to, `1` unless multisample anti-aliasing is used
+    pub samples: u32,
 }
 
 /// Describes a [`PipelineLayout`]
@@ -126,6 +188,11 @@ impl Pipeline {
     pub fn raw(&self) -> &wgpu::RenderPipeline {
         &self.raw
     }
+
+    /// Returns the [`Stencil`] mode the pipeline was built with
+    pub fn stencil(&self) -> Stencil {
+        self.stencil
+    }
 }
 
 impl PipelineLayout {
@@ -141,7 +208,7 @@ impl<'a> PipelineDescriptor<'a> {
     pub fn build(self, device: &wgpu::Device) -> Pipeline {
         let buffer_layouts: &[wgpu::VertexBufferLayout] =
             match (self.geometry_layout, self.instance_layout) {
-                (None, None) => panic!("Missing buffer layouts!"),
+                (None, None) => &[],
                 (None, Some(layout)) => &[layout.raw()],
                 (Some(layout), None) => &[layout.raw()],
                 (Some(geometry_layout), Some(instance_layout)) => {
@@ -165,7 +232,11 @@ impl<'a> PipelineDescriptor<'a> {
                     targets: &[Some(wgpu::ColorTargetState {
                         format: TextureFormat::Standard.raw(),
                         blend: Some(self.blend.raw()),
-                        write_mask: wgpu::ColorWrites::ALL,
+                        write_mask: if self.stencil == Stencil::Write {
+                            wgpu::ColorWrites::empty()
+                        } else {
+                            wgpu::ColorWrites::ALL
+                        },
                     })],
                 }),
                 primitive: wgpu::PrimitiveState {
@@ -174,7 +245,7 @@ impl<'a> PipelineDescriptor<'a> {
                     cull_mode: self.cull.raw(),
                     polygon_mode: self.draw.raw(),
                     strip_index_format: if self.primitive.raw().is_strip() {
-                        Some(wgpu::IndexFormat::Uint32)
+                        Some(self.index_format.raw())
                     } else {
                         None
                     },
@@ -182,20 +253,25 @@ impl<'a> PipelineDescriptor<'a> {
                     conservative: false,
                 },
                 multisample: wgpu::MultisampleState {
-                    count: 1,
+                    count: self.samples,
                     mask: !0,
                     alpha_to_coverage_enabled: false,
                 },
-                depth_stencil: self.depth_function.map(|mode| wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: mode.raw(),
-                    stencil: wgpu::StencilState::default(),
-                    bias: wgpu::DepthBiasState::default(),
-                }),
+                depth_stencil: match (self.depth_function, self.stencil) {
+                    (None, Stencil::Disabled) => None,
+                    (depth_function, stencil) => Some(wgpu::DepthStencilState {
+                        format: wgpu::TextureFormat::Depth24PlusStencil8,
+                        depth_write_enabled: depth_function.is_some(),
+                        depth_compare: depth_function
+                            .map_or(wgpu::CompareFunction::Always, CompareFunction::raw),
+                        stencil: stencil.raw(),
+                        bias: wgpu::DepthBiasState::default(),
+                    }),
+                },
                 multiview: None,
                 cache: None,
             }),
+            stencil: self.stencil,
         }
     }
 }
@@ -254,6 +330,18 @@ impl Primitive {
             Primitive::PointList => wgpu::PrimitiveTopology::PointList,
             Primitive::LineList => wgpu::PrimitiveTopology::LineList,
             Primitive::TriangleList => wgpu::PrimitiveTopology::TriangleList,
+            Primitive::LineStrip => wgpu::PrimitiveTopology::LineStrip,
+            Primitive::TriangleStrip => wgpu::PrimitiveTopology::TriangleStrip,
+        }
+    }
+}
+
+impl IndexFormat {
+    /// Maps the [`IndexFormat`] to the internal [`wgpu::IndexFormat`]
+    pub fn raw(self) -> wgpu::IndexFormat {
+        match self {
+            IndexFormat::U16 => wgpu::IndexFormat::Uint16,
+            IndexFormat::U32 => wgpu::IndexFormat::Uint32,
         }
     }
 }
@@ -275,6 +363,7 @@ impl CompareFunction {
             CompareFunction::Less => wgpu::CompareFunction::Less,
             CompareFunction::LessEqual => wgpu::CompareFunction::LessEqual,
             CompareFunction::Equal => wgpu::CompareFunction::Equal,
+            CompareFunction::NotEqual => wgpu::CompareFunction::NotEqual,
             CompareFunction::GreaterEqual => wgpu::CompareFunction::GreaterEqual,
             CompareFunction::Greater => wgpu::CompareFunction::Greater,
             CompareFunction::Always => wgpu::CompareFunction::Always,
@@ -283,6 +372,36 @@ impl CompareFunction {
     }
 }
 
+impl Stencil {
+    /// Maps the [`Stencil`] to the internal [`wgpu::StencilState`]
+    fn raw(self) -> wgpu::StencilState {
+        let (compare, pass_op) = match self {
+            Stencil::Disabled => return wgpu::StencilState::default(),
+            Stencil::Write => (
+                wgpu::CompareFunction::Always,
+                wgpu::StencilOperation::Replace,
+            ),
+            Stencil::Inside => (wgpu::CompareFunction::Equal, wgpu::StencilOperation::Keep),
+            Stencil::Outside => (
+                wgpu::CompareFunction::NotEqual,
+                wgpu::StencilOperation::Keep,
+            ),
+        };
+        let face = wgpu::StencilFaceState {
+            compare,
+            fail_op: wgpu::StencilOperation::Keep,
+            depth_fail_op: wgpu::StencilOperation::Keep,
+            pass_op,
+        };
+        wgpu::StencilState {
+            front: face,
+            back: face,
+            read_mask: 0xFF,
+            write_mask: if self == Stencil::Write { 0xFF } else { 0x00 },
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct PipelineBuilder<'a> {
     label: Option<&'a str>,
@@ -291,9 +410,12 @@ pub struct PipelineBuilder<'a> {
     draw: Option<Draw>,
     cull: Option<Cull>,
     depth_function: Option<CompareFunction>,
+    stencil: Option<Stencil>,
     blend: Option<Blend>,
     winding: Option<Winding>,
     primitive: Option<Primitive>,
+    index_format: Option<IndexFormat>,
+    samples: Option<u32>,
     geometry_layout: Option<BufferLayout>,
     instance_layout: Option<BufferLayout>,
 }
@@ -333,6 +455,11 @@ impl<'a> PipelineBuilder<'a> {
         self
     }
 
+    pub fn stencil(mut self, stencil: Stencil) -> Self {
+        self.stencil = Some(stencil);
+        self
+    }
+
     pub fn blend(mut self, blend: Blend) -> Self {
         self.blend = Some(blend);
         self
@@ -348,6 +475,16 @@ impl<'a> PipelineBuilder<'a> {
         self
     }
 
+    pub fn index_format(mut self, index_format: IndexFormat) -> Self {
+        self.index_format = Some(index_format);
+        self
+    }
+
+    pub fn samples(mut self, samples: u32) -> Self {
+        self.samples = Some(samples);
+        self
+    }
+
     pub fn geometry_layout(mut self, layout: BufferLayout) -> Self {
         self.geometry_layout = Some(layout);
         self
@@ -369,8 +506,11 @@ impl<'a> PipelineBuilder<'a> {
             cull: self.cull.expect("Missing cull mode in pipeline"),
             blend: self.blend.expect("Missing blend mode in pipeline"),
             depth_function: self.depth_function,
-            winding: self.winding.unwrap_or(Winding::Clockwise),
+            stencil: self.stencil.unwrap_or_default(),
+            winding: self.winding.unwrap_or_default(),
             primitive: self.primitive.unwrap_or(Primitive::TriangleList),
+            index_format: self.index_format.unwrap_or_default(),
+            samples: self.samples.unwrap_or(1),
         }
         .build(device)
     }
@@ -405,3 +545,64 @@ impl<'a> PipelineLayoutBuilder<'a> {
         .build(device)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{mat4::Mat4, vec3::Vec3, vec4::Vec4};
+    use std::f32::consts::PI;
+
+    /// Projects a triangle to normalized device coordinates and returns its winding
+    fn projected_winding(view_projection: Mat4, triangle: [Vec3; 3]) -> Winding {
+        let [a, b, c] = triangle.map(|vertex| {
+            let clip = view_projection * Vec4::from(vertex);
+            (clip.x / clip.w, clip.y / clip.w)
+        });
+        let area = (b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1);
+        if area > 0.0 {
+            Winding::Reverse
+        } else {
+            Winding::Clockwise
+        }
+    }
+
+    #[test]
+    fn default_winding() {
+        // The faces of a unit cube, counter-clockwise when seen from the outside
+        let front = [
+            Vec3::new(-1.0, -1.0, 1.0),
+            Vec3::new(1.0, -1.0, 1.0),
+            Vec3::new(1.0, 1.0, 1.0),
+        ];
+        let back = [
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(-1.0, -1.0, -1.0),
+            Vec3::new(-1.0, 1.0, -1.0),
+        ];
+        let projection = Mat4::perspective(PI / 2.0, 1.0, 0.1, 100.0);
+
+        {
+            let view = Mat4::look_at(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::UP);
+            assert_eq!(
+                projected_winding(projection * view, front),
+                Winding::default()
+            );
+            assert_ne!(
+                projected_winding(projection * view, back),
+                Winding::default()
+            );
+        }
+
+        {
+            let view = Mat4::look_at(Vec3::new(0.0, 0.0, -5.0), Vec3::ZERO, Vec3::UP);
+            assert_eq!(
+                projected_winding(projection * view, back),
+                Winding::default()
+            );
+            assert_ne!(
+                projected_winding(projection * view, front),
+                Winding::default()
+            );
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 /// Describes a sampler wrapper around the raw [`wgpu::Sampler`]
 #[derive(Debug)]
 pub struct Sampler {
@@ -14,6 +16,17 @@ pub struct SamplerDescriptor<'a> {
     pub wrapping: TextureWrapping,
     /// The texture filtering more to use
     pub filtering: TextureFiltering,
+    /// The filtering mode to use between mip levels, `None` uses the same mode as `filtering`
+    ///
+    /// Linear `filtering` with a linear mip filter gives trilinear filtering,
+    /// while nearest `filtering` with a linear mip filter keeps pixel art crisp up close
+    /// but still blends smoothly between mip levels
+    pub mip_filter: Option<TextureFiltering>,
+    /// The maximum anisotropy level, ranging from `1` (no anisotropic filtering) to `16`
+    ///
+    /// Anisotropic filtering keeps textures viewed at steep angles sharp,
+    /// it requires linear filtering for both `filtering` and `mip_filter`
+    pub anisotropy: u16,
 }
 
 /// Describes a texture wrapping configuration
@@ -47,6 +60,25 @@ pub enum TextureFiltering {
     Linear,
 }
 
+/// Specifies an invalid sampler configuration, caught before it reaches wgpu
+#[derive(Debug, Clone, Copy)]
+pub enum SamplerError {
+    /// The anisotropy level is outside of the `1..=16` range
+    AnisotropyOutOfRange {
+        /// The requested anisotropy level
+        anisotropy: u16,
+    },
+    /// Anisotropic filtering was requested with a nearest filtering mode
+    AnisotropyWithoutLinearFiltering {
+        /// The requested anisotropy level
+        anisotropy: u16,
+        /// The requested texture filtering mode
+        filtering: TextureFiltering,
+        /// The requested mip filtering mode
+        mip_filter: TextureFiltering,
+    },
+}
+
 impl Sampler {
     /// Returns a reference to the raw [`wgpu::Sampler`]
     pub fn raw(&self) -> &wgpu::Sampler {
@@ -55,10 +87,44 @@ impl Sampler {
 }
 
 impl<'a> SamplerDescriptor<'a> {
+    /// Checks whether the sampler configuration is valid, returns a [`SamplerError`] describing
+    /// the first invalid combination of fields otherwise
+    pub fn validate(&self) -> Result<(), SamplerError> {
+        if !(1..=16).contains(&self.anisotropy) {
+            return Err(SamplerError::AnisotropyOutOfRange {
+                anisotropy: self.anisotropy,
+            });
+        }
+
+        let mip_filter = self.mip_filter.unwrap_or(self.filtering);
+        let is_linear = matches!(
+            (self.filtering, mip_filter),
+            (TextureFiltering::Linear, TextureFiltering::Linear)
+        );
+        if self.anisotropy > 1 && !is_linear {
+            return Err(SamplerError::AnisotropyWithoutLinearFiltering {
+                anisotropy: self.anisotropy,
+                filtering: self.filtering,
+                mip_filter,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Builds a [`Sampler`]
+    ///
+    /// # Panics:
+    /// If the configuration is invalid (see [`SamplerDescriptor::validate()`]),
+    /// the caller thread panics with a description of the problem.
     pub fn build(self, device: &wgpu::Device) -> Sampler {
+        if let Err(error) = self.validate() {
+            panic!("{}", error);
+        }
+
         let wrapping = self.wrapping.raw();
         let filtering = self.filtering.raw();
+        let mip_filter = self.mip_filter.unwrap_or(self.filtering).raw();
         Sampler {
             raw: device.create_sampler(&wgpu::SamplerDescriptor {
                 label: self.label,
@@ -67,11 +133,11 @@ impl<'a> SamplerDescriptor<'a> {
                 address_mode_w: wrapping,
                 mag_filter: filtering,
                 min_filter: filtering,
-                mipmap_filter: filtering,
+                mipmap_filter: mip_filter,
                 lod_min_clamp: 0.0,
                 lod_max_clamp: 100.0,
                 compare: None,
-                anisotropy_clamp: 1,
+                anisotropy_clamp: self.anisotropy,
                 border_color: if let TextureWrapping::ClampToBorder = self.wrapping {
                     Some(wgpu::SamplerBorderColor::OpaqueBlack)
                 } else {
@@ -103,3 +169,86 @@ impl TextureFiltering {
         }
     }
 }
+
+impl fmt::Display for SamplerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SamplerError::AnisotropyOutOfRange { anisotropy } => {
+                write!(
+                    f,
+                    "Invalid sampler anisotropy {}:\n\tAnisotropy must range from 1 to 16",
+                    anisotropy
+                )
+            }
+            SamplerError::AnisotropyWithoutLinearFiltering {
+                anisotropy,
+                filtering,
+                mip_filter,
+            } => {
+                write!(
+                    f,
+                    "Invalid sampler anisotropy {}:\n\tAnisotropic filtering requires linear filtering, got {:?} filtering and {:?} mip filtering",
+                    anisotropy, filtering, mip_filter
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptor(
+        filtering: TextureFiltering,
+        mip_filter: Option<TextureFiltering>,
+        anisotropy: u16,
+    ) -> SamplerDescriptor<'static> {
+        SamplerDescriptor {
+            label: None,
+            wrapping: TextureWrapping::Repeat,
+            filtering,
+            mip_filter,
+            anisotropy,
+        }
+    }
+
+    #[test]
+    fn validate() {
+        {
+            let sampler = descriptor(TextureFiltering::Nearest, None, 1);
+            assert!(sampler.validate().is_ok());
+        }
+
+        {
+            let sampler = descriptor(TextureFiltering::Linear, None, 16);
+            assert!(sampler.validate().is_ok());
+        }
+
+        {
+            let sampler = descriptor(TextureFiltering::Linear, None, 0);
+            assert!(matches!(
+                sampler.validate(),
+                Err(SamplerError::AnisotropyOutOfRange { anisotropy: 0 })
+            ));
+        }
+
+        {
+            let sampler = descriptor(TextureFiltering::Linear, None, 17);
+            assert!(sampler.validate().is_err());
+        }
+
+        {
+            let sampler = descriptor(TextureFiltering::Nearest, None, 4);
+            assert!(matches!(
+                sampler.validate(),
+                Err(SamplerError::AnisotropyWithoutLinearFiltering { .. })
+            ));
+        }
+
+        {
+            let sampler = descriptor(TextureFiltering::Linear, Some(TextureFiltering::Nearest), 4);
+            assert!(sampler.validate().is_err());
+        }
+    }
+}
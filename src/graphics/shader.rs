@@ -1,10 +1,17 @@
 use std::{borrow::Cow, error::Error, fs, path::Path};
 
+use crate::graphics::group::{
+    BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BufferConfig, LayoutResource,
+    ResourceAccess, SamplerConfig, TextureConfig, TextureKind,
+};
+
 /// Describes a wrapper around [`wgpu::ShaderModule`]
 #[derive(Debug)]
 pub struct Shader {
     /// The internal [`wgpu::ShaderModule`]
     raw: wgpu::ShaderModule,
+    /// The WGSL source code of the shader, kept around for reflection
+    source: String,
 }
 
 impl Shader {
@@ -18,15 +25,374 @@ impl Shader {
         label: Option<&str>,
     ) -> Result<Self, Box<dyn Error>> {
         let source = fs::read_to_string(path)?;
+        Ok(Self::from_source(device, &source, label))
+    }
+
+    /// Creates a new shader from WGSL source code:
+    /// - `device` is the raw [`wgpu::Device`]
+    /// - `source` is the WGSL source code of the shader
+    /// - `label` is an optional debugging label which is assigned to the shader unit
+    pub fn from_source(device: &wgpu::Device, source: &str, label: Option<&str>) -> Self {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label,
-            source: wgpu::ShaderSource::Wgsl(Cow::Owned(source)),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
         });
-        Ok(Self { raw: shader })
+        Self {
+            raw: shader,
+            source: source.to_owned(),
+        }
     }
 
     /// Returns the raw [`wgpu::ShaderModule`] to use in pipeline creation
     pub fn raw(&self) -> &wgpu::ShaderModule {
         &self.raw
     }
+
+    /// Returns the WGSL source code of the shader
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Creates the bind group layouts declared by the shader through reflection
+    /// - `device` is the raw [`wgpu::Device`]
+    ///
+    /// The returned list is indexed by the `@group` attribute, so it can be passed
+    /// directly to a pipeline layout. Groups that the shader skips produce empty layouts.
+    ///
+    /// The shader accessibility of each resource is derived from the entry points that use it,
+    /// resources that no entry point uses are accessible by either shader,
+    /// except for read-write storage buffers which are only ever accessible in the fragment shader.
+    ///
+    /// Samplers are always reflected as [`SamplerConfig::Linear`] (or [`SamplerConfig::Compare`]),
+    /// since the shader doesn't specify whether a sampler filters or not.
+    ///
+    /// Returns an error if the shader fails to parse or validate, if it declares
+    /// a resource that can't be described by a [`LayoutResource`],
+    /// or if its vertex shader uses a read-write storage buffer.
+    pub fn reflect_layouts(
+        &self,
+        device: &wgpu::Device,
+    ) -> Result<Vec<BindGroupLayout>, Box<dyn Error>> {
+        Ok(Self::reflect_entries(&self.source)?
+            .iter()
+            .map(|entries| {
+                BindGroupLayoutDescriptor {
+                    label: None,
+                    entries,
+                }
+                .build(device)
+            })
+            .collect())
+    }
+
+    /// Parses the WGSL source and maps its resources to layout entries, indexed by their group
+    fn reflect_entries(source: &str) -> Result<Vec<Vec<BindGroupLayoutEntry>>, Box<dyn Error>> {
+        let module = naga::front::wgsl::parse_str(source)?;
+        let info = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)?;
+
+        let mut groups: Vec<Vec<BindGroupLayoutEntry>> = Vec::new();
+        for (handle, global) in module.global_variables.iter() {
+            let Some(binding) = &global.binding else {
+                continue;
+            };
+            let resource = Self::reflect_resource(&module, global)?;
+
+            let (mut vertex, mut fragment) = (false, false);
+            for (index, entry_point) in module.entry_points.iter().enumerate() {
+                if info.get_entry_point(index)[handle].is_empty() {
+                    continue;
+                }
+                match entry_point.stage {
+                    naga::ShaderStage::Vertex => vertex = true,
+                    naga::ShaderStage::Fragment => fragment = true,
+                    stage => {
+                        return Err(format!(
+                            "Resource at @group({}) @binding({}) is used in an unsupported {:?} stage",
+                            binding.group, binding.binding, stage
+                        )
+                        .into());
+                    }
+                }
+            }
+            // Read-write storage can't be visible to the vertex shader,
+            // see `BufferConfig::StorageReadWrite`
+            let read_write = matches!(
+                resource,
+                LayoutResource::Buffer(BufferConfig::StorageReadWrite)
+            );
+            if read_write && vertex {
+                return Err(format!(
+                    "Read-write storage at @group({}) @binding({}) is used in the unsupported Vertex stage",
+                    binding.group, binding.binding
+                )
+                .into());
+            }
+            let access = match (vertex, fragment) {
+                _ if read_write => ResourceAccess::Fragment,
+                (true, false) => ResourceAccess::Vertex,
+                (false, true) => ResourceAccess::Fragment,
+                _ => ResourceAccess::Either,
+            };
+
+            let group = binding.group as usize;
+            if groups.len() <= group {
+                groups.resize_with(group + 1, Vec::new);
+            }
+            groups[group].push(BindGroupLayoutEntry {
+                binding: binding.binding,
+                resource,
+                access,
+                min_binding_size: None,
+            });
+        }
+        Ok(groups)
+    }
+
+    /// Maps a resource declared in the shader to a [`LayoutResource`]
+    fn reflect_resource(
+        module: &naga::Module,
+        global: &naga::GlobalVariable,
+    ) -> Result<LayoutResource, Box<dyn Error>> {
+        let unsupported = |what: &str| -> Box<dyn Error> {
+            format!("Unsupported {} in shader: {:?}", what, global.name).into()
+        };
+
+        match global.space {
+            naga::AddressSpace::Uniform => {
+                return Ok(LayoutResource::Buffer(BufferConfig::Uniform));
+            }
+            naga::AddressSpace::Storage { access } => {
+                let config = if access.contains(naga::StorageAccess::STORE) {
+                    BufferConfig::StorageReadWrite
+                } else {
+                    BufferConfig::Storage
+                };
+                return Ok(LayoutResource::Buffer(config));
+            }
+            naga::AddressSpace::Handle => (),
+            _ => return Err(unsupported("address space")),
+        }
+
+        match module.types[global.ty].inner {
+            naga::TypeInner::Sampler { comparison: false } => {
+                Ok(LayoutResource::Sampler(SamplerConfig::Linear))
+            }
+            naga::TypeInner::Sampler { comparison: true } => {
+                Ok(LayoutResource::Sampler(SamplerConfig::Compare))
+            }
+            naga::TypeInner::Image {
+                dim,
+                arrayed,
+                class,
+            } => {
+                let (kind, multi) = match class {
+                    naga::ImageClass::Sampled {
+                        kind: naga::ScalarKind::Float,
+                        multi,
+                    } => (TextureKind::Image, multi),
+                    naga::ImageClass::Depth { multi } => (TextureKind::Depth, multi),
+                    _ => return Err(unsupported("texture class")),
+                };
+                Ok(LayoutResource::Texture(match (dim, arrayed, multi) {
+                    (naga::ImageDimension::D1, false, false) => TextureConfig::D1(kind),
+                    (naga::ImageDimension::D2, false, false) => TextureConfig::D2(kind),
+                    (naga::ImageDimension::D2, true, false) => TextureConfig::D2Array(kind),
+                    (naga::ImageDimension::D3, false, false) => TextureConfig::D3(kind),
+                    (naga::ImageDimension::Cube, false, false) => TextureConfig::Cubemap(kind),
+                    (naga::ImageDimension::D2, false, true) => TextureConfig::D2Multisampled(kind),
+                    _ => return Err(unsupported("texture dimension")),
+                }))
+            }
+            _ => Err(unsupported("resource type")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reflect_entries() {
+        {
+            let groups = Shader::reflect_entries(
+                "@group(0) @binding(0) var<uniform> transform: mat4x4<f32>;
+                @group(0) @binding(1) var<storage, read> lights: array<vec4<f32>>;
+                @group(2) @binding(3) var image: texture_2d<f32>;
+                @group(2) @binding(4) var image_sampler: sampler;
+
+                @vertex
+                fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+                    return transform * vec4<f32>(f32(index), 0.0, 0.0, 1.0);
+                }
+
+                @fragment
+                fn fs_main() -> @location(0) vec4<f32> {
+                    return lights[0] * textureSample(image, image_sampler, vec2<f32>(0.5));
+                }",
+            )
+            .unwrap();
+            assert_eq!(groups.len(), 3);
+            assert!(groups[1].is_empty());
+
+            let transform = &groups[0][0];
+            assert_eq!(transform.binding, 0);
+            assert!(matches!(
+                transform.resource,
+                LayoutResource::Buffer(BufferConfig::Uniform)
+            ));
+            assert!(matches!(transform.access, ResourceAccess::Vertex));
+
+            let lights = &groups[0][1];
+            assert_eq!(lights.binding, 1);
+            assert!(matches!(
+                lights.resource,
+                LayoutResource::Buffer(BufferConfig::Storage)
+            ));
+            assert!(matches!(lights.access, ResourceAccess::Fragment));
+
+            let image = &groups[2][0];
+            assert_eq!(image.binding, 3);
+            assert!(matches!(
+                image.resource,
+                LayoutResource::Texture(TextureConfig::D2(TextureKind::Image))
+            ));
+            let sampler = &groups[2][1];
+            assert_eq!(sampler.binding, 4);
+            assert!(matches!(
+                sampler.resource,
+                LayoutResource::Sampler(SamplerConfig::Linear)
+            ));
+        }
+
+        {
+            // Resources used by both stages (or by neither) are accessible by either shader
+            let groups = Shader::reflect_entries(
+                "@group(0) @binding(0) var<uniform> color: vec4<f32>;
+                @group(0) @binding(1) var<uniform> unused: vec4<f32>;
+
+                @vertex
+                fn vs_main() -> @builtin(position) vec4<f32> {
+                    return color;
+                }
+
+                @fragment
+                fn fs_main() -> @location(0) vec4<f32> {
+                    return color;
+                }",
+            )
+            .unwrap();
+            assert!(matches!(groups[0][0].access, ResourceAccess::Either));
+            assert!(matches!(groups[0][1].access, ResourceAccess::Either));
+        }
+
+        {
+            let groups = Shader::reflect_entries(
+                "@group(0) @binding(0) var<storage, read_write> particles: array<vec4<f32>>;
+                @group(0) @binding(1) var shadow: texture_depth_2d;
+                @group(0) @binding(2) var shadow_sampler: sampler_comparison;
+                @group(0) @binding(3) var layers: texture_2d_array<f32>;
+                @group(0) @binding(4) var sky: texture_cube<f32>;
+                @group(0) @binding(5) var volume: texture_3d<f32>;
+                @group(0) @binding(6) var samples: texture_multisampled_2d<f32>;",
+            )
+            .unwrap();
+            let resources: Vec<_> = groups[0].iter().map(|entry| entry.resource).collect();
+            assert!(matches!(
+                resources[..],
+                [
+                    LayoutResource::Buffer(BufferConfig::StorageReadWrite),
+                    LayoutResource::Texture(TextureConfig::D2(TextureKind::Depth)),
+                    LayoutResource::Sampler(SamplerConfig::Compare),
+                    LayoutResource::Texture(TextureConfig::D2Array(TextureKind::Image)),
+                    LayoutResource::Texture(TextureConfig::Cubemap(TextureKind::Image)),
+                    LayoutResource::Texture(TextureConfig::D3(TextureKind::Image)),
+                    LayoutResource::Texture(TextureConfig::D2Multisampled(TextureKind::Image)),
+                ]
+            ));
+            // Unused read-write storage is still kept out of the vertex shader
+            assert!(matches!(groups[0][0].access, ResourceAccess::Fragment));
+        }
+
+        {
+            let groups = Shader::reflect_entries(
+                "@group(0) @binding(0) var<storage, read_write> counter: atomic<u32>;
+
+                @vertex
+                fn vs_main() -> @builtin(position) vec4<f32> {
+                    return vec4<f32>(0.0);
+                }
+
+                @fragment
+                fn fs_main() -> @location(0) vec4<f32> {
+                    atomicAdd(&counter, 1u);
+                    return vec4<f32>(1.0);
+                }",
+            )
+            .unwrap();
+            assert!(matches!(groups[0][0].access, ResourceAccess::Fragment));
+        }
+
+        {
+            // Globals without a binding aren't resources
+            let groups = Shader::reflect_entries("var<private> counter: u32;").unwrap();
+            assert!(groups.is_empty());
+        }
+    }
+
+    #[test]
+    fn reflect_unsupported() {
+        let error = |source: &str| Shader::reflect_entries(source).unwrap_err().to_string();
+
+        {
+            let error = error("@group(0) @binding(0) var ids: texture_2d<u32>;");
+            assert!(error.contains("texture class"));
+        }
+
+        {
+            let error =
+                error("@group(0) @binding(0) var output: texture_storage_2d<rgba8unorm, write>;");
+            assert!(error.contains("texture class"));
+        }
+
+        {
+            let error = error("@group(0) @binding(0) var lines: texture_1d_array<f32>;");
+            assert!(error.contains("texture dimension"));
+        }
+
+        {
+            let error = error(
+                "@group(0) @binding(0) var<storage, read_write> data: array<u32>;
+
+                @compute @workgroup_size(1)
+                fn cs_main() {
+                    data[0] = 1u;
+                }",
+            );
+            assert!(error.contains("Compute"));
+        }
+
+        {
+            let error = error(
+                "@group(0) @binding(0) var<storage, read_write> data: array<vec4<f32>>;
+
+                @vertex
+                fn vs_main() -> @builtin(position) vec4<f32> {
+                    data[0] = vec4<f32>(1.0);
+                    return data[1];
+                }",
+            );
+            assert!(error.contains("Read-write storage at @group(0) @binding(0)"));
+            assert!(error.contains("Vertex"));
+        }
+
+        {
+            // Parse errors are reported as well
+            assert!(Shader::reflect_entries("@group(0) @binding(0) var").is_err());
+        }
+    }
 }
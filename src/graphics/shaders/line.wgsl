@@ -0,0 +1,62 @@
+// Expands every line segment instance into a screen-space quad of a fixed pixel width.
+
+struct Uniforms {
+    view_projection: mat4x4<f32>,
+    viewport: vec2<f32>,
+}
+
+struct Line {
+    @location(0) start: vec3<f32>,
+    @location(1) end: vec3<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) width: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32, line: Line) -> VertexOutput {
+    // x selects the end of the segment, y selects the side of the segment
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+
+    let clip_start = uniforms.view_projection * vec4<f32>(line.start, 1.0);
+    let clip_end = uniforms.view_projection * vec4<f32>(line.end, 1.0);
+    let screen_start = clip_start.xy / clip_start.w * uniforms.viewport;
+    let screen_end = clip_end.xy / clip_end.w * uniforms.viewport;
+
+    // Degenerate segments fall back to a horizontal direction instead of producing NaNs
+    let delta = screen_end - screen_start;
+    var direction = vec2<f32>(1.0, 0.0);
+    if dot(delta, delta) > 0.0 {
+        direction = normalize(delta);
+    }
+    let normal = vec2<f32>(-direction.y, direction.x);
+
+    // Half of the width in pixels, converted to normalized device coordinates
+    let offset = normal * corner.y * line.width / uniforms.viewport;
+    let clip = select(clip_start, clip_end, corner.x > 0.5);
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(clip.xy + offset * clip.w, clip.z, clip.w);
+    out.color = line.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
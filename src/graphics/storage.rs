@@ -0,0 +1,175 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use bytemuck::Pod;
+use wgpu::{Device, Queue};
+
+use crate::graphics::buffer::{AnyBufferHandle, BufferUsage};
+
+/// A growable storage buffer holding a variable-length array of items.
+///
+/// GPU-driven rendering usually uploads a list of per-instance data every frame
+/// and indexes it in the shader (e.g. by `instance_index`), a [`StorageArray`] packages that pattern:
+///
+/// ```rust
+/// # use whirl::graphics::{group::BindGroup, storage::StorageArray};
+/// # type InstanceData = [f32; 4];
+/// # fn run(
+/// #     device: &wgpu::Device,
+/// #     queue: &wgpu::Queue,
+/// #     instance_data: &[InstanceData],
+/// #     mut bind_group: BindGroup,
+/// #     create_bind_group: impl Fn(&wgpu::Device, &StorageArray<InstanceData>) -> BindGroup,
+/// # ) {
+/// let mut instances = StorageArray::<InstanceData>::new(device, 256, Some("instances"));
+///
+/// // Every frame
+/// if instances.update(device, queue, instance_data) {
+///     // The buffer was reallocated, so the bind group has to be rebuilt
+///     bind_group = create_bind_group(device, &instances);
+/// }
+/// # }
+/// ```
+///
+/// Which matches the following WGSL declaration:
+///
+/// ```wgsl
+/// @group(0) @binding(0) var<storage, read> instances: array<InstanceData>;
+/// ```
+///
+/// Keep in mind that `arrayLength(&instances)` is the capacity of the buffer rather than
+/// the amount of items in the array, use [`StorageArray::len()`] as the instance count of the draw
+/// (or pass it to the shader) instead.
+///
+/// A [`StorageArray`] implements [`AnyBufferHandle`], so it can be bound directly.
+#[derive(Debug)]
+pub struct StorageArray<T: Pod> {
+    /// The raw writable storage buffer
+    raw: wgpu::Buffer,
+    /// The amount of items in the array
+    len: usize,
+    /// The amount of items the buffer can hold
+    capacity: usize,
+    /// The optional debugging label, kept around to label reallocated buffers
+    label: Option<String>,
+    /// The type of the items
+    marker: PhantomData<T>,
+}
+
+impl<T: Pod> StorageArray<T> {
+    /// Creates a new empty storage array that can initially hold `capacity` items.
+    /// - `device` -> the [`wgpu::Device`] needed to create the buffer
+    /// - `capacity` -> the initial amount of items the buffer can hold
+    /// - `label` -> an optional debugging label which is assigned to the buffer
+    ///
+    /// # Panics:
+    /// - If `capacity` is equal to zero, since a storage buffer can't be bound with a size of zero.
+    pub fn new(device: &Device, capacity: usize, label: Option<&str>) -> Self {
+        assert!(capacity > 0, "Storage array capacity cannot be zero!");
+        Self {
+            raw: Self::create_buffer(device, capacity, label),
+            len: 0,
+            capacity,
+            label: label.map(str::to_owned),
+            marker: PhantomData,
+        }
+    }
+
+    /// Replaces the contents of the array with `items` and uploads them.
+    /// - `device` -> the [`wgpu::Device`] needed to reallocate the buffer if the items don't fit
+    /// - `queue` -> the [`wgpu::Queue`] needed to upload the items
+    /// - `items` -> the new contents of the array, which may be empty
+    ///
+    /// The buffer is reused whenever the items fit, otherwise its capacity is doubled until they do.
+    ///
+    /// Returns whether the buffer was reallocated, in which case any bind group
+    /// referencing the old buffer has to be rebuilt.
+    pub fn update(&mut self, device: &Device, queue: &Queue, items: &[T]) -> bool {
+        let capacity = grown_capacity(self.capacity, items.len());
+        let reallocated = capacity != self.capacity;
+        if reallocated {
+            self.capacity = capacity;
+            self.raw = Self::create_buffer(device, capacity, self.label.as_deref());
+        }
+
+        self.len = items.len();
+        if !items.is_empty() {
+            queue.write_buffer(&self.raw, 0, bytemuck::cast_slice(items));
+        }
+        reallocated
+    }
+
+    /// Returns the amount of items in the array.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the array is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the amount of items the buffer can hold before it has to be reallocated.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the raw [`wgpu::Buffer`].
+    pub fn raw(&self) -> &wgpu::Buffer {
+        &self.raw
+    }
+
+    /// Returns the buffer as a [`wgpu::BufferSlice`].
+    pub fn as_slice(&self) -> wgpu::BufferSlice<'_> {
+        self.raw.slice(..)
+    }
+
+    /// Creates a writable storage buffer that holds `capacity` items.
+    fn create_buffer(device: &Device, capacity: usize, label: Option<&str>) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label,
+            size: capacity as u64 * size_of::<T>() as u64,
+            usage: BufferUsage::Storage {
+                is_writable: true,
+                is_readable: false,
+            }
+            .raw(),
+            mapped_at_creation: false,
+        })
+    }
+}
+
+impl<T: Debug + Pod> AnyBufferHandle for StorageArray<T> {
+    fn raw(&self) -> &wgpu::Buffer {
+        self.raw()
+    }
+
+    fn as_slice(&self) -> wgpu::BufferSlice<'_> {
+        self.as_slice()
+    }
+}
+
+/// Returns the capacity needed to hold `len` items, doubling `capacity` until they fit.
+fn grown_capacity(mut capacity: usize, len: usize) -> usize {
+    while capacity < len {
+        capacity *= 2;
+    }
+    capacity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn growth() {
+        {
+            assert_eq!(grown_capacity(64, 0), 64);
+            assert_eq!(grown_capacity(64, 64), 64);
+        }
+
+        {
+            assert_eq!(grown_capacity(64, 65), 128);
+            assert_eq!(grown_capacity(64, 1000), 1024);
+        }
+    }
+}
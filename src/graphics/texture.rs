@@ -1,6 +1,17 @@
-use std::{error::Error, fmt, path::PathBuf};
+use std::{
+    error::Error,
+    fmt,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex, mpsc},
+    task::{Context, Poll, Waker},
+    thread,
+};
 
-use image::{EncodableLayout, ImageReader};
+use image::{ColorType, DynamicImage, EncodableLayout, ImageError, ImageReader, RgbaImage};
+
+use crate::graphics::color::Color;
 
 /// Describes a wrapper around [`wgpu::Texture`] with more information
 #[derive(Debug)]
@@ -11,6 +22,8 @@ pub struct Texture {
     raw_view: wgpu::TextureView,
     /// Represents the dimensions (width, height, depth) of the texture
     size: TextureSize,
+    /// Represents the format of the texture
+    format: TextureFormat,
 }
 
 /// Describes a texture
@@ -22,8 +35,28 @@ pub struct TextureDescriptor<'a> {
     pub dimension: TextureDimension,
     /// The usage of this texture (image binding, storage binding, render attachment)
     pub usage: TextureUsage,
-    /// The data source of this texture (file, depth, stencil, blank, bytes)
+    /// The data source of this texture (file, cubemap, depth, stencil, blank, bytes)
     pub source: TextureSource,
+    /// The amount of array layers of this texture, `1` for a regular texture
+    ///
+    /// A 2D texture with more than 1 layer is a texture array whose view spans every layer,
+    /// the data of a bytes source holds the layers one after another.
+    /// A [`TextureSource::Cubemap`] always has 6 layers, one per face, so this is ignored for cubemaps
+    pub layers: u32,
+    /// The mip levels of this texture
+    ///
+    /// If the source carries data (file, bytes), every level below the first is generated
+    /// from the uploaded data, otherwise the levels are left blank
+    pub mip_levels: MipLevels,
+    /// The amount of samples per pixel of this texture, `1` unless it's used for multisample anti-aliasing
+    ///
+    /// A multisampled texture must be a blank 2D texture without mip levels, and the pipelines
+    /// rendering to it must have the same amount of samples, it's either an attachment that's
+    /// resolved into a regular texture at the end of a render pass
+    /// (see [`crate::graphics::pass::RenderPassDescriptor::resolve_target`]),
+    /// or an image that's rendered to and then read sample by sample in a shader
+    /// (see [`crate::graphics::group::TextureConfig::D2Multisampled`])
+    pub samples: u32,
 }
 
 /// Describes the size of a texture
@@ -33,7 +66,7 @@ pub struct TextureSize {
     pub width: u32,
     /// The height of this texture (in pixels)
     pub height: u32,
-    /// The depth of this texture (in pixels), this is 1 for 2D textures
+    /// The depth of this texture (in pixels), or the amount of layers of a 2D texture
     pub depth: u32,
 }
 
@@ -61,12 +94,46 @@ pub enum TextureFormat {
     Signed,
     /// An unsigned integer format
     Unsigned,
+    /// A normalized format with a single 8-bit channel, useful for height maps and masks
+    R8Unorm,
+    /// A normalized format with two 8-bit channels
+    Rg8Unorm,
+    /// A floating point format with a single 16-bit channel
+    R16Float,
+    /// A floating point format with two 16-bit channels
+    Rg16Float,
+    /// A floating point RGBA format with 16-bit channels, the usual format for HDR colors
+    Rgba16Float,
+    /// A floating point RGBA format with 32-bit channels, which can't be filtered without
+    /// [`wgpu::Features::FLOAT32_FILTERABLE`]
+    Rgba32Float,
     /// A depth buffer format
     Depth,
     /// A stencil buffer format
     Stencil,
     /// A combined depth + stencil buffer format
     DepthStencil,
+    /// A block compressed RGBA format with 1-bit alpha (BC1, also known as DXT1), 8 bytes per 4x4 block
+    Bc1,
+    /// A block compressed RGBA format with smooth alpha (BC3, also known as DXT5), 16 bytes per 4x4 block
+    Bc3,
+    /// A high quality block compressed RGBA format (BC7), 16 bytes per 4x4 block
+    Bc7,
+}
+
+/// Specifies the amount of mip levels of a texture
+///
+/// Mip levels are successively halved versions of the texture,
+/// sampling them when the texture is minified avoids the shimmering of sampling the full size level
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum MipLevels {
+    /// The texture only has its full size level
+    #[default]
+    None,
+    /// The texture has a full mip chain, halving down to a single pixel
+    Auto,
+    /// The texture has an exact amount of mip levels, at least 1 and at most the full mip chain
+    Count(u32),
 }
 
 /// Specifies the usage of the texture
@@ -96,6 +163,15 @@ pub enum TextureUsage {
 pub enum TextureSource {
     /// The texture's source data comes from a file (png, jpeg, bmp)
     File { path: PathBuf },
+    /// The texture's source data comes from an image file decoded to floating point pixels (hdr, exr, png, ...),
+    /// which keeps the range of HDR images rather than clamping them to 8 bits
+    ///
+    /// The format is chosen from the decoded image, images with 32-bit float channels (such as hdr and exr files)
+    /// become [`TextureFormat::Rgba32Float`] textures, any other image becomes a [`TextureFormat::Rgba16Float`] texture
+    HdrFile { path: PathBuf },
+    /// The texture's source data comes from 6 square files of the same size, one per cubemap face,
+    /// in the order +X, -X, +Y, -Y, +Z, -Z
+    Cubemap { faces: [PathBuf; 6] },
     /// The texture's source data is an empty depth buffer of specified dimensions
     Depth { width: u32, height: u32 },
     /// The texture's source data is an empty stencil buffer of specified dimensions
@@ -115,6 +191,22 @@ pub enum TextureSource {
         format: TextureFormat,
         bytes: Vec<u8>,
     },
+    /// The texture's source data is pre-compressed block data (and a block compressed format) of specified dimensions
+    ///
+    /// The data is uploaded as is, so it must contain exactly one mip level of tightly packed blocks,
+    /// and the dimensions must be multiples of the format's block size
+    Compressed {
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+        bytes: Vec<u8>,
+    },
+    /// The texture's source data comes from a KTX2 file holding sRGB block compressed data (BC1, BC3, BC7)
+    ///
+    /// The format is read from the file, only the base level of a 2D file with a single layer and face
+    /// is loaded, and supercompressed (zstd, zlib, basis) files aren't supported
+    #[cfg(feature = "ktx2")]
+    Ktx2File { path: PathBuf },
 }
 
 /// Specifies a texture error that may have occurred.
@@ -141,6 +233,20 @@ pub enum TextureError {
         /// The underlying cause of the failure
         cause: &'static str,
     },
+    /// The texture format doesn't support the requested usage on this device
+    UnsupportedFormat {
+        /// The format of the texture
+        format: TextureFormat,
+        /// The usage the format doesn't support
+        usage: TextureUsage,
+    },
+    /// The texture's source data doesn't match the size the texture requires
+    DataSizeMismatch {
+        /// The amount of bytes the texture requires
+        expected: usize,
+        /// The amount of bytes the source data contains
+        actual: usize,
+    },
     /// The texture is of an illegal size
     IllegalSize {
         /// The illegal size information
@@ -148,9 +254,123 @@ pub enum TextureError {
         /// Specifies which extent (width/height) was of an illegal size
         cause: &'static str,
     },
+    /// The texture requested an illegal amount of array layers
+    IllegalLayers {
+        /// The requested amount of layers
+        layers: u32,
+        /// Specifies why the layer count is illegal
+        cause: &'static str,
+    },
+    /// The texture requested an illegal amount of mip levels
+    IllegalMipLevels {
+        /// The requested amount of mip levels
+        count: u32,
+        /// The amount of mip levels of the full mip chain
+        max: u32,
+    },
+    /// The texture requested an illegal amount of samples per pixel
+    IllegalSampleCount {
+        /// The requested amount of samples
+        count: u32,
+        /// Specifies why the sample count is illegal
+        cause: &'static str,
+    },
+    /// The mip levels of the texture can't be generated
+    MipmapFailure {
+        /// The format of the texture
+        format: TextureFormat,
+        /// The underlying cause of the failure
+        cause: &'static str,
+    },
+    /// The texture couldn't be read back to the CPU
+    ReadFailure {
+        /// The underlying cause of the failure
+        cause: Box<dyn Error>,
+    },
+    /// The texture couldn't be saved to an image file
+    SaveFailure {
+        /// The file the texture tried to save to
+        file: PathBuf,
+        /// The underlying cause of the failure
+        cause: Box<dyn Error>,
+    },
+}
+
+/// A future that resolves to a [`Texture`] once its image file has been loaded in the background
+///
+/// Created by [`Texture::load_async`]
+#[derive(Debug)]
+pub struct TextureLoad {
+    /// The [`wgpu::Device`] used to create the texture once the image is decoded
+    device: wgpu::Device,
+    /// The [`wgpu::Queue`] used to upload the image once it's decoded
+    queue: wgpu::Queue,
+    /// The file the texture is being loaded from
+    path: PathBuf,
+    /// The state shared with the background thread
+    state: Arc<Mutex<LoadState>>,
+}
+
+/// The state shared between a [`TextureLoad`] and its background thread
+#[derive(Debug, Default)]
+struct LoadState {
+    /// The decoded image (or the failure), set by the background thread once it's done
+    image: Option<Result<RgbaImage, ImageFailure>>,
+    /// The waker of the last task that polled the [`TextureLoad`]
+    waker: Option<Waker>,
+}
+
+/// Specifies why reading an image file failed, sendable across threads unlike [`TextureError`]
+#[derive(Debug)]
+enum ImageFailure {
+    /// The file couldn't be opened
+    Open(std::io::Error),
+    /// The file's contents couldn't be decoded
+    Decode(ImageError),
 }
 
 impl Texture {
+    /// Loads a texture from an image file without blocking the caller
+    /// - `device` -> the [`wgpu::Device`] needed to create this GPU resource
+    /// - `queue` -> the [`wgpu::Queue`] needed to write the image data to this texture on the GPU
+    /// - `path` -> the path of the image file (png, jpeg, bmp)
+    ///
+    /// The file is read and decoded on a background thread as soon as this function is called,
+    /// the GPU is only touched once the returned future completes, that's when the texture is
+    /// created and the image is uploaded, on whichever thread polls the future
+    ///
+    /// The `device` and `queue` are cloned into the future, which is fine since both are `Send`
+    /// and cheap to clone in wgpu
+    ///
+    /// The texture is created the same way as a [`TextureSource::File`] with a writable
+    /// [`TextureUsage::Image`] usage
+    pub fn load_async(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        path: impl Into<PathBuf>,
+    ) -> TextureLoad {
+        let path = path.into();
+        let state = Arc::new(Mutex::new(LoadState::default()));
+
+        let thread_path = path.clone();
+        let thread_state = Arc::clone(&state);
+        thread::spawn(move || {
+            let image = read_image(&thread_path);
+            let mut state = thread_state.lock().unwrap();
+            state.image = Some(image);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        TextureLoad {
+            device: device.clone(),
+            queue: queue.clone(),
+            path,
+            state,
+        }
+    }
+
     /// Returns a reference to the raw [`wgpu::Texture`]
     pub fn raw(&self) -> &wgpu::Texture {
         &self.raw
@@ -165,6 +385,331 @@ impl Texture {
     pub fn size(&self) -> TextureSize {
         self.size
     }
+
+    /// Returns the amount of mip levels of the texture, which is also the amount
+    /// of levels [`Texture::view()`] covers
+    pub fn mip_level_count(&self) -> u32 {
+        self.raw.mip_level_count()
+    }
+
+    /// Returns the amount of samples per pixel of the texture, which is greater than `1` for multisampled textures
+    pub fn sample_count(&self) -> u32 {
+        self.raw.sample_count()
+    }
+
+    /// Returns the [`TextureFormat`] of the texture
+    ///
+    /// Use [`TextureFormat::raw()`] to get the [`wgpu::TextureFormat`],
+    /// e.g. when creating a pipeline that renders to this texture
+    pub fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    /// Records a command to zero every texel of the texture, without a render pass
+    /// - `encoder` -> the [`wgpu::CommandEncoder`] to record the clear command into
+    ///
+    /// This is cheaper than a draw-based clear and works for storage textures too.
+    /// Unlike clearing a buffer, the texture doesn't need the `COPY_DST` usage (it doesn't have to be writable),
+    /// but the device must have [`wgpu::Features::CLEAR_TEXTURE`] enabled, otherwise wgpu panics
+    pub fn clear(&self, encoder: &mut wgpu::CommandEncoder) {
+        encoder.clear_texture(&self.raw, &wgpu::ImageSubresourceRange::default());
+    }
+
+    /// Copies the texture back to the CPU and returns its tightly packed texels, blocking until the GPU is done
+    /// - `device` -> the [`wgpu::Device`] needed to create the staging buffer and wait for the copy
+    /// - `queue` -> the [`wgpu::Queue`] needed to submit the copy
+    ///
+    /// Every command submitted to the `queue` before this call finishes before the texels are read,
+    /// so this is how a frame rendered offscreen (e.g. in a test or on a server) is read back
+    ///
+    /// Returns a [`TextureError::ReadFailure`] if the texture isn't readable (its [`TextureUsage`]
+    /// doesn't have `is_readable` set), is multisampled, is of a depth and/or stencil format
+    /// which can't be copied as a whole, or if the staging buffer couldn't be mapped
+    pub fn read_pixels(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<Vec<u8>, TextureError> {
+        self.err_on_unreadable()?;
+
+        let (bytes_per_row, rows_per_image) = self.format.layout(self.size);
+        // Rows of a texture to buffer copy must be aligned
+        let padded_bytes_per_row =
+            bytes_per_row.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let rows = rows_per_image * self.size.depth;
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("texture readback buffer"),
+            size: (padded_bytes_per_row * rows) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("texture readback encoder"),
+        });
+        encoder.copy_texture_to_buffer(
+            self.raw.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &staging,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(rows_per_image),
+                },
+            },
+            self.size.raw(),
+        );
+        queue.submit([encoder.finish()]);
+
+        let slice = staging.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(|err| TextureError::ReadFailure { cause: err.into() })?;
+        match receiver.recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => return Err(TextureError::ReadFailure { cause: err.into() }),
+            Err(err) => return Err(TextureError::ReadFailure { cause: err.into() }),
+        }
+
+        let padded = slice.get_mapped_range();
+        let mut pixels = Vec::with_capacity((bytes_per_row * rows) as usize);
+        for row in padded.chunks_exact(padded_bytes_per_row as usize) {
+            pixels.extend_from_slice(&row[..bytes_per_row as usize]);
+        }
+        drop(padded);
+        staging.unmap();
+        Ok(pixels)
+    }
+
+    /// Reads the texture back to the CPU (see [`Texture::read_pixels()`]) and saves it as a PNG file
+    /// - `device` -> the [`wgpu::Device`] needed to read the texture back
+    /// - `queue` -> the [`wgpu::Queue`] needed to read the texture back
+    /// - `path` -> the path of the PNG file to write
+    ///
+    /// Layers and depth slices are stacked vertically into a single image
+    ///
+    /// Returns a [`TextureError::SaveFailure`] if the texture isn't of the [`TextureFormat::Standard`]
+    /// or [`TextureFormat::UnsignedNormalized`] format or the file couldn't be written,
+    /// and a [`TextureError::ReadFailure`] if the texture couldn't be read back
+    pub fn save_png(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        path: impl AsRef<Path>,
+    ) -> Result<(), TextureError> {
+        let path = path.as_ref();
+        if !matches!(
+            self.format,
+            TextureFormat::Standard | TextureFormat::UnsignedNormalized
+        ) {
+            return Err(TextureError::SaveFailure {
+                file: path.to_path_buf(),
+                cause: format!(
+                    "Only RGBA8 textures can be saved as PNG, got {:?}",
+                    self.format
+                )
+                .into(),
+            });
+        }
+        image::save_buffer_with_format(
+            path,
+            &self.read_pixels(device, queue)?,
+            self.size.width,
+            self.size.height * self.size.depth,
+            image::ColorType::Rgba8,
+            image::ImageFormat::Png,
+        )
+        .map_err(|err| TextureError::SaveFailure {
+            file: path.to_path_buf(),
+            cause: err.into(),
+        })
+    }
+
+    /// Checks whether the texture can be copied back to the CPU as a whole
+    fn err_on_unreadable(&self) -> Result<(), TextureError> {
+        let cause = if !self.raw.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            "The texture doesn't have a readable usage"
+        } else if self.raw.sample_count() > 1 {
+            "Multisampled textures can't be copied, read back their resolve target instead"
+        } else if self.format.raw().is_depth_stencil_format() {
+            "Depth and stencil textures can't be copied as a whole"
+        } else {
+            return Ok(());
+        };
+        Err(TextureError::ReadFailure {
+            cause: cause.into(),
+        })
+    }
+
+    /// Fills every mip level below the first by rendering a linearly filtered downsample of the level above it
+    /// - `device` -> the [`wgpu::Device`] needed to create the internal blit pipeline
+    /// - `queue` -> the [`wgpu::Queue`] needed to submit the downsampling passes
+    ///
+    /// The texture must have been created with the `RENDER_ATTACHMENT` and `TEXTURE_BINDING` usages,
+    /// which [`TextureDescriptor::build()`] adds whenever it has mip levels to generate
+    fn generate_mipmaps(&self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let mip_level_count = self.mip_level_count();
+        if mip_level_count == 1 {
+            return;
+        }
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("mipmap shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/mipmap.wgsl").into()),
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("mipmap pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(self.format.raw().into())],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("mipmap sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let view = |mip_level, layer| {
+            self.raw.create_view(&wgpu::TextureViewDescriptor {
+                label: Some("mipmap view"),
+                dimension: Some(wgpu::TextureViewDimension::D2),
+                base_mip_level: mip_level,
+                mip_level_count: Some(1),
+                base_array_layer: layer,
+                array_layer_count: Some(1),
+                ..Default::default()
+            })
+        };
+
+        // Every layer (e.g. each face of a cubemap) has its own mip chain
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("mipmap encoder"),
+        });
+        for layer in 0..self.raw.depth_or_array_layers() {
+            let views: Vec<_> = (0..mip_level_count)
+                .map(|mip_level| view(mip_level, layer))
+                .collect();
+            self.record_downsamples(&mut encoder, device, &pipeline, &sampler, &views);
+        }
+        queue.submit([encoder.finish()]);
+    }
+
+    /// Records a pass per mip level of a single layer, downsampling the view of the level above it
+    fn record_downsamples(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        device: &wgpu::Device,
+        pipeline: &wgpu::RenderPipeline,
+        sampler: &wgpu::Sampler,
+        views: &[wgpu::TextureView],
+    ) {
+        for mip_level in 1..views.len() {
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("mipmap bind group"),
+                layout: &pipeline.get_bind_group_layout(0),
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&views[mip_level - 1]),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(sampler),
+                    },
+                ],
+            });
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("mipmap pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &views[mip_level],
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                    resolve_target: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+    }
+
+    /// Creates a 1x1 texture filled with a single color
+    /// - `device` -> the [`wgpu::Device`] needed to create this GPU resource
+    /// - `queue` -> the [`wgpu::Queue`] needed to write the color to this texture on the GPU
+    /// - `color` -> the color of the only pixel
+    ///
+    /// The color is stored as is in a linear RGBA8 ([`TextureFormat::UnsignedNormalized`]) texture,
+    /// which makes it suitable for placeholder textures whenever an asset is missing
+    ///
+    /// Returns a [`TextureError`] if the device can't create the texture
+    pub fn solid(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        color: Color,
+    ) -> Result<Self, TextureError> {
+        TextureDescriptor {
+            label: Some("Solid texture"),
+            dimension: TextureDimension::D2,
+            usage: TextureUsage::Image {
+                is_writable: true,
+                is_readable: false,
+            },
+            source: TextureSource::Bytes {
+                width: 1,
+                height: 1,
+                format: TextureFormat::UnsignedNormalized,
+                bytes: color.as_bytes().to_vec(),
+            },
+            layers: 1,
+            mip_levels: MipLevels::None,
+            samples: 1,
+        }
+        .build(device, queue)
+    }
+
+    /// Creates a 1x1 opaque white texture, the neutral placeholder for color textures
+    pub fn white(device: &wgpu::Device, queue: &wgpu::Queue) -> Result<Self, TextureError> {
+        Self::solid(device, queue, Color::WHITE)
+    }
+
+    /// Creates a 1x1 opaque black texture
+    pub fn black(device: &wgpu::Device, queue: &wgpu::Queue) -> Result<Self, TextureError> {
+        Self::solid(device, queue, Color::BLACK)
+    }
+
+    /// Creates a 1x1 normal map texture whose normal points straight out of the surface `(0, 0, 1)`,
+    /// the neutral placeholder for normal maps
+    pub fn default_normal(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<Self, TextureError> {
+        Self::solid(device, queue, Color::opaque(0.5, 0.5, 1.0))
+    }
 }
 
 impl<'a> TextureDescriptor<'a> {
@@ -176,8 +721,11 @@ impl<'a> TextureDescriptor<'a> {
         device: &wgpu::Device,
         queue: &wgpu::Queue,
     ) -> Result<Texture, TextureError> {
+        Self::err_on_unsupported(device.features(), self.source.format(), self.usage)?;
         match self.source.clone() {
             TextureSource::File { path } => Self::into_file(self, device, queue, path),
+            TextureSource::HdrFile { path } => Self::into_hdr_file(self, device, queue, path),
+            TextureSource::Cubemap { faces } => Self::into_cubemap(self, device, queue, faces),
             TextureSource::Depth { width, height } => Self::into_depth(self, device, width, height),
             TextureSource::Stencil { width, height } => {
                 Self::into_stencil(self, device, width, height)
@@ -196,6 +744,14 @@ impl<'a> TextureDescriptor<'a> {
                 format,
                 bytes,
             } => Self::into_bytes(self, device, queue, width, height, format, bytes),
+            TextureSource::Compressed {
+                width,
+                height,
+                format,
+                bytes,
+            } => Self::into_compressed(self, device, queue, width, height, format, bytes),
+            #[cfg(feature = "ktx2")]
+            TextureSource::Ktx2File { path } => Self::into_ktx2_file(self, device, queue, path),
         }
     }
 
@@ -203,10 +759,11 @@ impl<'a> TextureDescriptor<'a> {
         queue: &wgpu::Queue,
         texture: &Texture,
         texture_size: TextureSize,
-        pixel_size: u32,
+        format: TextureFormat,
         mip_level: u32,
         bytes: &[u8],
     ) {
+        let (bytes_per_row, rows_per_image) = format.layout(texture_size);
         queue.write_texture(
             wgpu::TexelCopyTextureInfo {
                 texture: texture.raw(),
@@ -217,29 +774,62 @@ impl<'a> TextureDescriptor<'a> {
             bytes,
             wgpu::TexelCopyBufferLayout {
                 offset: 0,
-                bytes_per_row: Some(pixel_size * texture_size.width),
-                rows_per_image: Some(texture_size.height),
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(rows_per_image),
             },
             texture_size.raw(),
         );
     }
 
-    fn into_args(self, device: &wgpu::Device, size: TextureSize, format: TextureFormat) -> Texture {
+    fn into_args(
+        self,
+        device: &wgpu::Device,
+        size: TextureSize,
+        format: TextureFormat,
+    ) -> Result<Texture, TextureError> {
+        self.err_on_layers()?;
+        let size = TextureSize {
+            depth: self.layer_count(),
+            ..size
+        };
+        Self::err_on_limits(&device.limits(), self.dimension, size)?;
+        let mip_level_count = self.mip_levels.count(self.dimension, size)?;
+        self.err_on_samples(device.features(), format, mip_level_count)?;
+        let mut usage = self.usage.raw();
+        // The mip levels of a source with data are generated by rendering each level from the one above it
+        if mip_level_count > 1 && self.source.has_data() {
+            Self::err_on_unmippable(device.features(), self.dimension, format)?;
+            usage |= wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING;
+        }
+        // A multisampled texture only gets its contents by being rendered to,
+        // so a multisampled image is a render attachment as well
+        if self.samples > 1 {
+            usage |= wgpu::TextureUsages::RENDER_ATTACHMENT;
+        }
+        let view_dimension = match self.source {
+            TextureSource::Cubemap { .. } => Some(wgpu::TextureViewDimension::Cube),
+            _ if self.layer_count() > 1 => Some(wgpu::TextureViewDimension::D2Array),
+            _ => None,
+        };
         let raw_texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: self.label, 
+            label: self.label,
             size: size.raw(),
-            mip_level_count: 1,
-            sample_count: 1,
+            mip_level_count,
+            sample_count: self.samples,
             dimension: self.dimension.raw(),
             format: format.raw(),
-            usage: self.usage.raw(),
+            usage,
             view_formats: &[],
         });
-        Texture {
-            raw_view: raw_texture.create_view(&wgpu::TextureViewDescriptor::default()),
+        Ok(Texture {
+            raw_view: raw_texture.create_view(&wgpu::TextureViewDescriptor {
+                dimension: view_dimension,
+                ..Default::default()
+            }),
             raw: raw_texture,
             size,
-        }
+            format,
+        })
     }
 
     fn into_file(
@@ -248,49 +838,110 @@ impl<'a> TextureDescriptor<'a> {
         queue: &wgpu::Queue,
         path: PathBuf,
     ) -> Result<Texture, TextureError> {
-        let image_reader = match ImageReader::open(&path) {
-            Ok(image_reader) => image_reader,
-            Err(cause) => {
-                return Err(TextureError::OpenFailure {
-                    file: path,
-                    cause: Box::new(cause),
-                });
-            }
+        let image = match read_image(&path) {
+            Ok(image) => image,
+            Err(failure) => return Err(failure.into_error(path)),
+        };
+        let image_size = TextureSize {
+            width: image.width(),
+            height: image.height(),
+            depth: 1,
         };
-        let image = match image_reader.decode() {
+        self.err_on_unwritable(&path)?;
+        let texture = self.into_args(device, image_size, TextureFormat::Standard)?;
+        Self::upload_texture(
+            queue,
+            &texture,
+            image_size,
+            TextureFormat::Standard,
+            0,
+            image.as_bytes(),
+        );
+        texture.generate_mipmaps(device, queue);
+        Ok(texture)
+    }
+
+    fn into_hdr_file(
+        self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        path: PathBuf,
+    ) -> Result<Texture, TextureError> {
+        let image = match decode_image(&path) {
             Ok(image) => image,
-            Err(cause) => {
-                return Err(TextureError::DecodeFailure {
-                    file: path,
-                    cause: Box::new(cause),
-                });
-            }
+            Err(failure) => return Err(failure.into_error(path)),
         };
+        let format = match image.color() {
+            ColorType::Rgb32F | ColorType::Rgba32F => TextureFormat::Rgba32Float,
+            _ => TextureFormat::Rgba16Float,
+        };
+        Self::err_on_unsupported(device.features(), format, self.usage)?;
+        self.err_on_unwritable(&path)?;
+
+        let image = image.into_rgba32f();
         let image_size = TextureSize {
             width: image.width(),
             height: image.height(),
             depth: 1,
         };
-        if let TextureUsage::Image {
-            is_writable,
-            is_readable: _is_readable,
-        } = self.usage
-            && !is_writable
-        {
-            return Err(TextureError::WriteFailure {
-                file: path,
-                cause: "Texture is not writable",
-            });
+        let bytes = match format {
+            TextureFormat::Rgba32Float => bytemuck::cast_slice(image.as_raw()).to_vec(),
+            _ => {
+                let channels: Vec<u16> = image
+                    .as_raw()
+                    .iter()
+                    .map(|&channel| half::f16::from_f32(channel).to_bits())
+                    .collect();
+                bytemuck::cast_slice(&channels).to_vec()
+            }
+        };
+        let texture = self.into_args(device, image_size, format)?;
+        Self::upload_texture(queue, &texture, image_size, format, 0, &bytes);
+        texture.generate_mipmaps(device, queue);
+        Ok(texture)
+    }
+
+    fn into_cubemap(
+        self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        faces: [PathBuf; 6],
+    ) -> Result<Texture, TextureError> {
+        self.err_on_unwritable(&faces[0])?;
+        let mut bytes = Vec::new();
+        let mut face_size = None;
+        for path in faces {
+            let image = match read_image(&path) {
+                Ok(image) => image,
+                Err(failure) => return Err(failure.into_error(path)),
+            };
+            let size = (image.width(), image.height());
+            if size.0 != size.1 || face_size.is_some_and(|face_size| face_size != size) {
+                return Err(TextureError::IllegalSize {
+                    size,
+                    cause: "Cubemap faces must be square and of the same size",
+                });
+            }
+            face_size = Some(size);
+            bytes.extend_from_slice(image.as_bytes());
         }
-        let texture = self.into_args(device, image_size, TextureFormat::Standard);
+
+        let (width, height) = face_size.expect("a cubemap always has 6 faces");
+        let texture_size = TextureSize {
+            width,
+            height,
+            depth: 6,
+        };
+        let texture = self.into_args(device, texture_size, TextureFormat::Standard)?;
         Self::upload_texture(
             queue,
             &texture,
-            image_size,
-            4,
+            texture_size,
+            TextureFormat::Standard,
             0,
-            image.into_rgba8().as_bytes(),
+            &bytes,
         );
+        texture.generate_mipmaps(device, queue);
         Ok(texture)
     }
 
@@ -306,7 +957,7 @@ impl<'a> TextureDescriptor<'a> {
             height,
             depth: 1,
         };
-        Ok(self.into_args(device, size, TextureFormat::Depth))
+        self.into_args(device, size, TextureFormat::Depth)
     }
 
     fn into_stencil(
@@ -316,7 +967,7 @@ impl<'a> TextureDescriptor<'a> {
         height: u32,
     ) -> Result<Texture, TextureError> {
         Self::err_on_zero(width, height)?;
-        Ok(self.into_args(
+        self.into_args(
             device,
             TextureSize {
                 width,
@@ -324,7 +975,7 @@ impl<'a> TextureDescriptor<'a> {
                 depth: 1,
             },
             TextureFormat::Stencil,
-        ))
+        )
     }
 
     fn into_depth_stencil(
@@ -334,7 +985,7 @@ impl<'a> TextureDescriptor<'a> {
         height: u32,
     ) -> Result<Texture, TextureError> {
         Self::err_on_zero(width, height)?;
-        Ok(self.into_args(
+        self.into_args(
             device,
             TextureSize {
                 width,
@@ -342,7 +993,7 @@ impl<'a> TextureDescriptor<'a> {
                 depth: 1,
             },
             TextureFormat::DepthStencil,
-        ))
+        )
     }
 
     fn into_blank(
@@ -353,7 +1004,7 @@ impl<'a> TextureDescriptor<'a> {
         format: TextureFormat,
     ) -> Result<Texture, TextureError> {
         Self::err_on_zero(width, height)?;
-        Ok(self.into_args(
+        self.into_args(
             device,
             TextureSize {
                 width,
@@ -361,7 +1012,7 @@ impl<'a> TextureDescriptor<'a> {
                 depth: 1,
             },
             format,
-        ))
+        )
     }
 
     pub fn into_bytes(
@@ -377,68 +1028,530 @@ impl<'a> TextureDescriptor<'a> {
         let texture_size = TextureSize {
             width,
             height,
-            depth: 1,
+            depth: self.layers,
         };
-        let texture = self.into_args(device, texture_size, format);
-        Self::upload_texture(queue, &texture, texture_size, 4, 0, &bytes);
+        let (bytes_per_row, rows_per_image) = format.layout(texture_size);
+        let expected = (bytes_per_row * rows_per_image * self.layers) as usize;
+        if bytes.len() != expected {
+            return Err(TextureError::DataSizeMismatch {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+
+        let texture = self.into_args(device, texture_size, format)?;
+        Self::upload_texture(queue, &texture, texture_size, format, 0, &bytes);
+        texture.generate_mipmaps(device, queue);
         Ok(texture)
     }
 
-    fn err_on_zero(width: u32, height: u32) -> Result<(), TextureError> {
-        if width == 0 {
+    fn into_compressed(
+        self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        width: u32,
+        height: u32,
+        format: TextureFormat,
+        bytes: Vec<u8>,
+    ) -> Result<Texture, TextureError> {
+        Self::err_on_zero(width, height)?;
+        let (block_width, block_height) = format.raw().block_dimensions();
+        if block_width == 1 && block_height == 1 {
+            return Err(TextureError::UnsupportedFormat {
+                format,
+                usage: self.usage,
+            });
+        }
+        if !width.is_multiple_of(block_width) || !height.is_multiple_of(block_height) {
             return Err(TextureError::IllegalSize {
                 size: (width, height),
-                cause: "Texture width cannot be zero",
+                cause: "Compressed texture dimensions must be multiples of the block size",
             });
         }
 
-        if height == 0 {
-            return Err(TextureError::IllegalSize {
-                size: (width, height),
-                cause: "Texture height cannot be zero",
+        let texture_size = TextureSize {
+            width,
+            height,
+            depth: 1,
+        };
+        let (bytes_per_row, rows_per_image) = format.layout(texture_size);
+        let expected = (bytes_per_row * rows_per_image) as usize;
+        if bytes.len() != expected {
+            return Err(TextureError::DataSizeMismatch {
+                expected,
+                actual: bytes.len(),
             });
         }
 
-        Ok(())
+        let texture = self.into_args(device, texture_size, format)?;
+        Self::upload_texture(queue, &texture, texture_size, format, 0, &bytes);
+        Ok(texture)
     }
-}
 
-impl TextureSize {
-    /// Maps the high level [`TextureSize`] to a [`wgpu::Extent3d`]
-    pub fn raw(self) -> wgpu::Extent3d {
-        wgpu::Extent3d {
-            width: self.width,
-            height: self.height,
-            depth_or_array_layers: self.depth,
+    #[cfg(feature = "ktx2")]
+    fn into_ktx2_file(
+        self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        path: PathBuf,
+    ) -> Result<Texture, TextureError> {
+        let file = std::fs::read(&path).map_err(|err| TextureError::OpenFailure {
+            file: path.clone(),
+            cause: err.into(),
+        })?;
+        let source = decode_ktx2(&file).map_err(|cause| TextureError::DecodeFailure {
+            file: path.clone(),
+            cause,
+        })?;
+        TextureDescriptor { source, ..self }.build(device, queue)
+    }
+
+    /// Checks that the texture can be written to, which uploading the image of a file requires
+    fn err_on_unwritable(&self, file: &Path) -> Result<(), TextureError> {
+        if let TextureUsage::Image {
+            is_writable,
+            is_readable: _is_readable,
+        } = self.usage
+            && !is_writable
+        {
+            return Err(TextureError::WriteFailure {
+                file: file.to_path_buf(),
+                cause: "Texture is not writable",
+            });
         }
+        Ok(())
     }
-}
 
-impl TextureDimension {
-    /// Maps the high level [`TextureDimension`] to a [`wgpu::TextureDimension`]
-    pub fn raw(self) -> wgpu::TextureDimension {
-        match self {
-            TextureDimension::D1 => wgpu::TextureDimension::D1,
-            TextureDimension::D2 => wgpu::TextureDimension::D2,
-            TextureDimension::D3 => wgpu::TextureDimension::D3,
+    /// Returns the amount of array layers of the texture, which is always 6 for a cubemap
+    /// regardless of [`TextureDescriptor::layers`]
+    fn layer_count(&self) -> u32 {
+        match self.source {
+            TextureSource::Cubemap { .. } => 6,
+            _ => self.layers,
         }
     }
-}
 
-impl TextureFormat {
-    /// Maps the high level [`TextureFormat`] to a [`wgpu::TextureFormat`]
-    pub fn raw(self) -> wgpu::TextureFormat {
-        match self {
-            TextureFormat::Standard => wgpu::TextureFormat::Rgba8UnormSrgb,
-            TextureFormat::SignedNormalized => wgpu::TextureFormat::Rgba8Snorm,
-            TextureFormat::UnsignedNormalized => wgpu::TextureFormat::Rgba8Unorm,
-            TextureFormat::Signed => wgpu::TextureFormat::Rgba8Sint,
+    /// Checks that the layer count fits the source and the dimension of the texture,
+    /// only 2D textures can have layers, cubemaps included
+    fn err_on_layers(&self) -> Result<(), TextureError> {
+        let is_2d = matches!(self.dimension, TextureDimension::D2);
+        let cause = match (&self.source, self.layer_count()) {
+            (TextureSource::Cubemap { .. }, _) if !is_2d => "Cubemaps must be 2D",
+            (TextureSource::Cubemap { .. }, _) => return Ok(()),
+            (_, 0) => "The layer count can't be zero",
+            (_, 1) => return Ok(()),
+            _ if !is_2d => "Only 2D textures can have layers",
+            (
+                TextureSource::File { .. }
+                | TextureSource::HdrFile { .. }
+                | TextureSource::Compressed { .. },
+                _,
+            ) => "File and compressed textures must have a single layer",
+            _ => return Ok(()),
+        };
+        Err(TextureError::IllegalLayers {
+            layers: self.layer_count(),
+            cause,
+        })
+    }
+
+    /// Checks that a multisampled texture is a blank 2D image or attachment without mip levels,
+    /// and that its format supports the sample count on the device
+    ///
+    /// Multisampled textures can't be copied to or from, so they can't be writable or readable either
+    fn err_on_samples(
+        &self,
+        features: wgpu::Features,
+        format: TextureFormat,
+        mip_level_count: u32,
+    ) -> Result<(), TextureError> {
+        let cause = match self.samples {
+            0 => "The sample count can't be zero",
+            1 => return Ok(()),
+            _ if !matches!(self.dimension, TextureDimension::D2) => {
+                "Multisampled textures must be 2D"
+            }
+            _ if mip_level_count > 1 => "Multisampled textures can't have mip levels",
+            _ if self.layers > 1 => "Multisampled textures can't have layers",
+            _ if self.source.has_data()
+                || !matches!(
+                    self.usage,
+                    TextureUsage::Image {
+                        is_writable: false,
+                        is_readable: false
+                    } | TextureUsage::Attachment {
+                        is_writable: false,
+                        is_readable: false
+                    }
+                ) =>
+            {
+                "Multisampled textures must be blank images or attachments that are neither writable nor readable"
+            }
+            samples
+                if !format
+                    .raw()
+                    .guaranteed_format_features(features)
+                    .flags
+                    .sample_count_supported(samples) =>
+            {
+                "The format doesn't support the sample count on this device"
+            }
+            _ => return Ok(()),
+        };
+        Err(TextureError::IllegalSampleCount {
+            count: self.samples,
+            cause,
+        })
+    }
+
+    /// Checks that the mip levels of a texture can be generated, which renders each level
+    /// from a linearly filtered sample of the one above it, so it takes a 2D texture
+    /// of a format that is both renderable and filterable
+    fn err_on_unmippable(
+        features: wgpu::Features,
+        dimension: TextureDimension,
+        format: TextureFormat,
+    ) -> Result<(), TextureError> {
+        if !matches!(dimension, TextureDimension::D2) {
+            return Err(TextureError::MipmapFailure {
+                format,
+                cause: "Mip levels can only be generated for 2D textures",
+            });
+        }
+
+        let format_features = format.raw().guaranteed_format_features(features);
+        if !format_features
+            .allowed_usages
+            .contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+            || !format_features
+                .flags
+                .contains(wgpu::TextureFormatFeatureFlags::FILTERABLE)
+        {
+            return Err(TextureError::MipmapFailure {
+                format,
+                cause: "The format must be both renderable and filterable",
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks the format/usage combination against the formats features the device guarantees
+    ///
+    /// If the device enables [`wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`],
+    /// the adapter may support more than what's guaranteed, which can't be queried from the device,
+    /// so the check is skipped and wgpu's own validation applies instead
+    fn err_on_unsupported(
+        features: wgpu::Features,
+        format: TextureFormat,
+        usage: TextureUsage,
+    ) -> Result<(), TextureError> {
+        if features.contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES) {
+            return Ok(());
+        }
+
+        if !features.contains(format.raw().required_features()) {
+            return Err(TextureError::UnsupportedFormat { format, usage });
+        }
+
+        let allowed_usages = format
+            .raw()
+            .guaranteed_format_features(features)
+            .allowed_usages;
+        if !allowed_usages.contains(usage.raw()) {
+            return Err(TextureError::UnsupportedFormat { format, usage });
+        }
+
+        Ok(())
+    }
+
+    /// Checks the size against the limits of the device for the dimension of the texture,
+    /// so oversized textures (e.g. an 8K image on a limited device) fail gracefully instead of panicking
+    fn err_on_limits(
+        limits: &wgpu::Limits,
+        dimension: TextureDimension,
+        size: TextureSize,
+    ) -> Result<(), TextureError> {
+        let illegal = |cause| {
+            Err(TextureError::IllegalSize {
+                size: (size.width, size.height),
+                cause,
+            })
+        };
+        match dimension {
+            TextureDimension::D1 => {
+                if size.width > limits.max_texture_dimension_1d {
+                    return illegal("Texture width exceeds the max_texture_dimension_1d limit");
+                }
+                if size.height != 1 || size.depth != 1 {
+                    return illegal("1D textures must have a height and depth of 1");
+                }
+            }
+            TextureDimension::D2 => {
+                if size.width > limits.max_texture_dimension_2d {
+                    return illegal("Texture width exceeds the max_texture_dimension_2d limit");
+                }
+                if size.height > limits.max_texture_dimension_2d {
+                    return illegal("Texture height exceeds the max_texture_dimension_2d limit");
+                }
+                if size.depth > limits.max_texture_array_layers {
+                    return illegal("Texture layers exceed the max_texture_array_layers limit");
+                }
+            }
+            TextureDimension::D3 => {
+                if size.width > limits.max_texture_dimension_3d {
+                    return illegal("Texture width exceeds the max_texture_dimension_3d limit");
+                }
+                if size.height > limits.max_texture_dimension_3d {
+                    return illegal("Texture height exceeds the max_texture_dimension_3d limit");
+                }
+                if size.depth == 0 {
+                    return illegal("Texture depth cannot be zero");
+                }
+                if size.depth > limits.max_texture_dimension_3d {
+                    return illegal("Texture depth exceeds the max_texture_dimension_3d limit");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn err_on_zero(width: u32, height: u32) -> Result<(), TextureError> {
+        if width == 0 {
+            return Err(TextureError::IllegalSize {
+                size: (width, height),
+                cause: "Texture width cannot be zero",
+            });
+        }
+
+        if height == 0 {
+            return Err(TextureError::IllegalSize {
+                size: (width, height),
+                cause: "Texture height cannot be zero",
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Future for TextureLoad {
+    type Output = Result<Texture, TextureError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        let Some(image) = state.image.take() else {
+            state.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        };
+        drop(state);
+
+        let image = match image {
+            Ok(image) => image,
+            Err(failure) => return Poll::Ready(Err(failure.into_error(self.path.clone()))),
+        };
+        let descriptor = TextureDescriptor {
+            label: None,
+            dimension: TextureDimension::D2,
+            usage: TextureUsage::Image {
+                is_writable: true,
+                is_readable: false,
+            },
+            source: TextureSource::Bytes {
+                width: image.width(),
+                height: image.height(),
+                format: TextureFormat::Standard,
+                bytes: image.into_raw(),
+            },
+            layers: 1,
+            mip_levels: MipLevels::None,
+            samples: 1,
+        };
+        Poll::Ready(descriptor.build(&self.device, &self.queue))
+    }
+}
+
+impl ImageFailure {
+    /// Maps the failure to a [`TextureError`] for the given file
+    fn into_error(self, file: PathBuf) -> TextureError {
+        match self {
+            ImageFailure::Open(cause) => TextureError::OpenFailure {
+                file,
+                cause: Box::new(cause),
+            },
+            ImageFailure::Decode(cause) => TextureError::DecodeFailure {
+                file,
+                cause: Box::new(cause),
+            },
+        }
+    }
+}
+
+/// Opens and decodes an image file into RGBA8 pixels
+fn read_image(path: &Path) -> Result<RgbaImage, ImageFailure> {
+    decode_image(path).map(DynamicImage::into_rgba8)
+}
+
+/// Opens and decodes an image file, keeping the pixels in the format of the file
+fn decode_image(path: &Path) -> Result<DynamicImage, ImageFailure> {
+    let image_reader = ImageReader::open(path).map_err(ImageFailure::Open)?;
+    image_reader.decode().map_err(ImageFailure::Decode)
+}
+
+/// Decodes the base level of a KTX2 file into a [`TextureSource::Compressed`] source
+#[cfg(feature = "ktx2")]
+fn decode_ktx2(file: &[u8]) -> Result<TextureSource, Box<dyn Error>> {
+    let reader = ktx2::Reader::new(file)?;
+    let header = reader.header();
+    if header.supercompression_scheme.is_some() {
+        return Err("Supercompressed KTX2 files aren't supported".into());
+    }
+    if header.pixel_depth > 1 || header.layer_count > 1 || header.face_count != 1 {
+        return Err("Only 2D KTX2 files with a single layer and face are supported".into());
+    }
+    let format = match header.format {
+        Some(ktx2::Format::BC1_RGBA_SRGB_BLOCK) => TextureFormat::Bc1,
+        Some(ktx2::Format::BC3_SRGB_BLOCK) => TextureFormat::Bc3,
+        Some(ktx2::Format::BC7_SRGB_BLOCK) => TextureFormat::Bc7,
+        format => return Err(format!("Unsupported KTX2 format {:?}", format).into()),
+    };
+    let level = reader
+        .levels()
+        .next()
+        .ok_or("The KTX2 file has no levels")?;
+    Ok(TextureSource::Compressed {
+        width: header.pixel_width,
+        height: header.pixel_height.max(1),
+        format,
+        bytes: level.data.to_vec(),
+    })
+}
+
+impl TextureSource {
+    /// Returns the format the texture created from this source will have
+    pub fn format(&self) -> TextureFormat {
+        match self {
+            TextureSource::File { .. } | TextureSource::Cubemap { .. } => TextureFormat::Standard,
+            // Images with 32-bit float channels become Rgba32Float textures, which is only known once decoded
+            TextureSource::HdrFile { .. } => TextureFormat::Rgba16Float,
+            TextureSource::Depth { .. } => TextureFormat::Depth,
+            TextureSource::Stencil { .. } => TextureFormat::Stencil,
+            TextureSource::DepthStencil { .. } => TextureFormat::DepthStencil,
+            TextureSource::Blank { format, .. } => *format,
+            TextureSource::Bytes { format, .. } => *format,
+            TextureSource::Compressed { format, .. } => *format,
+            // The exact block compressed format is only known once the file is parsed,
+            // every one of them requires the same device feature
+            #[cfg(feature = "ktx2")]
+            TextureSource::Ktx2File { .. } => TextureFormat::Bc7,
+        }
+    }
+
+    /// Returns whether the source carries data that gets uploaded to the texture
+    fn has_data(&self) -> bool {
+        matches!(
+            self,
+            TextureSource::File { .. }
+                | TextureSource::HdrFile { .. }
+                | TextureSource::Cubemap { .. }
+                | TextureSource::Bytes { .. }
+                | TextureSource::Compressed { .. }
+        )
+    }
+}
+
+impl MipLevels {
+    /// Returns the amount of mip levels of a texture, returns a [`TextureError`]
+    /// if an explicit count is zero or longer than the full mip chain
+    /// - `dimension` -> the dimension of the texture, 1D textures can't have more than 1 level
+    /// - `size` -> the size of the full size level of the texture
+    pub fn count(
+        self,
+        dimension: TextureDimension,
+        size: TextureSize,
+    ) -> Result<u32, TextureError> {
+        let max = size.raw().max_mips(dimension.raw());
+        match self {
+            MipLevels::None => Ok(1),
+            MipLevels::Auto => Ok(max),
+            MipLevels::Count(count) if (1..=max).contains(&count) => Ok(count),
+            MipLevels::Count(count) => Err(TextureError::IllegalMipLevels { count, max }),
+        }
+    }
+}
+
+impl TextureSize {
+    /// Maps the high level [`TextureSize`] to a [`wgpu::Extent3d`]
+    pub fn raw(self) -> wgpu::Extent3d {
+        wgpu::Extent3d {
+            width: self.width,
+            height: self.height,
+            depth_or_array_layers: self.depth,
+        }
+    }
+}
+
+impl TextureDimension {
+    /// Maps the high level [`TextureDimension`] to a [`wgpu::TextureDimension`]
+    pub fn raw(self) -> wgpu::TextureDimension {
+        match self {
+            TextureDimension::D1 => wgpu::TextureDimension::D1,
+            TextureDimension::D2 => wgpu::TextureDimension::D2,
+            TextureDimension::D3 => wgpu::TextureDimension::D3,
+        }
+    }
+}
+
+impl TextureFormat {
+    /// Maps the high level [`TextureFormat`] to a [`wgpu::TextureFormat`]
+    pub fn raw(self) -> wgpu::TextureFormat {
+        match self {
+            TextureFormat::Standard => wgpu::TextureFormat::Rgba8UnormSrgb,
+            TextureFormat::SignedNormalized => wgpu::TextureFormat::Rgba8Snorm,
+            TextureFormat::UnsignedNormalized => wgpu::TextureFormat::Rgba8Unorm,
+            TextureFormat::Signed => wgpu::TextureFormat::Rgba8Sint,
             TextureFormat::Unsigned => wgpu::TextureFormat::Rgba8Uint,
+            TextureFormat::R8Unorm => wgpu::TextureFormat::R8Unorm,
+            TextureFormat::Rg8Unorm => wgpu::TextureFormat::Rg8Unorm,
+            TextureFormat::R16Float => wgpu::TextureFormat::R16Float,
+            TextureFormat::Rg16Float => wgpu::TextureFormat::Rg16Float,
+            TextureFormat::Rgba16Float => wgpu::TextureFormat::Rgba16Float,
+            TextureFormat::Rgba32Float => wgpu::TextureFormat::Rgba32Float,
             TextureFormat::Depth => wgpu::TextureFormat::Depth32Float,
             TextureFormat::Stencil => wgpu::TextureFormat::Stencil8,
             TextureFormat::DepthStencil => wgpu::TextureFormat::Depth24PlusStencil8,
+            TextureFormat::Bc1 => wgpu::TextureFormat::Bc1RgbaUnormSrgb,
+            TextureFormat::Bc3 => wgpu::TextureFormat::Bc3RgbaUnormSrgb,
+            TextureFormat::Bc7 => wgpu::TextureFormat::Bc7RgbaUnormSrgb,
         }
     }
+
+    /// Returns the size in bytes of a single pixel of this format
+    ///
+    /// For block compressed formats, this is the size of a whole block of pixels,
+    /// and for [`TextureFormat::DepthStencil`], whose data can't be copied as a whole,
+    /// this is the size of a packed depth and stencil value
+    pub fn bytes_per_pixel(self) -> u32 {
+        match self {
+            TextureFormat::DepthStencil => 4,
+            format => format
+                .raw()
+                .block_copy_size(None)
+                .expect("every other format has a single aspect"),
+        }
+    }
+
+    /// Returns the `(bytes_per_row, rows_per_image)` of tightly packed data of this format
+    /// - `size` -> the size of the texture
+    ///
+    /// For block compressed formats, a row is a row of blocks rather than a row of pixels
+    pub fn layout(self, size: TextureSize) -> (u32, u32) {
+        let (block_width, block_height) = self.raw().block_dimensions();
+        (
+            size.width.div_ceil(block_width) * self.bytes_per_pixel(),
+            size.height.div_ceil(block_height),
+        )
+    }
 }
 
 impl TextureUsage {
@@ -502,9 +1615,583 @@ impl fmt::Display for TextureError {
                     file, cause
                 )
             }
+            TextureError::UnsupportedFormat { format, usage } => {
+                write!(
+                    f,
+                    "Unsupported texture format {:?}:\n\tThe format doesn't support the {:?} usage on this device",
+                    format, usage
+                )
+            }
+            TextureError::DataSizeMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Texture data size mismatch:\n\tExpected {} bytes, got {} bytes",
+                    expected, actual
+                )
+            }
             TextureError::IllegalSize { size, cause } => {
                 write!(f, "Illegal texture size: {:?}:\n\t{}", size, cause)
             }
+            TextureError::IllegalMipLevels { count, max } => {
+                write!(
+                    f,
+                    "Illegal texture mip level count {}:\n\tThe count must be between 1 and {}, the length of the full mip chain",
+                    count, max
+                )
+            }
+            TextureError::IllegalLayers { layers, cause } => {
+                write!(f, "Illegal texture layer count {}:\n\t{}", layers, cause)
+            }
+            TextureError::IllegalSampleCount { count, cause } => {
+                write!(f, "Illegal texture sample count {}:\n\t{}", count, cause)
+            }
+            TextureError::MipmapFailure { format, cause } => {
+                write!(
+                    f,
+                    "Couldn't generate mip levels for texture format {:?}:\n\t{}",
+                    format, cause
+                )
+            }
+            TextureError::ReadFailure { cause } => {
+                write!(f, "Couldn't read texture back:\n\t{}", cause)
+            }
+            TextureError::SaveFailure { file, cause } => {
+                write!(f, "Couldn't save texture to file {:?}:\n\t{}", file, cause)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsupported_format() {
+        let features = wgpu::Features::empty();
+        let image = TextureUsage::Image {
+            is_writable: true,
+            is_readable: false,
+        };
+        let storage = TextureUsage::Storage {
+            is_writable: false,
+            is_readable: false,
+        };
+        let attachment = TextureUsage::Attachment {
+            is_writable: false,
+            is_readable: false,
+        };
+
+        {
+            let result =
+                TextureDescriptor::err_on_unsupported(features, TextureFormat::Standard, image);
+            assert!(result.is_ok());
+        }
+
+        {
+            let result =
+                TextureDescriptor::err_on_unsupported(features, TextureFormat::Depth, attachment);
+            assert!(result.is_ok());
+        }
+
+        {
+            let result =
+                TextureDescriptor::err_on_unsupported(features, TextureFormat::Standard, storage);
+            assert!(matches!(
+                result,
+                Err(TextureError::UnsupportedFormat {
+                    format: TextureFormat::Standard,
+                    ..
+                })
+            ));
+        }
+
+        {
+            let result =
+                TextureDescriptor::err_on_unsupported(features, TextureFormat::Depth, storage);
+            assert!(result.is_err());
+        }
+        {
+            let result = TextureDescriptor::err_on_unsupported(features, TextureFormat::Bc7, image);
+            assert!(result.is_err());
+        }
+
+        {
+            let features = wgpu::Features::TEXTURE_COMPRESSION_BC;
+            let result = TextureDescriptor::err_on_unsupported(features, TextureFormat::Bc7, image);
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn layout() {
+        let size = TextureSize {
+            width: 64,
+            height: 32,
+            depth: 1,
+        };
+
+        {
+            assert_eq!(TextureFormat::Standard.layout(size), (256, 32));
+        }
+
+        {
+            assert_eq!(TextureFormat::Bc1.layout(size), (128, 8));
+        }
+
+        {
+            assert_eq!(TextureFormat::Bc7.layout(size), (256, 8));
+        }
+    }
+
+    #[test]
+    fn bytes_per_pixel() {
+        {
+            assert_eq!(TextureFormat::Standard.bytes_per_pixel(), 4);
+        }
+
+        {
+            assert_eq!(TextureFormat::Stencil.bytes_per_pixel(), 1);
+        }
+
+        {
+            assert_eq!(TextureFormat::DepthStencil.bytes_per_pixel(), 4);
+        }
+
+        {
+            let formats = [
+                TextureFormat::R8Unorm,
+                TextureFormat::Rg8Unorm,
+                TextureFormat::R16Float,
+                TextureFormat::Rg16Float,
+                TextureFormat::Rgba16Float,
+                TextureFormat::Rgba32Float,
+            ];
+            let sizes = formats.map(TextureFormat::bytes_per_pixel);
+            assert_eq!(sizes, [1, 2, 2, 4, 8, 16]);
+        }
+
+        {
+            // A block of 4x4 pixels
+            assert_eq!(TextureFormat::Bc1.bytes_per_pixel(), 8);
+        }
+
+        {
+            let size = TextureSize {
+                width: 6,
+                height: 2,
+                depth: 1,
+            };
+            assert_eq!(TextureFormat::Stencil.layout(size), (6, 2));
+        }
+    }
+
+    #[test]
+    fn size_limits() {
+        // The downlevel limits cap 2D textures at 2048 and 3D textures at 256
+        let limits = wgpu::Limits::downlevel_defaults();
+        let size = |width, height, depth| TextureSize {
+            width,
+            height,
+            depth,
+        };
+
+        {
+            let result = TextureDescriptor::err_on_limits(
+                &limits,
+                TextureDimension::D2,
+                size(2048, 2048, 1),
+            );
+            assert!(result.is_ok());
+        }
+
+        {
+            let result = TextureDescriptor::err_on_limits(
+                &limits,
+                TextureDimension::D2,
+                size(8192, 4096, 1),
+            );
+            let Err(TextureError::IllegalSize { size, cause }) = result else {
+                panic!("Expected an illegal size, got: {:?}", result);
+            };
+            assert_eq!(size, (8192, 4096));
+            assert!(cause.contains("max_texture_dimension_2d"));
+        }
+
+        {
+            let result =
+                TextureDescriptor::err_on_limits(&limits, TextureDimension::D1, size(64, 2, 1));
+            assert!(result.is_err());
+        }
+
+        {
+            let result = TextureDescriptor::err_on_limits(
+                &limits,
+                TextureDimension::D3,
+                size(256, 256, 256),
+            );
+            assert!(result.is_ok());
+        }
+
+        {
+            let result =
+                TextureDescriptor::err_on_limits(&limits, TextureDimension::D3, size(64, 64, 512));
+            let Err(TextureError::IllegalSize { cause, .. }) = result else {
+                panic!("Expected an illegal size, got: {:?}", result);
+            };
+            assert!(cause.contains("depth"));
+        }
+    }
+
+    #[test]
+    fn mip_levels() {
+        let size = |width, height| TextureSize {
+            width,
+            height,
+            depth: 1,
+        };
+
+        {
+            let count = MipLevels::Auto.count(TextureDimension::D2, size(256, 256));
+            assert_eq!(count.unwrap(), 9);
+        }
+
+        {
+            // The full mip chain follows the largest extent, rounding down
+            let count = MipLevels::Auto.count(TextureDimension::D2, size(300, 20));
+            assert_eq!(count.unwrap(), 9);
+        }
+
+        {
+            let count = MipLevels::Auto.count(TextureDimension::D1, size(64, 1));
+            assert_eq!(count.unwrap(), 1);
+        }
+
+        {
+            let count = MipLevels::None.count(TextureDimension::D2, size(256, 256));
+            assert_eq!(count.unwrap(), 1);
+        }
+
+        {
+            let count = MipLevels::Count(4).count(TextureDimension::D2, size(256, 256));
+            assert_eq!(count.unwrap(), 4);
+        }
+
+        {
+            let result = MipLevels::Count(10).count(TextureDimension::D2, size(256, 256));
+            assert!(matches!(
+                result,
+                Err(TextureError::IllegalMipLevels { count: 10, max: 9 })
+            ));
+        }
+
+        {
+            let result = MipLevels::Count(0).count(TextureDimension::D2, size(256, 256));
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn unmippable() {
+        let features = wgpu::Features::empty();
+
+        {
+            let result = TextureDescriptor::err_on_unmippable(
+                features,
+                TextureDimension::D2,
+                TextureFormat::Standard,
+            );
+            assert!(result.is_ok());
+        }
+
+        {
+            let result = TextureDescriptor::err_on_unmippable(
+                features,
+                TextureDimension::D2,
+                TextureFormat::UnsignedNormalized,
+            );
+            assert!(result.is_ok());
+        }
+
+        {
+            // Integer formats can't be filtered
+            let result = TextureDescriptor::err_on_unmippable(
+                features,
+                TextureDimension::D2,
+                TextureFormat::Unsigned,
+            );
+            assert!(matches!(
+                result,
+                Err(TextureError::MipmapFailure {
+                    format: TextureFormat::Unsigned,
+                    ..
+                })
+            ));
+        }
+
+        {
+            let result = TextureDescriptor::err_on_unmippable(
+                features,
+                TextureDimension::D3,
+                TextureFormat::Standard,
+            );
+            let Err(TextureError::MipmapFailure { cause, .. }) = result else {
+                panic!("Expected a mipmap failure, got: {:?}", result);
+            };
+            assert!(cause.contains("2D"));
+        }
+    }
+
+    #[test]
+    fn sample_count() {
+        let features = wgpu::Features::empty();
+        let attachment = TextureUsage::Attachment {
+            is_writable: false,
+            is_readable: false,
+        };
+        let descriptor = |dimension, usage, samples| TextureDescriptor {
+            label: None,
+            dimension,
+            usage,
+            source: TextureSource::Blank {
+                width: 4,
+                height: 4,
+                format: TextureFormat::Standard,
+            },
+            layers: 1,
+            mip_levels: MipLevels::None,
+            samples,
+        };
+        let cause = |result: Result<(), TextureError>| match result {
+            Err(TextureError::IllegalSampleCount { cause, .. }) => cause,
+            result => panic!("Expected an illegal sample count, got: {:?}", result),
+        };
+
+        {
+            let descriptor = descriptor(TextureDimension::D2, attachment, 4);
+            let result = descriptor.err_on_samples(features, TextureFormat::Standard, 1);
+            assert!(result.is_ok());
+        }
+
+        {
+            let image = TextureUsage::Image {
+                is_writable: false,
+                is_readable: false,
+            };
+            let descriptor = descriptor(TextureDimension::D2, image, 4);
+            let result = descriptor.err_on_samples(features, TextureFormat::Standard, 1);
+            assert!(result.is_ok());
+        }
+
+        {
+            let storage = TextureUsage::Storage {
+                is_writable: false,
+                is_readable: false,
+            };
+            let descriptor = descriptor(TextureDimension::D2, storage, 4);
+            let result = descriptor.err_on_samples(features, TextureFormat::Standard, 1);
+            assert!(cause(result).contains("images or attachments"));
+        }
+
+        {
+            let descriptor = descriptor(TextureDimension::D2, attachment, 4);
+            let result = descriptor.err_on_samples(features, TextureFormat::Standard, 2);
+            assert!(cause(result).contains("mip levels"));
+        }
+
+        {
+            let descriptor = descriptor(TextureDimension::D3, attachment, 4);
+            let result = descriptor.err_on_samples(features, TextureFormat::Standard, 1);
+            assert!(cause(result).contains("2D"));
+        }
+
+        {
+            let readable = TextureUsage::Attachment {
+                is_writable: false,
+                is_readable: true,
+            };
+            let descriptor = descriptor(TextureDimension::D2, readable, 4);
+            let result = descriptor.err_on_samples(features, TextureFormat::Standard, 1);
+            assert!(cause(result).contains("neither writable nor readable"));
+        }
+
+        {
+            let descriptor = descriptor(TextureDimension::D2, attachment, 3);
+            let result = descriptor.err_on_samples(features, TextureFormat::Standard, 1);
+            assert!(cause(result).contains("doesn't support"));
+        }
+
+        {
+            let descriptor = descriptor(TextureDimension::D2, attachment, 0);
+            let result = descriptor.err_on_samples(features, TextureFormat::Standard, 1);
+            assert!(cause(result).contains("zero"));
+        }
+    }
+
+    #[test]
+    fn layers() {
+        let image = TextureUsage::Image {
+            is_writable: true,
+            is_readable: false,
+        };
+        let descriptor = |dimension, source, layers| TextureDescriptor {
+            label: None,
+            dimension,
+            usage: image,
+            source,
+            layers,
+            mip_levels: MipLevels::None,
+            samples: 1,
+        };
+        let blank = || TextureSource::Blank {
+            width: 4,
+            height: 4,
+            format: TextureFormat::Standard,
+        };
+        let cubemap = || TextureSource::Cubemap {
+            faces: Default::default(),
+        };
+        let cause = |result: Result<(), TextureError>| match result {
+            Err(TextureError::IllegalLayers { cause, .. }) => cause,
+            result => panic!("Expected an illegal layer count, got: {:?}", result),
+        };
+
+        {
+            let result = descriptor(TextureDimension::D2, blank(), 8).err_on_layers();
+            assert!(result.is_ok());
+        }
+
+        {
+            let result = descriptor(TextureDimension::D2, cubemap(), 6).err_on_layers();
+            assert!(result.is_ok());
+        }
+
+        {
+            // The layers of a cubemap are derived from its faces
+            for layers in [0, 1, 6, 8] {
+                let descriptor = descriptor(TextureDimension::D2, cubemap(), layers);
+                assert!(descriptor.err_on_layers().is_ok());
+                assert_eq!(descriptor.layer_count(), 6);
+            }
+            assert_eq!(
+                descriptor(TextureDimension::D2, blank(), 3).layer_count(),
+                3
+            );
+        }
+
+        {
+            let result = descriptor(TextureDimension::D3, cubemap(), 1).err_on_layers();
+            assert!(cause(result).contains("must be 2D"));
+        }
+
+        {
+            let result = descriptor(TextureDimension::D3, blank(), 4).err_on_layers();
+            assert!(cause(result).contains("Only 2D"));
+        }
+
+        {
+            let file = TextureSource::File {
+                path: PathBuf::new(),
+            };
+            let result = descriptor(TextureDimension::D2, file, 2).err_on_layers();
+            assert!(cause(result).contains("single layer"));
+        }
+
+        {
+            let result = descriptor(TextureDimension::D2, blank(), 0).err_on_layers();
+            assert!(cause(result).contains("zero"));
+        }
+    }
+
+    #[cfg(feature = "ktx2")]
+    #[test]
+    fn ktx2_file() {
+        // Lays out a single level KTX2 file: the header, the level index, an empty DFD and the level data
+        let file = |format: u32, supercompression: u32, face_count: u32, data: &[u8]| {
+            let header = ktx2::Header {
+                format: ktx2::Format::new(format),
+                type_size: 1,
+                pixel_width: 4,
+                pixel_height: 4,
+                pixel_depth: 0,
+                layer_count: 0,
+                face_count,
+                level_count: 1,
+                supercompression_scheme: ktx2::SupercompressionScheme::new(supercompression),
+                index: ktx2::Index {
+                    dfd_byte_offset: 104,
+                    dfd_byte_length: 4,
+                    kvd_byte_offset: 0,
+                    kvd_byte_length: 0,
+                    sgd_byte_offset: 0,
+                    sgd_byte_length: 0,
+                },
+            };
+            let level = ktx2::LevelIndex {
+                byte_offset: 108,
+                byte_length: data.len() as u64,
+                uncompressed_byte_length: data.len() as u64,
+            };
+            let mut file = header.as_bytes().to_vec();
+            file.extend_from_slice(&level.as_bytes());
+            file.extend_from_slice(&4u32.to_le_bytes());
+            file.extend_from_slice(data);
+            file
+        };
+        let block: Vec<u8> = (0..16).collect();
+        let bc7_srgb = ktx2::Format::BC7_SRGB_BLOCK.value();
+
+        {
+            let source = decode_ktx2(&file(bc7_srgb, 0, 1, &block)).unwrap();
+            let TextureSource::Compressed {
+                width,
+                height,
+                format,
+                bytes,
+            } = source
+            else {
+                panic!("Expected a compressed source, got {:?}", source);
+            };
+            assert_eq!((width, height), (4, 4));
+            assert!(matches!(format, TextureFormat::Bc7));
+            assert_eq!(bytes, block);
+        }
+
+        {
+            let bc1_srgb = ktx2::Format::BC1_RGBA_SRGB_BLOCK.value();
+            let source = decode_ktx2(&file(bc1_srgb, 0, 1, &block[..8])).unwrap();
+            assert!(matches!(source.format(), TextureFormat::Bc1));
+        }
+
+        {
+            let bc7_unorm = ktx2::Format::BC7_UNORM_BLOCK.value();
+            let result = decode_ktx2(&file(bc7_unorm, 0, 1, &block));
+            assert!(
+                result
+                    .unwrap_err()
+                    .to_string()
+                    .contains("Unsupported KTX2 format")
+            );
+        }
+
+        {
+            let zstd = 2;
+            let result = decode_ktx2(&file(bc7_srgb, zstd, 1, &block));
+            assert!(result.unwrap_err().to_string().contains("Supercompressed"));
+        }
+
+        {
+            let result = decode_ktx2(&file(bc7_srgb, 0, 6, &block));
+            assert!(
+                result
+                    .unwrap_err()
+                    .to_string()
+                    .contains("single layer and face")
+            );
+        }
+
+        {
+            assert!(decode_ktx2(b"not a ktx2 file").is_err());
         }
     }
 }
@@ -0,0 +1,448 @@
+use std::fmt::Debug;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::{Device, Queue};
+
+use crate::{
+    graphics::{
+        buffer::{AnyBufferHandle, BufferHandle, BufferUsage},
+        color::Color,
+        group::{BindGroupLayout, BindGroupLayoutBuilder, ResourceAccess},
+    },
+    math::{mat4::Mat4, vec2::Vec2, vec3::Vec3},
+};
+
+/// A uniform buffer paired with the value it holds.
+///
+/// Most shader data that changes every frame (a camera matrix, the time, the resolution)
+/// is a single value in a single uniform buffer, a [`Uniform`] packages both together.
+///
+/// ```rust
+/// # use whirl::{graphics::uniforms::Uniform, math::mat4::Mat4};
+/// # fn frame(device: &wgpu::Device, queue: &wgpu::Queue, view_projection: Mat4) {
+/// let mut camera = Uniform::new(&device, view_projection, Some("camera"));
+///
+/// // Every frame
+/// camera.set(view_projection);
+/// camera.upload(&queue);
+/// # }
+/// ```
+///
+/// A [`Uniform`] implements [`AnyBufferHandle`], so it can be bound directly.
+/// Its buffer is readable, so the value the GPU sees can be read back with [`BufferHandle::read()`].
+///
+/// Keep in mind that `T` must match the layout of the uniform in WGSL,
+/// so fields may need to be padded to the alignment WGSL expects (e.g. a `vec3<f32>` is 16-byte aligned).
+#[derive(Debug)]
+pub struct Uniform<T: Pod> {
+    /// The writable (and readable) uniform buffer that holds exactly one item, the value
+    buffer: BufferHandle<T>,
+}
+
+impl<T: Pod> Uniform<T> {
+    /// Creates a new uniform buffer holding `value`.
+    /// - `device` -> the [`wgpu::Device`] needed to create the buffer
+    /// - `value` -> the initial value, uploaded immediately
+    /// - `label` -> an optional debugging label which is assigned to the buffer
+    pub fn new(device: &Device, value: T, label: Option<&str>) -> Self {
+        Self {
+            buffer: BufferHandle::create(
+                device,
+                &[value],
+                BufferUsage::Uniform {
+                    is_writable: true,
+                    is_readable: true,
+                },
+                label,
+            ),
+        }
+    }
+
+    /// Sets the value of the uniform.
+    ///
+    /// This function does not upload, meaning it does not update the GPU buffer after calling it.
+    ///
+    /// To accomplish that, call [`Uniform::upload()`].
+    pub fn set(&mut self, value: T) {
+        self.buffer.skip_and_update_item(0, value);
+    }
+
+    /// Uploads the value of the uniform to the GPU buffer.
+    pub fn upload(&self, queue: &Queue) {
+        queue.write_buffer(
+            self.buffer.raw(),
+            0,
+            bytemuck::cast_slice(self.buffer.items()),
+        );
+    }
+
+    /// Returns the value of the uniform.
+    pub fn get(&self) -> T {
+        self.buffer.items()[0]
+    }
+
+    /// Returns the underlying [`BufferHandle`].
+    pub fn buffer(&self) -> &BufferHandle<T> {
+        &self.buffer
+    }
+}
+
+impl<T: Debug + Pod> AnyBufferHandle for Uniform<T> {
+    fn raw(&self) -> &wgpu::Buffer {
+        self.buffer.raw()
+    }
+
+    fn as_slice(&self) -> wgpu::BufferSlice<'_> {
+        self.buffer.as_slice()
+    }
+}
+
+/// The per-frame data nearly every shader needs, laid out to match the following WGSL struct:
+///
+/// ```wgsl
+/// struct Globals {
+///     time: f32,
+///     delta_time: f32,
+///     resolution: vec2<f32>,
+/// }
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+pub struct GlobalsUniform {
+    /// The time elapsed since the globals were created (in seconds)
+    pub time: f32,
+    /// The time elapsed since the previous frame (in seconds)
+    pub delta_time: f32,
+    /// The size of the render target (in pixels)
+    pub resolution: Vec2,
+}
+
+/// A [`GlobalsUniform`] paired with its uniform buffer, updated once per frame.
+///
+/// ```rust
+/// # use whirl::graphics::{group::BindGroupBuilder, uniforms::{Globals, globals_layout}};
+/// # fn run(device: &wgpu::Device, queue: &wgpu::Queue, width: u32, height: u32, delta_time: f32) {
+/// let mut globals = Globals::new(device, width as f32, height as f32);
+/// let layout = globals_layout(device);
+/// let group = BindGroupBuilder::new()
+///     .add_buffer(&globals)
+///     .build(&layout, device);
+///
+/// // Every frame
+/// globals.tick(delta_time);
+/// globals.upload(queue);
+///
+/// // Whenever the window is resized
+/// globals.resize(width as f32, height as f32);
+/// # }
+/// ```
+///
+/// The globals are meant to be bound at group `0` (see [`globals_layout()`]),
+/// so every shader can rely on finding them in the same place.
+///
+/// A [`Globals`] implements [`AnyBufferHandle`], so it can be bound directly.
+#[derive(Debug)]
+pub struct Globals {
+    /// The uniform holding the globals
+    uniform: Uniform<GlobalsUniform>,
+}
+
+/// The camera data of a forward-lit scene, laid out to match the following WGSL struct:
+///
+/// ```wgsl
+/// struct Camera {
+///     view_projection: mat4x4<f32>,
+///     position: vec3<f32>,
+/// }
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+pub struct CameraUniform {
+    /// The view-projection matrix of the camera
+    pub view_projection: Mat4,
+    /// The world space position of the camera, needed for specular lighting
+    pub position: Vec3,
+    /// Pads the struct to the 16-byte alignment of `mat4x4<f32>`
+    _padding: f32,
+}
+
+/// A light that shines in every direction from a point, laid out to match the following WGSL struct:
+///
+/// ```wgsl
+/// struct PointLight {
+///     position: vec3<f32>,
+///     range: f32,
+///     color: vec3<f32>,
+///     intensity: f32,
+/// }
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+pub struct PointLight {
+    /// The world space position of the light
+    pub position: Vec3,
+    /// The distance at which the light no longer has any effect
+    pub range: f32,
+    /// The color of the light (RGB)
+    pub color: Vec3,
+    /// The brightness of the light
+    pub intensity: f32,
+}
+
+/// A light that shines in a single direction from infinitely far away (e.g. the sun),
+/// laid out to match the following WGSL struct:
+///
+/// ```wgsl
+/// struct DirectionalLight {
+///     direction: vec3<f32>,
+///     intensity: f32,
+///     color: vec3<f32>,
+/// }
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+pub struct DirectionalLight {
+    /// The direction the light travels in, normalized
+    pub direction: Vec3,
+    /// The brightness of the light
+    pub intensity: f32,
+    /// The color of the light (RGB)
+    pub color: Vec3,
+    /// Pads the struct to the 16-byte alignment of `vec3<f32>`
+    _padding: f32,
+}
+
+impl Globals {
+    /// Creates the globals, with the time starting at zero
+    /// - `device` -> the [`wgpu::Device`] needed to create the buffer
+    /// - `width` -> the width of the render target (in pixels)
+    /// - `height` -> the height of the render target (in pixels)
+    pub fn new(device: &Device, width: f32, height: f32) -> Self {
+        Self {
+            uniform: Uniform::new(
+                device,
+                GlobalsUniform {
+                    time: 0.0,
+                    delta_time: 0.0,
+                    resolution: Vec2::new(width, height),
+                },
+                Some("globals"),
+            ),
+        }
+    }
+
+    /// Advances the time by `delta_time` (in seconds), which also becomes the new delta time.
+    ///
+    /// This function does not upload, call [`Globals::upload()`] afterwards.
+    pub fn tick(&mut self, delta_time: f32) {
+        let mut globals = self.uniform.get();
+        globals.tick(delta_time);
+        self.uniform.set(globals);
+    }
+
+    /// Sets the resolution to the new size of the render target (in pixels).
+    ///
+    /// This function does not upload, call [`Globals::upload()`] afterwards.
+    pub fn resize(&mut self, width: f32, height: f32) {
+        let mut globals = self.uniform.get();
+        globals.resize(width, height);
+        self.uniform.set(globals);
+    }
+
+    /// Uploads the globals to the GPU buffer.
+    pub fn upload(&self, queue: &Queue) {
+        self.uniform.upload(queue);
+    }
+
+    /// Returns the current globals.
+    pub fn get(&self) -> GlobalsUniform {
+        self.uniform.get()
+    }
+
+    /// Returns the underlying [`Uniform`].
+    pub fn uniform(&self) -> &Uniform<GlobalsUniform> {
+        &self.uniform
+    }
+}
+
+impl GlobalsUniform {
+    /// Advances the time by `delta_time` (in seconds), which also becomes the new delta time
+    pub fn tick(&mut self, delta_time: f32) {
+        self.time += delta_time;
+        self.delta_time = delta_time;
+    }
+
+    /// Sets the resolution to the new size of the render target (in pixels)
+    pub fn resize(&mut self, width: f32, height: f32) {
+        self.resolution = Vec2::new(width, height);
+    }
+}
+
+impl AnyBufferHandle for Globals {
+    fn raw(&self) -> &wgpu::Buffer {
+        self.uniform.raw()
+    }
+
+    fn as_slice(&self) -> wgpu::BufferSlice<'_> {
+        self.uniform.as_slice()
+    }
+}
+
+impl CameraUniform {
+    /// Creates the camera data
+    /// - `view_projection` -> the view-projection matrix of the camera
+    /// - `position` -> the world space position of the camera
+    pub fn new(view_projection: Mat4, position: Vec3) -> Self {
+        Self {
+            view_projection,
+            position,
+            _padding: 0.0,
+        }
+    }
+}
+
+impl PointLight {
+    /// Creates a point light
+    /// - `position` -> the world space position of the light
+    /// - `color` -> the color of the light, the alpha channel is ignored
+    /// - `intensity` -> the brightness of the light
+    /// - `range` -> the distance at which the light no longer has any effect
+    pub fn new(position: Vec3, color: Color, intensity: f32, range: f32) -> Self {
+        Self {
+            position,
+            range,
+            color: Vec3::new(color.red(), color.green(), color.blue()),
+            intensity,
+        }
+    }
+}
+
+impl DirectionalLight {
+    /// Creates a directional light
+    /// - `direction` -> the direction the light travels in, it gets normalized
+    /// - `color` -> the color of the light, the alpha channel is ignored
+    /// - `intensity` -> the brightness of the light
+    pub fn new(direction: Vec3, color: Color, intensity: f32) -> Self {
+        Self {
+            direction: direction.normalize(),
+            intensity,
+            color: Vec3::new(color.red(), color.green(), color.blue()),
+            _padding: 0.0,
+        }
+    }
+}
+
+/// Creates the bind group layout of the [`Globals`]
+/// - `device` -> the [`wgpu::Device`] needed to create the layout
+///
+/// The layout expects a single [`GlobalsUniform`] in a uniform buffer (e.g. a [`Globals`])
+/// visible to both the vertex and the fragment stage, matching this WGSL declaration:
+///
+/// ```wgsl
+/// @group(0) @binding(0) var<uniform> globals: Globals;
+/// ```
+pub fn globals_layout(device: &Device) -> BindGroupLayout {
+    BindGroupLayoutBuilder::new()
+        .label("globals layout")
+        .add_uniform_buffer(ResourceAccess::Either)
+        .build(device)
+}
+
+/// Creates the bind group layout of a forward-lit scene
+/// - `device` -> the [`wgpu::Device`] needed to create the layout
+///
+/// The layout expects the following resources, matching these WGSL declarations:
+///
+/// ```wgsl
+/// @group(0) @binding(0) var<uniform> camera: Camera;
+/// @group(0) @binding(1) var<storage, read> point_lights: array<PointLight>;
+/// @group(0) @binding(2) var<storage, read> directional_lights: array<DirectionalLight>;
+/// ```
+///
+/// - binding `0` -> a [`CameraUniform`] in a uniform buffer (e.g. a [`Uniform<CameraUniform>`])
+/// - binding `1` -> a storage buffer of [`PointLight`]s
+/// - binding `2` -> a storage buffer of [`DirectionalLight`]s
+///
+/// The light count of each list is the length of the WGSL runtime-sized array (`arrayLength`),
+/// so the storage buffers should hold exactly the lights in the scene.
+/// Since storage buffers can't be empty, a zeroed light (with no intensity) can be used as filler.
+pub fn forward_lighting_layout(device: &Device) -> BindGroupLayout {
+    BindGroupLayoutBuilder::new()
+        .label("forward lighting layout")
+        .add_uniform_buffer(ResourceAccess::Either)
+        .add_storage_buffer(ResourceAccess::Fragment)
+        .add_storage_buffer(ResourceAccess::Fragment)
+        .build(device)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        {
+            assert_eq!(size_of::<GlobalsUniform>(), 16);
+            assert_eq!(std::mem::offset_of!(GlobalsUniform, delta_time), 4);
+            assert_eq!(std::mem::offset_of!(GlobalsUniform, resolution), 8);
+        }
+
+        {
+            assert_eq!(size_of::<CameraUniform>(), 80);
+            assert_eq!(std::mem::offset_of!(CameraUniform, position), 64);
+        }
+
+        {
+            assert_eq!(size_of::<PointLight>(), 32);
+            assert_eq!(std::mem::offset_of!(PointLight, range), 12);
+            assert_eq!(std::mem::offset_of!(PointLight, color), 16);
+            assert_eq!(std::mem::offset_of!(PointLight, intensity), 28);
+        }
+
+        {
+            assert_eq!(size_of::<DirectionalLight>(), 32);
+            assert_eq!(std::mem::offset_of!(DirectionalLight, intensity), 12);
+            assert_eq!(std::mem::offset_of!(DirectionalLight, color), 16);
+        }
+    }
+
+    #[test]
+    fn globals_tick() {
+        let mut globals = GlobalsUniform::zeroed();
+
+        {
+            globals.tick(0.25);
+            assert_eq!((globals.time, globals.delta_time), (0.25, 0.25));
+        }
+
+        {
+            // The time accumulates while the delta time only keeps the last frame
+            globals.tick(0.5);
+            globals.tick(0.125);
+            assert_eq!((globals.time, globals.delta_time), (0.875, 0.125));
+        }
+
+        {
+            globals.tick(0.0);
+            assert_eq!((globals.time, globals.delta_time), (0.875, 0.0));
+        }
+    }
+
+    #[test]
+    fn globals_resize() {
+        let mut globals = GlobalsUniform::zeroed();
+        globals.tick(1.0);
+
+        {
+            globals.resize(1280.0, 720.0);
+            assert_eq!(globals.resolution, Vec2::new(1280.0, 720.0));
+        }
+
+        {
+            // Resizing leaves the time untouched
+            globals.resize(640.0, 480.0);
+            assert_eq!(globals.resolution, Vec2::new(640.0, 480.0));
+            assert_eq!((globals.time, globals.delta_time), (1.0, 1.0));
+        }
+    }
+}
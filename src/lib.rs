@@ -15,6 +15,18 @@
 //! - Games
 //! - Simulations
 //! - General-purpose rendering
+//! - Headless rendering (automated visual testing, batch image generation, cloud rendering)
+//!
+//! ## Headless rendering
+//! Rendering doesn't require a window or a surface:
+//! 1. Build a [`graphics::context::Context`] with no compatible surface
+//!    (`ContextDescriptor::build(None)`), [`graphics::context::Backend::All`] picks up
+//!    software adapters too
+//! 2. Create a readable [`graphics::texture::TextureUsage::Attachment`] texture
+//!    of the [`graphics::texture::TextureFormat::Standard`] format as the render target
+//! 3. Record a render pass into its view and submit it like any other frame
+//! 4. Read the target back with [`graphics::texture::Texture::read_pixels()`],
+//!    or write it to a file with [`graphics::texture::Texture::save_png()`]
 
 pub mod math;
 pub mod graphics;
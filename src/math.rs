@@ -1,5 +1,7 @@
 //! The math module, containing all the essential types and functionality.
 //!
+use std::fmt;
+
 /// Contains functionality related to 2D vectors.
 pub mod vec2;
 /// Contains functionality related to 3D vectors.
@@ -14,6 +16,13 @@ pub mod mat4;
 /// Contains functionality related to quaternions.
 pub mod quat;
 
+/// Contains functionality related to axis-aligned bounding boxes.
+pub mod aabb;
+/// Contains functionality related to rays.
+pub mod ray;
+/// Contains functionality related to bounding spheres.
+pub mod sphere;
+
 /// `EPSILON` is a small number `(0.001)` that is used for equality comparisons
 pub const EPSILON: f32 = 1e-3;
 
@@ -31,3 +40,107 @@ pub fn cmp_f64(a: f64, b: f64, epsilon: f64) -> bool {
 pub fn lerp(a: f32, b: f32, factor: f32) -> f32 {
     a + (b - a) * factor
 }
+
+/// Clamps an f32 value to the range of `lo - hi`
+///
+/// Unlike [`f32::clamp()`], this doesn't panic if `lo` is greater than `hi`, in which case `hi` wins
+pub fn clamp(x: f32, lo: f32, hi: f32) -> f32 {
+    x.max(lo).min(hi)
+}
+
+/// Smoothly interpolates between 0 and 1 as `x` goes from `edge0` to `edge1`,
+/// easing in and out with a Hermite curve (`3t² - 2t³`)
+///
+/// Values outside of the edges are clamped, which gives 0 before `edge0` and 1 after `edge1`
+pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = clamp((x - edge0) / (edge1 - edge0), 0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Maps an f32 value from the range of `in_min - in_max` to the range of `out_min - out_max`
+///
+/// The value isn't clamped, so values outside of the input range end up outside of the output range
+pub fn remap(x: f32, in_min: f32, in_max: f32, out_min: f32, out_max: f32) -> f32 {
+    lerp(out_min, out_max, (x - in_min) / (in_max - in_min))
+}
+
+/// The amount of decimal places used when displaying math types without a specified precision
+const DISPLAY_PRECISION: usize = 2;
+
+/// Writes a list of components as `(x, y, ...)`, honoring the formatter's precision
+fn fmt_components(f: &mut fmt::Formatter, components: &[f32]) -> fmt::Result {
+    let precision = f.precision().unwrap_or(DISPLAY_PRECISION);
+    write!(f, "(")?;
+    for (i, component) in components.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{:.*}", precision, component)?;
+    }
+    write!(f, ")")
+}
+
+/// Writes the rows of a matrix as an aligned grid, honoring the formatter's precision
+fn fmt_rows<const N: usize>(f: &mut fmt::Formatter, rows: [[f32; N]; N]) -> fmt::Result {
+    let precision = f.precision().unwrap_or(DISPLAY_PRECISION);
+    let cells = rows.map(|row| row.map(|value| format!("{:.*}", precision, value)));
+    let mut widths = [0; N];
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    for (i, row) in cells.iter().enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+        write!(f, "[")?;
+        for (j, (cell, width)) in row.iter().zip(widths).enumerate() {
+            if j > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:>width$}", cell)?;
+        }
+        write!(f, "]")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smoothstep_edges() {
+        {
+            assert!(cmp_f32(smoothstep(1.0, 3.0, 1.0), 0.0, EPSILON));
+            assert!(cmp_f32(smoothstep(1.0, 3.0, 3.0), 1.0, EPSILON));
+            assert!(cmp_f32(smoothstep(1.0, 3.0, 2.0), 0.5, EPSILON));
+        }
+
+        {
+            // Clamped outside of the edges
+            assert!(cmp_f32(smoothstep(1.0, 3.0, -5.0), 0.0, EPSILON));
+            assert!(cmp_f32(smoothstep(1.0, 3.0, 10.0), 1.0, EPSILON));
+            assert!(cmp_f32(smoothstep(0.0, 1.0, 0.25), 0.15625, EPSILON));
+        }
+    }
+
+    #[test]
+    fn clamp_and_remap() {
+        {
+            assert!(cmp_f32(clamp(5.0, -1.0, 1.0), 1.0, EPSILON));
+            assert!(cmp_f32(clamp(-5.0, -1.0, 1.0), -1.0, EPSILON));
+            assert!(cmp_f32(clamp(0.5, -1.0, 1.0), 0.5, EPSILON));
+        }
+
+        {
+            assert!(cmp_f32(remap(0.0, 0.0, 1.0, -1.0, 1.0), -1.0, EPSILON));
+            assert!(cmp_f32(remap(0.75, 0.0, 1.0, -1.0, 1.0), 0.5, EPSILON));
+            assert!(cmp_f32(remap(1.0, 0.0, 1.0, -1.0, 1.0), 1.0, EPSILON));
+            assert!(cmp_f32(remap(15.0, 10.0, 20.0, 100.0, 0.0), 50.0, EPSILON));
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::math::vec3::Vec3;
+
+/// Represents an axis-aligned bounding box, described by its minimum and maximum corners
+///
+/// An AABB is the cheapest bounding volume to build and to test for overlaps,
+/// which makes it a good fit for broad-phase collision detection and culling
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Zeroable, Pod)]
+pub struct Aabb {
+    /// The corner with the smallest coordinates
+    pub min: Vec3,
+    /// The corner with the largest coordinates
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// Creates a new bounding box
+    /// - `min` -> the corner with the smallest coordinates
+    /// - `max` -> the corner with the largest coordinates
+    pub const fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    /// Creates the smallest bounding box that encloses all of the points
+    /// - `points` -> the points to enclose
+    ///
+    /// If `points` is empty, the caller thread panics
+    pub fn from_points(points: &[Vec3]) -> Self {
+        assert!(
+            !points.is_empty(),
+            "Cannot create a bounding box from an empty slice!"
+        );
+
+        points
+            .iter()
+            .fold(Self::new(points[0], points[0]), |aabb, &point| {
+                Self::new(aabb.min.min(point), aabb.max.max(point))
+            })
+    }
+
+    /// Returns the center of the box
+    pub fn center(&self) -> Vec3 {
+        self.min.lerp(self.max, 0.5)
+    }
+
+    /// Returns the half-size of the box along each axis, the distance from the center to the faces
+    pub fn extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    /// Returns whether the point lies within the box, including its faces
+    /// - `point` -> the point to test
+    pub fn contains(&self, point: Vec3) -> bool {
+        (self.min.x <= point.x && point.x <= self.max.x)
+            && (self.min.y <= point.y && point.y <= self.max.y)
+            && (self.min.z <= point.z && point.z <= self.max.z)
+    }
+
+    /// Returns the smallest box that encloses both boxes
+    /// - `self` -> the first box
+    /// - `other` -> the second box
+    pub fn merge(&self, other: &Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// Returns whether the boxes overlap, boxes that only touch count as overlapping
+    /// - `self` -> the first box
+    /// - `other` -> the second box
+    pub fn intersects(&self, other: &Self) -> bool {
+        (self.min.x <= other.max.x && other.min.x <= self.max.x)
+            && (self.min.y <= other.max.y && other.min.y <= self.max.y)
+            && (self.min.z <= other.max.z && other.min.z <= self.max.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::EPSILON;
+
+    #[test]
+    fn from_points() {
+        let points = [
+            Vec3::new(1.0, -2.0, 0.0),
+            Vec3::new(-1.0, 4.0, 2.0),
+            Vec3::new(0.0, 0.0, -3.0),
+        ];
+        let aabb = Aabb::from_points(&points);
+        assert!(aabb.min.cmp(Vec3::new(-1.0, -2.0, -3.0), EPSILON));
+        assert!(aabb.max.cmp(Vec3::new(1.0, 4.0, 2.0), EPSILON));
+        assert!(aabb.center().cmp(Vec3::new(0.0, 1.0, -0.5), EPSILON));
+        assert!(aabb.extents().cmp(Vec3::new(1.0, 3.0, 2.5), EPSILON));
+        for point in points {
+            assert!(aabb.contains(point));
+        }
+        assert!(!aabb.contains(Vec3::new(0.0, 5.0, 0.0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot create a bounding box from an empty slice!")]
+    fn from_no_points() {
+        Aabb::from_points(&[]);
+    }
+
+    #[test]
+    fn merge() {
+        let a = Aabb::new(Vec3::new(-2.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 1.0));
+        let b = Aabb::new(Vec3::new(0.0, -1.0, 0.5), Vec3::new(3.0, 0.5, 4.0));
+        let merged = a.merge(&b);
+        assert!(merged.min.cmp(Vec3::new(-2.0, -1.0, 0.0), EPSILON));
+        assert!(merged.max.cmp(Vec3::new(3.0, 1.0, 4.0), EPSILON));
+        assert_eq!(merged, b.merge(&a));
+    }
+
+    #[test]
+    fn intersects() {
+        let a = Aabb::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 1.0));
+        {
+            let overlapping = Aabb::new(Vec3::new(0.5, 0.5, 0.5), Vec3::new(2.0, 2.0, 2.0));
+            assert!(a.intersects(&overlapping));
+            assert!(overlapping.intersects(&a));
+        }
+
+        {
+            let touching = Aabb::new(Vec3::new(1.0, 0.0, 0.0), Vec3::new(2.0, 1.0, 1.0));
+            assert!(a.intersects(&touching));
+        }
+
+        {
+            // Overlapping on 2 axes isn't enough
+            let separated = Aabb::new(Vec3::new(0.0, 0.0, 1.5), Vec3::new(1.0, 1.0, 2.0));
+            assert!(!a.intersects(&separated));
+            assert!(!separated.intersects(&a));
+        }
+    }
+}
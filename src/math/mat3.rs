@@ -1,8 +1,8 @@
-use std::ops::Mul;
+use std::{fmt, ops::Mul};
 
 use bytemuck::{Pod, Zeroable};
 
-use crate::math::{vec2::Vec2, vec3::Vec3};
+use crate::math::{self, mat4::Mat4, vec2::Vec2, vec3::Vec3, vec4::Vec4};
 
 /// A matrix represents a linear transformation that is performed on a vector
 ///
@@ -11,7 +11,8 @@ use crate::math::{vec2::Vec2, vec3::Vec3};
 /// - Scaling -> scaling a vector in 2D space
 /// - Rotating -> rotating a vector in 2D space
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+#[derive(Debug, Clone, Copy, PartialEq, Zeroable, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mat3 {
     /// The vector that determines where the X basis unit vector lands
     pub x_axis: Vec3,
@@ -22,13 +23,16 @@ pub struct Mat3 {
 }
 
 impl Mat3 {
+    /// The identity matrix, which doesn't transform vectors at all
+    pub const IDENTITY: Self = Self {
+        x_axis: Vec3::new(1.0, 0.0, 0.0),
+        y_axis: Vec3::new(0.0, 1.0, 0.0),
+        w_axis: Vec3::new(0.0, 0.0, 1.0),
+    };
+
     /// Creates a new identity matrix
     pub fn new() -> Self {
-        Self {
-            x_axis: Vec3::new(1.0, 0.0, 0.0),
-            y_axis: Vec3::new(0.0, 1.0, 0.0),
-            w_axis: Vec3::new(0.0, 0.0, 1.0),
-        }
+        Self::IDENTITY
     }
 
     /// Creates a new scale matrix
@@ -67,6 +71,21 @@ impl Mat3 {
         }
     }
 
+    /// Creates a new matrix from the upper-left 3x3 of a [`Mat4`], dropping its translation
+    ///
+    /// The `xyz` components of the `x_axis`, `y_axis` and `z_axis` of the [`Mat4`] become the
+    /// `x_axis`, `y_axis` and `w_axis` of the [`Mat3`], so the resulting matrix carries the rotation
+    /// and scale of the 3D transformation (rather than being a 2D transformation)
+    /// - `mat` -> the [`Mat4`] to extract the 3x3 from
+    pub fn from_mat4(mat: Mat4) -> Self {
+        let xyz = |axis: Vec4| Vec3::new(axis.x, axis.y, axis.z);
+        Self {
+            x_axis: xyz(mat.x_axis),
+            y_axis: xyz(mat.y_axis),
+            w_axis: xyz(mat.z_axis),
+        }
+    }
+
     /// Multiplies this matrix by a vector
     ///
     /// This effectively applies the linear transformation described by the matrix
@@ -110,8 +129,39 @@ impl Mat3 {
         }
     }
 
+    /// Computes the determinant of the matrix, the factor by which it scales areas
+    /// (of 2D transformations) or volumes
+    ///
+    /// A determinant of zero means the matrix is degenerate, while a negative determinant means it mirrors space
+    pub fn determinant(self) -> f32 {
+        self.x_axis.dot(self.y_axis.cross(self.w_axis))
+    }
+
+    /// Computes the inverse of the matrix, the transformation that undoes this one
+    ///
+    /// Returns [`None`] if the matrix is singular (its determinant is within [`math::EPSILON`] of zero)
+    pub fn inverse(self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant.abs() < math::EPSILON {
+            return None;
+        }
+
+        // The rows of the adjugate are the cross products of the axes
+        let adjugate = Self {
+            x_axis: self.y_axis.cross(self.w_axis),
+            y_axis: self.w_axis.cross(self.x_axis),
+            w_axis: self.x_axis.cross(self.y_axis),
+        }
+        .transpose();
+        Some(Self {
+            x_axis: adjugate.x_axis / determinant,
+            y_axis: adjugate.y_axis / determinant,
+            w_axis: adjugate.w_axis / determinant,
+        })
+    }
+
     /// Returns the matrix data as an array
-    fn raw(&self) -> [[f32; 3]; 3] {
+    pub fn raw(&self) -> [[f32; 3]; 3] {
         [
             [self.x_axis.x, self.x_axis.y, self.x_axis.z],
             [self.y_axis.x, self.y_axis.y, self.y_axis.z],
@@ -120,7 +170,7 @@ impl Mat3 {
     }
 
     /// Composes a new matrix from an array
-    fn of(data: [[f32; 3]; 3]) -> Self {
+    pub fn of(data: [[f32; 3]; 3]) -> Self {
         Self {
             x_axis: Vec3::new(data[0][0], data[0][1], data[0][2]),
             y_axis: Vec3::new(data[1][0], data[1][1], data[1][2]),
@@ -134,7 +184,7 @@ impl Mat3 {
     /// - `self` -> the first matrix
     /// - `other` -> the second matrix
     /// - `epsilon` -> a very small value
-    fn cmp(&self, other: &Self, epsilon: f32) -> bool {
+    pub fn cmp(&self, other: &Self, epsilon: f32) -> bool {
         let m1 = self.raw();
         let m2 = other.raw();
         for i in 0..3 {
@@ -159,6 +209,14 @@ impl Default for Mat3 {
     }
 }
 
+/// Displays the matrix as a grid of rows, where each column is one of the matrix axes
+impl fmt::Display for Mat3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows = self.transpose().raw();
+        math::fmt_rows(f, rows)
+    }
+}
+
 impl Mul for Mat3 {
     type Output = Self;
 
@@ -178,7 +236,7 @@ impl Mul<Vec3> for Mat3 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::math::EPSILON;
+    use crate::math::{EPSILON, cmp_f32, quat::Quat};
     use std::f32::consts::PI;
 
     #[test]
@@ -249,6 +307,73 @@ mod tests {
         }
     }
 
+    #[test]
+    fn determinant() {
+        {
+            assert!(cmp_f32(Mat3::new().determinant(), 1.0, EPSILON));
+            assert!(cmp_f32(
+                Mat3::scale(Vec2::new(2.0, 3.0)).determinant(),
+                6.0,
+                EPSILON
+            ));
+            assert!(cmp_f32(
+                Mat3::scale(Vec2::new(-2.0, 3.0)).determinant(),
+                -6.0,
+                EPSILON
+            ));
+        }
+
+        {
+            // Translations and rotations preserve areas
+            let m = Mat3::translate(Vec2::new(4.0, -1.0)) * Mat3::rotate(PI / 3.0);
+            assert!(cmp_f32(m.determinant(), 1.0, EPSILON));
+            assert!(cmp_f32(
+                Mat3::scale(Vec2::new(0.0, 3.0)).determinant(),
+                0.0,
+                EPSILON
+            ));
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let identity = Mat3::new();
+        {
+            let m = Mat3::translate(Vec2::new(3.0, -2.0)) * Mat3::rotate(PI / 3.0);
+            let inverse = m.inverse().unwrap();
+            assert!((m * inverse).cmp(&identity, EPSILON));
+            assert!((inverse * m).cmp(&identity, EPSILON));
+
+            let expected = Mat3::rotate(-PI / 3.0) * Mat3::translate(Vec2::new(-3.0, 2.0));
+            assert!(inverse.cmp(&expected, EPSILON));
+
+            // Undoing the transform of a point
+            let point = Vec3::new(1.0, 2.0, 1.0);
+            assert!((inverse * (m * point)).cmp(point, EPSILON));
+        }
+
+        {
+            assert!(Mat3::scale(Vec2::new(0.0, 3.0)).inverse().is_none());
+        }
+    }
+
+    #[test]
+    fn from_mat4() {
+        let q = Quat::from_axis(Vec3::new(1.0, 2.0, -0.5).normalize(), PI / 5.0);
+        let v = Vec3::new(3.0, -1.0, 2.0);
+        {
+            let rotated = (Mat4::rotate(q) * Vec4::new(v.x, v.y, v.z, 0.0)).truncate();
+            assert!((Mat3::from_mat4(Mat4::rotate(q)) * v).cmp(rotated, EPSILON));
+        }
+
+        {
+            // The translation is dropped and restored as the identity
+            let m = Mat4::from_trs(Vec3::new(1.0, 2.0, 3.0), q, Vec3::new(2.0, 1.0, 0.5));
+            let expected = Mat4::from_trs(Vec3::new(0.0, 0.0, 0.0), q, Vec3::new(2.0, 1.0, 0.5));
+            assert!(Mat4::from_mat3(Mat3::from_mat4(m)).cmp(&expected, EPSILON));
+        }
+    }
+
     #[test]
     fn multiply_mat() {
         {
@@ -1,8 +1,11 @@
-use std::ops::Mul;
+use std::{
+    fmt,
+    ops::{Add, Mul, Sub},
+};
 
 use bytemuck::{Pod, Zeroable};
 
-use crate::math::{quat::Quat, vec3::Vec3, vec4::Vec4};
+use crate::math::{self, mat3::Mat3, quat::Quat, vec2::Vec2, vec3::Vec3, vec4::Vec4};
 
 /// A matrix represents a linear transformation that is performed on a vector
 ///
@@ -11,7 +14,8 @@ use crate::math::{quat::Quat, vec3::Vec3, vec4::Vec4};
 /// - Scaling -> scaling a vector in 3D space
 /// - Rotating -> rotating a vector in 3D space (with the help of quaternions)
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+#[derive(Debug, Clone, Copy, PartialEq, Zeroable, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mat4 {
     /// The vector that determines where the X basis unit vector lands
     pub x_axis: Vec4,
@@ -24,14 +28,17 @@ pub struct Mat4 {
 }
 
 impl Mat4 {
+    /// The identity matrix, which doesn't transform vectors at all
+    pub const IDENTITY: Self = Self {
+        x_axis: Vec4::new(1.0, 0.0, 0.0, 0.0),
+        y_axis: Vec4::new(0.0, 1.0, 0.0, 0.0),
+        z_axis: Vec4::new(0.0, 0.0, 1.0, 0.0),
+        w_axis: Vec4::new(0.0, 0.0, 0.0, 1.0),
+    };
+
     /// Creates a new identity matrix
     pub fn new() -> Self {
-        Self {
-            x_axis: Vec4::new(1.0, 0.0, 0.0, 0.0),
-            y_axis: Vec4::new(0.0, 1.0, 0.0, 0.0),
-            z_axis: Vec4::new(0.0, 0.0, 1.0, 0.0),
-            w_axis: Vec4::new(0.0, 0.0, 0.0, 1.0),
-        }
+        Self::IDENTITY
     }
 
     /// Creates a new scale matrix
@@ -88,6 +95,39 @@ impl Mat4 {
         }
     }
 
+    /// Creates a new transformation matrix that scales, then rotates and then translates
+    ///
+    /// This produces the same matrix as `Mat4::translate(translation) * Mat4::rotate(rotation) * Mat4::scale(scale)`
+    /// without the matrix multiplications
+    /// - `translation` -> the [`Vec3`] containing the translation values for `x`, `y` and `z`
+    /// - `rotation` -> the [`Quat`] that specifies the rotation axis and the rotation amount
+    /// - `scale` -> the [`Vec3`] containing the scalar values for `x`, `y` and `z`
+    pub fn from_trs(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        let rotation = Self::rotate(rotation);
+        Self {
+            x_axis: rotation.x_axis * scale.x,
+            y_axis: rotation.y_axis * scale.y,
+            z_axis: rotation.z_axis * scale.z,
+            w_axis: Vec4::new(translation.x, translation.y, translation.z, 1.0),
+        }
+    }
+
+    /// Creates a new matrix with a [`Mat3`] as its upper-left 3x3 and no translation,
+    /// the inverse of [`Mat3::from_mat4()`]
+    ///
+    /// The `x_axis`, `y_axis` and `w_axis` of the [`Mat3`] become the `xyz` components of the
+    /// `x_axis`, `y_axis` and `z_axis` of the [`Mat4`], while its `w_axis` is `(0.0, 0.0, 0.0, 1.0)`
+    /// - `mat` -> the [`Mat3`] holding the rotation and scale
+    pub fn from_mat3(mat: Mat3) -> Self {
+        let xyz = |axis: Vec3| Vec4::new(axis.x, axis.y, axis.z, 0.0);
+        Self {
+            x_axis: xyz(mat.x_axis),
+            y_axis: xyz(mat.y_axis),
+            z_axis: xyz(mat.w_axis),
+            w_axis: Vec4::new(0.0, 0.0, 0.0, 1.0),
+        }
+    }
+
     /// Multiplies this matrix by a vector
     ///
     /// This effectively applies the linear transformation described by the matrix
@@ -103,6 +143,27 @@ impl Mat4 {
         }
     }
 
+    /// Transforms a point by this matrix, treating it as a [`Vec4`] with `w = 1.0`
+    /// so that translations apply to it
+    ///
+    /// The result is divided by its `w` (perspective division), so this works for
+    /// projection matrices as well, see [`Vec4::homogenize()`]
+    /// - `point` -> the point to transform
+    pub fn transform_point(self, point: Vec3) -> Vec3 {
+        self.multiply_vec(Vec4::new(point.x, point.y, point.z, 1.0))
+            .homogenize()
+    }
+
+    /// Transforms a direction by this matrix, treating it as a [`Vec4`] with `w = 0.0`
+    /// so that translations don't apply to it
+    ///
+    /// No perspective division takes place
+    /// - `vector` -> the direction to transform
+    pub fn transform_vector(self, vector: Vec3) -> Vec3 {
+        self.multiply_vec(Vec4::new(vector.x, vector.y, vector.z, 0.0))
+            .truncate()
+    }
+
     /// Multiplies this matrix by a matrix
     ///
     /// This effectively combines the linear transformations of both those matrices
@@ -136,6 +197,102 @@ impl Mat4 {
         }
     }
 
+    /// Multiplies every element of the matrix by a scalar
+    ///
+    /// Not to be confused with [`Mat4::scale()`], which creates a scaling matrix, this is
+    /// useful for weighting matrices before adding them together (e.g. when blending transforms)
+    pub fn scale_scalar(self, scalar: f32) -> Self {
+        Self {
+            x_axis: self.x_axis * scalar,
+            y_axis: self.y_axis * scalar,
+            z_axis: self.z_axis * scalar,
+            w_axis: self.w_axis * scalar,
+        }
+    }
+
+    /// Computes the determinant of the matrix, the factor by which it scales volumes
+    ///
+    /// A determinant of zero means the matrix is degenerate (it flattens space into a plane,
+    /// line or point), while a negative determinant means it mirrors space
+    pub fn determinant(self) -> f32 {
+        let (s, c) = Self::minors(&self.raw());
+        Self::expand(&s, &c)
+    }
+
+    /// Computes the inverse of the matrix, the transformation that undoes this one
+    ///
+    /// The inverse is useful for turning a world transform into a view matrix, unprojecting
+    /// screen coordinates back into the world or building normal matrices
+    ///
+    /// Returns [`None`] if the matrix is singular (its determinant is within [`math::EPSILON`] of zero),
+    /// since such a matrix collapses space and can't be undone, rather than panicking
+    pub fn inverse(self) -> Option<Self> {
+        let a = self.raw();
+        let (s, c) = Self::minors(&a);
+        let determinant = Self::expand(&s, &c);
+        if determinant.abs() < math::EPSILON {
+            return None;
+        }
+
+        // The adjugate (transposed cofactor matrix) divided by the determinant
+        let inv = 1.0 / determinant;
+        Some(Self::of([
+            [
+                (a[1][1] * c[5] - a[1][2] * c[4] + a[1][3] * c[3]) * inv,
+                (-a[0][1] * c[5] + a[0][2] * c[4] - a[0][3] * c[3]) * inv,
+                (a[3][1] * s[5] - a[3][2] * s[4] + a[3][3] * s[3]) * inv,
+                (-a[2][1] * s[5] + a[2][2] * s[4] - a[2][3] * s[3]) * inv,
+            ],
+            [
+                (-a[1][0] * c[5] + a[1][2] * c[2] - a[1][3] * c[1]) * inv,
+                (a[0][0] * c[5] - a[0][2] * c[2] + a[0][3] * c[1]) * inv,
+                (-a[3][0] * s[5] + a[3][2] * s[2] - a[3][3] * s[1]) * inv,
+                (a[2][0] * s[5] - a[2][2] * s[2] + a[2][3] * s[1]) * inv,
+            ],
+            [
+                (a[1][0] * c[4] - a[1][1] * c[2] + a[1][3] * c[0]) * inv,
+                (-a[0][0] * c[4] + a[0][1] * c[2] - a[0][3] * c[0]) * inv,
+                (a[3][0] * s[4] - a[3][1] * s[2] + a[3][3] * s[0]) * inv,
+                (-a[2][0] * s[4] + a[2][1] * s[2] - a[2][3] * s[0]) * inv,
+            ],
+            [
+                (-a[1][0] * c[3] + a[1][1] * c[1] - a[1][2] * c[0]) * inv,
+                (a[0][0] * c[3] - a[0][1] * c[1] + a[0][2] * c[0]) * inv,
+                (-a[3][0] * s[3] + a[3][1] * s[1] - a[3][2] * s[0]) * inv,
+                (a[2][0] * s[3] - a[2][1] * s[1] + a[2][2] * s[0]) * inv,
+            ],
+        ]))
+    }
+
+    /// Computes the 2x2 minors that the cofactor expansion is built from
+    ///
+    /// The first array holds the minors of the first 2 axes and the second array
+    /// the minors of the last 2 axes, which are paired up during the expansion
+    fn minors(a: &[[f32; 4]; 4]) -> ([f32; 6], [f32; 6]) {
+        let s = [
+            a[0][0] * a[1][1] - a[1][0] * a[0][1],
+            a[0][0] * a[1][2] - a[1][0] * a[0][2],
+            a[0][0] * a[1][3] - a[1][0] * a[0][3],
+            a[0][1] * a[1][2] - a[1][1] * a[0][2],
+            a[0][1] * a[1][3] - a[1][1] * a[0][3],
+            a[0][2] * a[1][3] - a[1][2] * a[0][3],
+        ];
+        let c = [
+            a[2][0] * a[3][1] - a[3][0] * a[2][1],
+            a[2][0] * a[3][2] - a[3][0] * a[2][2],
+            a[2][0] * a[3][3] - a[3][0] * a[2][3],
+            a[2][1] * a[3][2] - a[3][1] * a[2][2],
+            a[2][1] * a[3][3] - a[3][1] * a[2][3],
+            a[2][2] * a[3][3] - a[3][2] * a[2][3],
+        ];
+        (s, c)
+    }
+
+    /// Expands the 2x2 minors computed by [`Mat4::minors()`] into the determinant
+    fn expand(s: &[f32; 6], c: &[f32; 6]) -> f32 {
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
     /// Creates a new view matrix
     ///
     /// A view matrix describes a camera in 3D space by the following 3 components:
@@ -143,12 +300,23 @@ impl Mat4 {
     /// - `target` -> the 3D position that specifies where the camera is looking at
     /// - `up` -> a unit vector specifying which axis is the direction `UP`, usually `(0.0, 1.0, 0.0)`
     pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Self {
-        // Forward vector
-        let f = (eye - target).normalize();
+        Self::look_to(eye, target - eye, up)
+    }
+
+    /// Creates a new view matrix from a viewing direction rather than a target,
+    /// which suits free-flying cameras
+    ///
+    /// This is equivalent to `Mat4::look_at(eye, eye + dir, up)`
+    /// - `eye` -> the 3D position of the camera
+    /// - `dir` -> the direction the camera is looking in, which doesn't have to be normalized
+    /// - `up` -> a unit vector specifying which axis is the direction `UP`, usually `(0.0, 1.0, 0.0)`
+    pub fn look_to(eye: Vec3, dir: Vec3, up: Vec3) -> Self {
+        // Forward vector, pointing backwards from the viewing direction
+        let f = (-dir).normalize();
         // Right vector
-        let r = f.cross(up).normalize();
+        let r = up.cross(f).normalize();
         // Up vector
-        let u = r.cross(f).normalize();
+        let u = f.cross(r).normalize();
         let view = Self {
             x_axis: Vec4::new(r.x, r.y, r.z, 0.0),
             y_axis: Vec4::new(u.x, u.y, u.z, 0.0),
@@ -198,6 +366,18 @@ impl Mat4 {
         }
     }
 
+    /// Creates a new orthographic projection matrix that maps pixel coordinates to normalized device coordinates
+    /// - `width` -> the width of the screen (in pixels)
+    /// - `height` -> the height of the screen (in pixels)
+    ///
+    /// `(0, 0)` is the top left pixel and `(width, height)` is the bottom right pixel, with Y pointing down
+    /// just like wgpu's framebuffer, which is what UI and 2D rendering usually expects
+    ///
+    /// Depth values from `0.0` to `1.0` are passed through as is
+    pub fn screen_ortho(width: f32, height: f32) -> Self {
+        Self::ortho(0.0, width, 0.0, height, 0.0, 1.0)
+    }
+
     /// Creates a new perspective projection matrix
     ///
     /// A perspective projection matrix describes the transformation from view to clip space
@@ -220,6 +400,121 @@ impl Mat4 {
         }
     }
 
+    /// Creates a new perspective projection matrix with the far plane pushed to infinity,
+    /// so distant geometry is never clipped
+    ///
+    /// This is the limit of [`Mat4::perspective()`] as `far` goes to infinity, so it targets the same
+    /// depth range, mapping the near plane to a depth of `-1.0` and infinitely far points towards `1.0`
+    /// - `fov` -> the field of view in radians, representing the vertical angle of sight
+    /// - `aspect_ratio` -> the aspect ratio of the window
+    /// - `near` -> the near plane (how close we are able to see before clipping)
+    pub fn perspective_infinite(fov: f32, aspect_ratio: f32, near: f32) -> Self {
+        let half_height = (fov / 2.0).tan();
+        Self {
+            x_axis: Vec4::new(1.0 / (aspect_ratio * half_height), 0.0, 0.0, 0.0),
+            y_axis: Vec4::new(0.0, 1.0 / half_height, 0.0, 0.0),
+            z_axis: Vec4::new(0.0, 0.0, -1.0, -1.0),
+            w_axis: Vec4::new(0.0, 0.0, -2.0 * near, 0.0),
+        }
+    }
+
+    /// Projects a world space point to pixel coordinates, treating this matrix as a view-projection matrix
+    /// - `self` -> the view-projection matrix
+    /// - `point` -> the world space point
+    /// - `viewport` -> the size of the viewport in pixels
+    ///
+    /// The returned coordinates start at the top left corner of the viewport, with Y pointing down
+    /// just like wgpu's framebuffer, which makes them suitable for placing 2D elements over 3D objects
+    ///
+    /// Returns `None` if the point is behind the camera
+    pub fn world_to_screen(&self, point: Vec3, viewport: Vec2) -> Option<Vec2> {
+        let clip = self.multiply_vec(Vec4::from(point));
+        if clip.w <= f32::EPSILON {
+            return None;
+        }
+
+        let ndc = clip.homogenize();
+        Some(Vec2::new(
+            (ndc.x + 1.0) * 0.5 * viewport.x,
+            (1.0 - ndc.y) * 0.5 * viewport.y,
+        ))
+    }
+
+    /// Unprojects a screen space point back into world space, the inverse of [`Mat4::world_to_screen()`],
+    /// which is useful for mouse picking
+    /// - `screen` -> the point in pixel coordinates (starting at the top left corner with Y pointing down),
+    ///   with its `z` being the normalized device depth, as produced by the projection matrix
+    ///   (e.g. `-1.0` at the near plane and `1.0` at the far plane for [`Mat4::perspective()`])
+    /// - `viewport` -> the viewport as `(x, y, width, height)` in pixels
+    /// - `inv_view_proj` -> the inverse of the view-projection matrix, see [`Mat4::inverse()`]
+    ///
+    /// Unprojecting the same pixel at the near and far depths gives the 2 ends of the picking ray
+    pub fn unproject(screen: Vec3, viewport: (f32, f32, f32, f32), inv_view_proj: Mat4) -> Vec3 {
+        let (x, y, width, height) = viewport;
+        let ndc = Vec3::new(
+            (screen.x - x) / width * 2.0 - 1.0,
+            1.0 - (screen.y - y) / height * 2.0,
+            screen.z,
+        );
+        inv_view_proj.transform_point(ndc)
+    }
+
+    /// Returns the translation of a transformation matrix (the `x`, `y` and `z` of the `w_axis`)
+    pub fn translation(&self) -> Vec3 {
+        Vec3::new(self.w_axis.x, self.w_axis.y, self.w_axis.z)
+    }
+
+    /// Returns the scale of a transformation matrix (the lengths of the 3 axis vectors)
+    ///
+    /// The scale factors are always positive, a negative scale (a mirrored axis) can't be told apart
+    /// from a rotation this way
+    pub fn scale_factors(&self) -> Vec3 {
+        let axis_length = |axis: Vec4| Vec3::new(axis.x, axis.y, axis.z).length();
+        Vec3::new(
+            axis_length(self.x_axis),
+            axis_length(self.y_axis),
+            axis_length(self.z_axis),
+        )
+    }
+
+    /// Decomposes a transformation matrix into its translation, rotation and scale,
+    /// the inverse of [`Mat4::from_trs()`]
+    ///
+    /// This assumes the matrix is an affine transform without shear (e.g. one built from a TRS),
+    /// other matrices don't have a meaningful decomposition and produce an arbitrary rotation
+    ///
+    /// A mirrored matrix (with a negative determinant) is decomposed with a negative `x` scale,
+    /// since a mirroring can't be represented by a rotation
+    ///
+    /// # Panics:
+    /// - If one of the scale factors is zero, since the rotation can't be recovered from a flattened axis
+    pub fn decompose(self) -> (Vec3, Quat, Vec3) {
+        let mut scale = self.scale_factors();
+        if self.determinant() < 0.0 {
+            scale.x = -scale.x;
+        }
+
+        let axis = |axis: Vec4, scale: f32| Vec3::new(axis.x, axis.y, axis.z) / scale;
+        let rotation = Quat::from_mat3(Mat3 {
+            x_axis: axis(self.x_axis, scale.x),
+            y_axis: axis(self.y_axis, scale.y),
+            w_axis: axis(self.z_axis, scale.z),
+        });
+        (self.translation(), rotation, scale)
+    }
+
+    /// Returns the matrix that transforms normals for this transformation matrix,
+    /// the inverse transpose of its upper-left 3x3
+    ///
+    /// Transforming normals by the matrix itself only works for rotations and uniform scales,
+    /// a non-uniform scale would tilt them so they're no longer perpendicular to the surface
+    ///
+    /// Returns the identity if the upper-left 3x3 is singular (e.g. a scale of zero)
+    pub fn normal_matrix(self) -> Mat3 {
+        let upper = Mat3::from_mat4(self);
+        upper.inverse().map(Mat3::transpose).unwrap_or_default()
+    }
+
     /// Returns the matrix data as an array
     pub fn raw(&self) -> [[f32; 4]; 4] {
         [
@@ -271,6 +566,14 @@ impl Default for Mat4 {
     }
 }
 
+/// Displays the matrix as a grid of rows, where each column is one of the matrix axes
+impl fmt::Display for Mat4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows = self.transpose().raw();
+        math::fmt_rows(f, rows)
+    }
+}
+
 impl Mul for Mat4 {
     type Output = Self;
 
@@ -279,6 +582,40 @@ impl Mul for Mat4 {
     }
 }
 
+impl Mul<f32> for Mat4 {
+    type Output = Self;
+
+    fn mul(self, scalar: f32) -> Self::Output {
+        self.scale_scalar(scalar)
+    }
+}
+
+impl Add for Mat4 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            x_axis: self.x_axis + other.x_axis,
+            y_axis: self.y_axis + other.y_axis,
+            z_axis: self.z_axis + other.z_axis,
+            w_axis: self.w_axis + other.w_axis,
+        }
+    }
+}
+
+impl Sub for Mat4 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            x_axis: self.x_axis - other.x_axis,
+            y_axis: self.y_axis - other.y_axis,
+            z_axis: self.z_axis - other.z_axis,
+            w_axis: self.w_axis - other.w_axis,
+        }
+    }
+}
+
 impl Mul<Vec4> for Mat4 {
     type Output = Vec4;
 
@@ -290,7 +627,7 @@ impl Mul<Vec4> for Mat4 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::math::EPSILON;
+    use crate::math::{EPSILON, cmp_f32};
     use std::f32::consts::PI;
 
     #[test]
@@ -310,6 +647,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn identity() {
+        {
+            assert_eq!(Mat4::IDENTITY, Mat4::new());
+            assert_eq!(Mat4::IDENTITY * Mat4::IDENTITY, Mat4::IDENTITY);
+            assert_ne!(Mat4::IDENTITY, Mat4::translate(Vec3::new(0.0, 1.0, 0.0)));
+        }
+
+        {
+            assert_eq!(Mat3::IDENTITY, Mat3::new());
+            assert_eq!(Quat::IDENTITY, Quat::new());
+        }
+    }
+
     #[test]
     fn scale() {
         {
@@ -375,6 +726,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn from_trs() {
+        let translation = Vec3::new(4.0, -2.0, 7.5);
+        let rotation = Quat::from_axis(Vec3::new(1.0, 2.0, -0.5).normalize(), PI / 5.0);
+        let scale = Vec3::new(2.0, 0.5, -3.0);
+
+        let expected = Mat4::translate(translation) * Mat4::rotate(rotation) * Mat4::scale(scale);
+        let m = Mat4::from_trs(translation, rotation, scale);
+        assert!(m.cmp(&expected, EPSILON));
+    }
+
+    #[test]
+    fn decompose() {
+        // A quaternion and its negation describe the same rotation
+        let same_rotation = |a: Quat, b: Quat| cmp_f32(a.dot(&b).abs(), 1.0, EPSILON);
+
+        let rotations = [
+            Quat::new(),
+            Quat::from_axis(Vec3::new(1.0, 2.0, -0.5).normalize(), PI / 5.0),
+            Quat::from_axis(Vec3::new(0.0, 1.0, 0.0), PI),
+            Quat::from_axis(Vec3::new(1.0, 0.0, 0.0), PI * 0.9),
+            Quat::from_axis(Vec3::new(0.0, 0.0, 1.0), -PI * 0.9),
+        ];
+        let translation = Vec3::new(4.0, -2.0, 7.5);
+        let scale = Vec3::new(2.0, 0.5, 3.0);
+        for rotation in rotations {
+            let (t, r, s) = Mat4::from_trs(translation, rotation, scale).decompose();
+            assert!(t.cmp(translation, EPSILON));
+            assert!(same_rotation(r, rotation));
+            assert!(s.cmp(scale, EPSILON));
+        }
+
+        {
+            // Mirrored matrices are decomposed with a negative x scale
+            let rotation = rotations[1];
+            let m = Mat4::from_trs(translation, rotation, Vec3::new(-2.0, 0.5, 3.0));
+            let (t, r, s) = m.decompose();
+            assert!(Mat4::from_trs(t, r, s).cmp(&m, EPSILON));
+            assert!(s.cmp(Vec3::new(-2.0, 0.5, 3.0), EPSILON));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by near-zero value")]
+    fn decompose_flattened() {
+        Mat4::scale(Vec3::new(1.0, 0.0, 1.0)).decompose();
+    }
+
+    #[test]
+    fn normal_matrix() {
+        let m = Mat4::from_trs(
+            Vec3::new(5.0, 1.0, -2.0),
+            Quat::from_axis(Vec3::new(0.0, 0.0, 1.0), PI / 6.0),
+            Vec3::new(4.0, 1.0, 0.5),
+        );
+        let tangent = Vec3::new(1.0, 1.0, 0.0);
+        let normal = Vec3::new(1.0, -1.0, 0.0);
+
+        {
+            let transformed_tangent =
+                (m * Vec4::new(tangent.x, tangent.y, tangent.z, 0.0)).truncate();
+            let transformed_normal = m.normal_matrix() * normal;
+            assert!(cmp_f32(
+                transformed_tangent.dot(transformed_normal),
+                0.0,
+                EPSILON
+            ));
+
+            // Transforming the normal by the matrix itself tilts it
+            let tilted_normal = (m * Vec4::new(normal.x, normal.y, normal.z, 0.0)).truncate();
+            assert!(!cmp_f32(
+                transformed_tangent.dot(tilted_normal),
+                0.0,
+                EPSILON
+            ));
+        }
+
+        {
+            let singular = Mat4::scale(Vec3::new(1.0, 0.0, 1.0));
+            assert!(singular.normal_matrix().cmp(&Mat3::new(), EPSILON));
+        }
+    }
+
     #[test]
     fn multiply_mat() {
         {
@@ -424,6 +858,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scalar_ops() {
+        {
+            let m = Mat4::new() * 3.0;
+            let raw = m.raw();
+            for (i, axis) in raw.iter().enumerate() {
+                for (j, value) in axis.iter().enumerate() {
+                    let expected = if i == j { 3.0 } else { 0.0 };
+                    assert!(cmp_f32(*value, expected, EPSILON));
+                }
+            }
+            assert!(m.cmp(&Mat4::new().scale_scalar(3.0), EPSILON));
+        }
+
+        {
+            // Weighted averaging of 2 translations
+            let a = Mat4::translate(Vec3::new(2.0, 0.0, -4.0));
+            let b = Mat4::translate(Vec3::new(0.0, 6.0, 4.0));
+            let average = a * 0.5 + b * 0.5;
+            assert!(average.cmp(&Mat4::translate(Vec3::new(1.0, 3.0, 0.0)), EPSILON));
+            assert!((average - a * 0.5).cmp(&(b * 0.5), EPSILON));
+        }
+    }
+
     #[rustfmt::skip]
     #[test]
     fn multiply_vec() {
@@ -452,6 +910,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn transform_point_and_vector() {
+        {
+            let m = Mat4::translate(Vec3::new(1.0, 2.0, 3.0));
+            let v = Vec3::new(4.0, -1.0, 0.5);
+            assert!(m.transform_point(v).cmp(Vec3::new(5.0, 1.0, 3.5), EPSILON));
+            assert!(m.transform_vector(v).cmp(v, EPSILON));
+        }
+
+        {
+            let m = Mat4::from_trs(
+                Vec3::new(1.0, 2.0, 3.0),
+                Quat::new(),
+                Vec3::new(2.0, 2.0, 2.0),
+            );
+            let v = Vec3::new(1.0, 0.0, 0.0);
+            assert!(m.transform_point(v).cmp(Vec3::new(3.0, 2.0, 3.0), EPSILON));
+            assert!(m.transform_vector(v).cmp(Vec3::new(2.0, 0.0, 0.0), EPSILON));
+        }
+
+        {
+            // Points are divided by w after a projection
+            let m = Mat4::perspective(PI / 2.0, 1.0, 0.1, 100.0);
+            let p = m.transform_point(Vec3::new(2.0, 1.0, -2.0));
+            let depth = (100.1 / 99.9 * 2.0 - 20.0 / 99.9) / 2.0;
+            assert!(p.cmp(Vec3::new(1.0, 0.5, depth), EPSILON));
+        }
+    }
+
     #[test]
     fn transform() {
         {
@@ -472,4 +959,282 @@ mod tests {
             assert!((translation * rotation * scale * vertex).cmp(expected, EPSILON));
         }
     }
+
+    #[test]
+    fn look_at() {
+        {
+            let m = Mat4::look_at(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::UP);
+            let v = Vec4::new(1.0, 2.0, 0.0, 1.0);
+            let expected = Vec4::new(1.0, 2.0, -5.0, 1.0);
+            assert!((m * v).cmp(expected, EPSILON));
+        }
+
+        {
+            let m = Mat4::look_at(Vec3::new(5.0, 0.0, 0.0), Vec3::ZERO, Vec3::UP);
+            let v = Vec4::new(0.0, 1.0, -1.0, 1.0);
+            let expected = Vec4::new(1.0, 1.0, -5.0, 1.0);
+            assert!((m * v).cmp(expected, EPSILON));
+        }
+    }
+
+    #[test]
+    fn look_to() {
+        let eye = Vec3::new(3.0, 2.0, -4.0);
+        let dir = Vec3::new(-1.0, -0.5, 2.0);
+        let expected = Mat4::look_at(eye, eye + dir, Vec3::UP);
+        assert!(Mat4::look_to(eye, dir, Vec3::UP).cmp(&expected, EPSILON));
+        assert!(Mat4::look_to(eye, dir * 10.0, Vec3::UP).cmp(&expected, EPSILON));
+    }
+
+    #[test]
+    fn perspective_infinite() {
+        let m = Mat4::perspective_infinite(PI / 2.0, 1.5, 0.1);
+        let depth = |z: f32| {
+            let clip = m * Vec4::new(0.0, 0.0, z, 1.0);
+            clip.z / clip.w
+        };
+
+        {
+            assert!(cmp_f32(depth(-0.1), -1.0, EPSILON));
+            assert!(depth(-1e6) < 1.0);
+            assert!(cmp_f32(depth(-1e6), 1.0, EPSILON));
+        }
+
+        {
+            // Matches a regular perspective with a very distant far plane
+            let finite = Mat4::perspective(PI / 2.0, 1.5, 0.1, 1e7);
+            assert!(m.cmp(&finite, EPSILON));
+        }
+    }
+
+    #[test]
+    fn world_to_screen() {
+        let view = Mat4::look_at(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::UP);
+        let projection = Mat4::perspective(PI / 2.0, 2.0, 0.1, 100.0);
+        let view_projection = projection * view;
+        let viewport = Vec2::new(800.0, 400.0);
+
+        {
+            let screen = view_projection.world_to_screen(Vec3::ZERO, viewport);
+            assert!(screen.unwrap().cmp(Vec2::new(400.0, 200.0), EPSILON));
+        }
+
+        {
+            // The top of the frustum at this distance is at y == 5, landing on the top edge
+            let screen = view_projection.world_to_screen(Vec3::new(0.0, 5.0, 0.0), viewport);
+            assert!(screen.unwrap().cmp(Vec2::new(400.0, 0.0), EPSILON));
+        }
+
+        {
+            let screen = view_projection.world_to_screen(Vec3::new(5.0, -2.5, 0.0), viewport);
+            assert!(screen.unwrap().cmp(Vec2::new(600.0, 300.0), EPSILON));
+        }
+
+        {
+            let screen = view_projection.world_to_screen(Vec3::new(0.0, 0.0, 10.0), viewport);
+            assert!(screen.is_none());
+        }
+    }
+
+    #[test]
+    fn unproject() {
+        let view = Mat4::look_at(Vec3::new(1.0, 3.0, 5.0), Vec3::ZERO, Vec3::UP);
+        let projection = Mat4::perspective(PI / 2.0, 2.0, 0.1, 100.0);
+        let view_projection = projection * view;
+        let inverse = view_projection.inverse().unwrap();
+
+        {
+            let point = Vec3::new(1.5, -0.5, 2.0);
+            let screen = view_projection
+                .world_to_screen(point, Vec2::new(800.0, 400.0))
+                .unwrap();
+            let depth = view_projection.transform_point(point).z;
+            let unprojected = Mat4::unproject(
+                Vec3::new(screen.x, screen.y, depth),
+                (0.0, 0.0, 800.0, 400.0),
+                inverse,
+            );
+            assert!(unprojected.cmp(point, EPSILON));
+        }
+
+        {
+            // The viewport can be offset within the window
+            let point = Vec3::new(-2.0, 1.0, 0.0);
+            let screen = view_projection
+                .world_to_screen(point, Vec2::new(800.0, 400.0))
+                .unwrap();
+            let depth = view_projection.transform_point(point).z;
+            let unprojected = Mat4::unproject(
+                Vec3::new(screen.x + 100.0, screen.y + 50.0, depth),
+                (100.0, 50.0, 800.0, 400.0),
+                inverse,
+            );
+            assert!(unprojected.cmp(point, EPSILON));
+        }
+    }
+
+    #[test]
+    fn screen_ortho() {
+        let m = Mat4::screen_ortho(800.0, 600.0);
+
+        {
+            let v = Vec4::new(0.0, 0.0, 0.0, 1.0);
+            let expected = Vec4::new(-1.0, 1.0, 0.0, 1.0);
+            assert!((m * v).cmp(expected, EPSILON));
+        }
+
+        {
+            let v = Vec4::new(800.0, 0.0, 0.0, 1.0);
+            let expected = Vec4::new(1.0, 1.0, 0.0, 1.0);
+            assert!((m * v).cmp(expected, EPSILON));
+        }
+
+        {
+            let v = Vec4::new(0.0, 600.0, 0.0, 1.0);
+            let expected = Vec4::new(-1.0, -1.0, 0.0, 1.0);
+            assert!((m * v).cmp(expected, EPSILON));
+        }
+
+        {
+            let v = Vec4::new(800.0, 600.0, 1.0, 1.0);
+            let expected = Vec4::new(1.0, -1.0, 1.0, 1.0);
+            assert!((m * v).cmp(expected, EPSILON));
+        }
+    }
+
+    #[test]
+    fn translation_and_scale_factors() {
+        let translation = Vec3::new(1.0, -2.0, 3.0);
+        let rotation = Quat::from_axis(Vec3::new(1.0, 1.0, 0.0), PI / 3.0);
+        let scale = Vec3::new(2.0, 0.5, 4.0);
+        let m = Mat4::translate(translation) * Mat4::rotate(rotation) * Mat4::scale(scale);
+
+        {
+            assert!(m.translation().cmp(translation, EPSILON));
+        }
+
+        {
+            assert!(m.scale_factors().cmp(scale, EPSILON));
+        }
+    }
+
+    #[test]
+    fn determinant() {
+        {
+            assert!(cmp_f32(Mat4::new().determinant(), 1.0, EPSILON));
+            assert!(cmp_f32(
+                Mat4::scale(Vec3::new(2.0, 2.0, 2.0)).determinant(),
+                8.0,
+                EPSILON
+            ));
+        }
+
+        {
+            let m = Mat4::of([
+                [2.0, 0.0, 0.0, 0.0],
+                [0.0, 2.0, 0.0, 0.0],
+                [0.0, 0.0, 2.0, 0.0],
+                [0.0, 0.0, 0.0, 2.0],
+            ]);
+            assert!(cmp_f32(m.determinant(), 16.0, EPSILON));
+        }
+
+        {
+            // Translations and rotations preserve volumes, mirroring flips the sign
+            let m = Mat4::translate(Vec3::new(4.0, -1.0, 2.0))
+                * Mat4::rotate(Quat::from_axis(Vec3::new(0.0, 1.0, 0.0), PI / 3.0));
+            assert!(cmp_f32(m.determinant(), 1.0, EPSILON));
+            assert!(cmp_f32(
+                Mat4::scale(Vec3::new(-1.0, 1.0, 1.0)).determinant(),
+                -1.0,
+                EPSILON
+            ));
+        }
+
+        {
+            let mut data = Mat4::perspective(PI / 2.0, 1.5, 0.1, 100.0).raw();
+            data[0] = [0.0; 4];
+            assert!(cmp_f32(Mat4::of(data).determinant(), 0.0, EPSILON));
+            // The transpose has the zero as a row
+            assert!(cmp_f32(
+                Mat4::of(data).transpose().determinant(),
+                0.0,
+                EPSILON
+            ));
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let identity = Mat4::new();
+        {
+            let m =
+                Mat4::translate(Vec3::new(3.0, -2.0, 5.0)) * Mat4::scale(Vec3::new(2.0, 4.0, 0.5));
+            let inverse = m.inverse().unwrap();
+            assert!((m * inverse).cmp(&identity, EPSILON));
+            assert!((inverse * m).cmp(&identity, EPSILON));
+
+            let expected = Mat4::scale(Vec3::new(0.5, 0.25, 2.0))
+                * Mat4::translate(Vec3::new(-3.0, 2.0, -5.0));
+            assert!(inverse.cmp(&expected, EPSILON));
+        }
+
+        {
+            let m = Mat4::perspective(PI / 2.0, 1.5, 0.1, 100.0)
+                * Mat4::look_at(
+                    Vec3::new(1.0, 2.0, 3.0),
+                    Vec3::new(0.0, 0.0, 0.0),
+                    Vec3::new(0.0, 1.0, 0.0),
+                );
+            assert!((m * m.inverse().unwrap()).cmp(&identity, EPSILON));
+        }
+
+        {
+            // A flattening scale can't be undone
+            assert!(Mat4::scale(Vec3::new(1.0, 0.0, 1.0)).inverse().is_none());
+        }
+    }
+
+    #[test]
+    fn display() {
+        let m = Mat4::translate(Vec3::new(1.0, -2.0, 10.5));
+        assert_eq!(
+            m.to_string(),
+            "[1.00, 0.00, 0.00,  1.00]\n\
+             [0.00, 1.00, 0.00, -2.00]\n\
+             [0.00, 0.00, 1.00, 10.50]\n\
+             [0.00, 0.00, 0.00,  1.00]"
+        );
+        assert_eq!(
+            format!("{:.0}", m),
+            "[1, 0, 0,  1]\n[0, 1, 0, -2]\n[0, 0, 1, 10]\n[0, 0, 0,  1]"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        {
+            // The columns keep their readable field layout
+            let m = Mat4::translate(Vec3::new(1.0, -2.0, 3.0));
+            let json = serde_json::to_value(m).unwrap();
+            assert_eq!(
+                json,
+                serde_json::json!({
+                    "x_axis": { "x": 1.0, "y": 0.0, "z": 0.0, "w": 0.0 },
+                    "y_axis": { "x": 0.0, "y": 1.0, "z": 0.0, "w": 0.0 },
+                    "z_axis": { "x": 0.0, "y": 0.0, "z": 1.0, "w": 0.0 },
+                    "w_axis": { "x": 1.0, "y": -2.0, "z": 3.0, "w": 1.0 },
+                })
+            );
+        }
+
+        {
+            let m = Mat4::perspective(PI / 3.0, 16.0 / 9.0, 0.1, 100.0)
+                * Mat4::translate(Vec3::new(1.0, -2.0, 3.0));
+            let json = serde_json::to_string(&m).unwrap();
+            let deserialized: Mat4 = serde_json::from_str(&json).unwrap();
+            assert!(deserialized.cmp(&m, EPSILON));
+        }
+    }
 }
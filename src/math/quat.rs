@@ -1,10 +1,11 @@
 use std::ops::Mul;
 
-use crate::math::vec3::Vec3;
+use crate::math::{mat3::Mat3, mat4::Mat4, vec3::Vec3};
 
 /// A quaternion describes rotation in 3D with an axis and an angle
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Quat {
     /// The X component of the rotation axis
     pub x: f32,
@@ -17,14 +18,17 @@ pub struct Quat {
 }
 
 impl Quat {
+    /// The identity quaternion, which doesn't rotate at all
+    pub const IDENTITY: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 1.0,
+    };
+
     /// Creates a new identity quaternion
     pub fn new() -> Self {
-        Self {
-            x: 0.0,
-            y: 0.0,
-            z: 0.0,
-            w: 1.0,
-        }
+        Self::IDENTITY
     }
 
     /// Creates a new quaternion from the values `(x, y, z, w)`
@@ -35,6 +39,8 @@ impl Quat {
     /// Creates a new quaternion from a rotation axis and an angle
     /// - `axis` -> is usually a basis vector in a specified direction (right, forward, up, ...)
     /// - `angle` -> the amount of rotation in radians
+    ///
+    /// A zero length `axis` results in the identity quaternion
     pub fn from_axis(axis: Vec3, angle: f32) -> Self {
         let half_angle = angle / 2.0;
         let sin_half = half_angle.sin();
@@ -47,7 +53,8 @@ impl Quat {
             z: axis.z * sin_half,
             w: cos_half,
         }
-        .normalize()
+        .try_normalize()
+        .unwrap_or_default()
     }
 
     /// Creates a new quaternion from the euler angles `(pitch, yaw, roll)`
@@ -88,6 +95,79 @@ impl Quat {
         q_yaw * q_pitch * q_roll
     }
 
+    /// Creates a new quaternion from a rotation matrix
+    /// - `mat` -> the [`Mat3`] whose axes are the rotated basis vectors, which should be orthonormal
+    ///
+    /// The quaternion component with the largest magnitude is solved for first,
+    /// which avoids dividing by a value close to zero
+    pub fn from_mat3(mat: Mat3) -> Self {
+        let (x_axis, y_axis, z_axis) = (mat.x_axis, mat.y_axis, mat.w_axis);
+        let trace = x_axis.x + y_axis.y + z_axis.z;
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::of(
+                (y_axis.z - z_axis.y) / s,
+                (z_axis.x - x_axis.z) / s,
+                (x_axis.y - y_axis.x) / s,
+                s / 4.0,
+            )
+        } else if x_axis.x > y_axis.y && x_axis.x > z_axis.z {
+            let s = (1.0 + x_axis.x - y_axis.y - z_axis.z).sqrt() * 2.0;
+            Self::of(
+                s / 4.0,
+                (y_axis.x + x_axis.y) / s,
+                (z_axis.x + x_axis.z) / s,
+                (y_axis.z - z_axis.y) / s,
+            )
+        } else if y_axis.y > z_axis.z {
+            let s = (1.0 + y_axis.y - x_axis.x - z_axis.z).sqrt() * 2.0;
+            Self::of(
+                (y_axis.x + x_axis.y) / s,
+                s / 4.0,
+                (z_axis.y + y_axis.z) / s,
+                (z_axis.x - x_axis.z) / s,
+            )
+        } else {
+            let s = (1.0 + z_axis.z - x_axis.x - y_axis.y).sqrt() * 2.0;
+            Self::of(
+                (z_axis.x + x_axis.z) / s,
+                (z_axis.y + y_axis.z) / s,
+                s / 4.0,
+                (x_axis.y - y_axis.x) / s,
+            )
+        };
+        q.normalize()
+    }
+
+    /// Creates a new quaternion from the rotation of a [`Mat4`], the inverse of [`Mat4::rotate()`]
+    /// - `mat` -> the [`Mat4`] whose upper-left 3x3 is a rotation matrix without any scale
+    pub fn from_mat4(mat: Mat4) -> Self {
+        Self::from_mat3(Mat3::from_mat4(mat))
+    }
+
+    /// Converts the quaternion back into the euler angles `(pitch, yaw, roll)`,
+    /// the inverse of [`Quat::from_euler()`]
+    ///
+    /// The pitch is in the range of `-π/2 - π/2`, while the yaw and roll are in the range of `-π - π`
+    ///
+    /// Near a pitch of `±π/2` (gimbal lock) the yaw and roll rotate about the same axis,
+    /// in which case the roll is `0.0` and the yaw carries the whole rotation
+    pub fn to_euler(self) -> (f32, f32, f32) {
+        let Self { x, y, z, w } = self.normalize();
+        let sin_pitch = (-2.0 * (y * z - x * w)).clamp(-1.0, 1.0);
+        let pitch = sin_pitch.asin();
+
+        // The cosine of the pitch vanishes, which leaves the yaw and roll terms undefined
+        if sin_pitch.abs() > 1.0 - 1e-6 {
+            let yaw = (-2.0 * (x * z - y * w)).atan2(1.0 - 2.0 * (y * y + z * z));
+            return (pitch, yaw, 0.0);
+        }
+
+        let yaw = (2.0 * (x * z + y * w)).atan2(1.0 - 2.0 * (x * x + y * y));
+        let roll = (2.0 * (x * y + z * w)).atan2(1.0 - 2.0 * (x * x + z * z));
+        (pitch, yaw, roll)
+    }
+
     /// Multiplies 2 quaternions together, resulting in a combined rotation
     /// - `self` -> the first quaternion
     /// - `other` -> the second quaternion
@@ -98,7 +178,8 @@ impl Quat {
             z: (self.w * other.z) + (self.x * other.y) - (self.y * other.x) + (self.z * other.w),
             w: (self.w * other.w) - (self.x * other.x) - (self.y * other.y) - (self.z * other.z),
         }
-        .normalize()
+        .try_normalize()
+        .unwrap_or_default()
     }
 
     /// Inverses the quaternion, which results in an inversed rotation effect
@@ -119,18 +200,33 @@ impl Quat {
     }
 
     /// Normalizes the quaternion
+    ///
+    /// # Panics:
+    /// - If the quaternion is of near-zero length, use [`Quat::try_normalize()`] to handle that case instead
     pub fn normalize(&self) -> Self {
+        match self.try_normalize() {
+            Some(q) => q,
+            None => panic!(
+                "Division by near-zero ({}) length in quaternion!",
+                self.length()
+            ),
+        }
+    }
+
+    /// Normalizes the quaternion, returning [`None`] if it's of near-zero length
+    /// (e.g. after accumulating floating point errors), since it has no direction to preserve
+    pub fn try_normalize(&self) -> Option<Self> {
         let len = self.length();
         if len < f32::EPSILON {
-            panic!("Division by near-zero ({}) length in quaternion!", len);
+            return None;
         }
 
-        Self {
+        Some(Self {
             x: self.x / len,
             y: self.y / len,
             z: self.z / len,
             w: self.w / len,
-        }
+        })
     }
 
     /// Computes the length of the quaternion
@@ -138,6 +234,168 @@ impl Quat {
         ((self.x * self.x) + (self.y * self.y) + (self.z * self.z) + (self.w * self.w)).sqrt()
     }
 
+    /// Rotates a vector by the rotation this quaternion describes, which is cheaper
+    /// than building a [`crate::math::mat4::Mat4`] just to rotate a single vector
+    /// - `self` -> the quaternion, which is normalized before rotating
+    /// - `vec` -> the vector to rotate
+    pub fn rotate_vec(&self, vec: Vec3) -> Vec3 {
+        let q = self.normalize();
+        let axis = Vec3::new(q.x, q.y, q.z);
+        // v + 2 * (q.xyz x (q.xyz x v + q.w * v))
+        vec + axis.cross(axis.cross(vec) + vec * q.w) * 2.0
+    }
+
+    /// Computes the exponential of the quaternion
+    ///
+    /// For a pure quaternion `(axis * angle, 0)` (as returned by [`Quat::ln()`] of a unit quaternion)
+    /// this is the unit quaternion rotating by `2 * angle` about `axis`
+    pub fn exp(&self) -> Self {
+        let scale = self.w.exp();
+        let angle = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+        // The axis is undefined for a zero vector part, but sin(angle) / angle tends to 1
+        let sin_over_angle = if angle < f32::EPSILON {
+            1.0
+        } else {
+            angle.sin() / angle
+        };
+
+        Self {
+            x: self.x * sin_over_angle * scale,
+            y: self.y * sin_over_angle * scale,
+            z: self.z * sin_over_angle * scale,
+            w: angle.cos() * scale,
+        }
+    }
+
+    /// Computes the natural logarithm of the quaternion, the inverse of [`Quat::exp()`]
+    ///
+    /// For a unit quaternion this is the pure quaternion `(axis * angle / 2, 0)`
+    ///
+    /// # Panics:
+    /// - if the quaternion is of zero length
+    pub fn ln(&self) -> Self {
+        let len = self.length();
+        if len < f32::EPSILON {
+            panic!("Logarithm of a near-zero ({}) length quaternion!", len);
+        }
+
+        let vec_len = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+        let angle = (self.w / len).clamp(-1.0, 1.0).acos();
+        // The axis is undefined for a zero vector part, but angle / sin(angle) tends to 1
+        let angle_over_vec_len = if vec_len < f32::EPSILON {
+            1.0 / len
+        } else {
+            angle / vec_len
+        };
+
+        Self {
+            x: self.x * angle_over_vec_len,
+            y: self.y * angle_over_vec_len,
+            z: self.z * angle_over_vec_len,
+            w: len.ln(),
+        }
+    }
+
+    /// Raises the quaternion to a power, which scales the angle of its rotation
+    /// - `self` -> the quaternion, which should be normalized
+    /// - `t` -> the power, `0.0` results in the identity, `1.0` in the quaternion itself
+    pub fn pow(&self, t: f32) -> Self {
+        let ln = self.ln();
+        Self {
+            x: ln.x * t,
+            y: ln.y * t,
+            z: ln.z * t,
+            w: ln.w * t,
+        }
+        .exp()
+    }
+
+    /// Performs spherical linear interpolation (slerp) between 2 quaternions, rotating
+    /// at a constant angular velocity along the shorter arc between them
+    /// - `self` -> the rotation at `t = 0.0`
+    /// - `other` -> the rotation at `t = 1.0`
+    /// - `t` -> the interpolation factor, in the range of `0.0 - 1.0`
+    ///
+    /// Both quaternions should be normalized, the result is normalized as well
+    pub fn slerp(self, other: Self, t: f32) -> Self {
+        let (other, dot) = self.shortest_arc(other);
+
+        // For nearly identical rotations sin(angle) approaches zero, so lerp instead
+        if dot > 1.0 - f32::EPSILON * 16.0 {
+            return self.blend(other, 1.0 - t, t);
+        }
+
+        let angle = dot.acos();
+        let sin = angle.sin();
+        self.blend(
+            other,
+            ((1.0 - t) * angle).sin() / sin,
+            (t * angle).sin() / sin,
+        )
+    }
+
+    /// Performs normalized linear interpolation (nlerp) between 2 quaternions along the shorter arc
+    /// - `self` -> the rotation at `t = 0.0`
+    /// - `other` -> the rotation at `t = 1.0`
+    /// - `t` -> the interpolation factor, in the range of `0.0 - 1.0`
+    ///
+    /// This is cheaper than [`Quat::slerp()`] and follows the same path, but it doesn't rotate
+    /// at a constant angular velocity, it speeds up towards the middle which becomes noticeable
+    /// for large angles between the quaternions
+    ///
+    /// Both quaternions should be normalized, the result is normalized as well
+    pub fn nlerp(self, other: Self, t: f32) -> Self {
+        let (other, _) = self.shortest_arc(other);
+        self.blend(other, 1.0 - t, t)
+    }
+
+    /// Returns `other` (or its negation, which describes the same rotation) so that it's on the shorter arc
+    /// from this quaternion, along with the (non-negative) dot product between them
+    fn shortest_arc(self, other: Self) -> (Self, f32) {
+        let dot = self.dot(&other);
+        if dot < 0.0 {
+            (Self::of(-other.x, -other.y, -other.z, -other.w), -dot)
+        } else {
+            (other, dot)
+        }
+    }
+
+    /// Computes the normalized weighted sum of 2 quaternions
+    fn blend(self, other: Self, self_weight: f32, other_weight: f32) -> Self {
+        Self {
+            x: self.x * self_weight + other.x * other_weight,
+            y: self.y * self_weight + other.y * other_weight,
+            z: self.z * self_weight + other.z * other_weight,
+            w: self.w * self_weight + other.w * other_weight,
+        }
+        .normalize()
+    }
+
+    /// Performs spherical quadrangle interpolation (squad) between 2 quaternions,
+    /// which is C1-continuous across consecutive segments unlike chaining spherical linear interpolations
+    /// - `q0` -> the rotation at the start of the segment (`t = 0.0`)
+    /// - `q1` -> the rotation at the end of the segment (`t = 1.0`)
+    /// - `a` -> the control point after `q0`
+    /// - `b` -> the control point before `q1`
+    /// - `t` -> the interpolation factor, in the range of `0.0 - 1.0`
+    ///
+    /// For a path through `q[0], q[1], ..., q[n]` the control point of `q[i]` is
+    /// `q[i] * exp(-(ln(q[i]⁻¹ * q[i - 1]) + ln(q[i]⁻¹ * q[i + 1])) / 4)`,
+    /// `a` is the control point of `q0` and `b` is the control point of `q1`
+    ///
+    /// All of the quaternions should be normalized
+    pub fn squad(q0: Self, q1: Self, a: Self, b: Self, t: f32) -> Self {
+        let path = Self::interpolate(q0, q1, t);
+        let control = Self::interpolate(a, b, t);
+        Self::interpolate(path, control, 2.0 * t * (1.0 - t))
+    }
+
+    /// Interpolates along the arc between 2 unit quaternions without taking the shortest path,
+    /// which squad relies on
+    fn interpolate(from: Self, to: Self, t: f32) -> Self {
+        from * (from.inverse() * to).pow(t)
+    }
+
     /// Compares 2 quaternions and returns if they're equal or not
     /// - `self` -> the first quaternion
     /// - `other` -> the second quaternion
@@ -151,6 +409,12 @@ impl Quat {
     }
 }
 
+impl Default for Quat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Mul for Quat {
     type Output = Self;
     fn mul(self, other: Self) -> Self {
@@ -158,7 +422,236 @@ impl Mul for Quat {
     }
 }
 
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+    fn mul(self, vec: Vec3) -> Vec3 {
+        self.rotate_vec(vec)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    // Implement unit tests
+    use super::*;
+    use crate::math::{EPSILON, cmp_f32, vec4::Vec4};
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn try_normalize() {
+        {
+            assert!(Quat::of(0.0, 0.0, 0.0, 0.0).try_normalize().is_none());
+            let q = Quat::of(0.0, 3.0, 0.0, 4.0).try_normalize().unwrap();
+            assert!(q.cmp(&Quat::of(0.0, 0.6, 0.0, 0.8), EPSILON));
+        }
+
+        {
+            // Degenerate inputs fall back to the identity
+            let zero = Quat::of(0.0, 0.0, 0.0, 0.0);
+            assert!((zero * zero).cmp(&Quat::new(), EPSILON));
+            let axis = Quat::from_axis(Vec3::new(0.0, 0.0, 0.0), PI);
+            assert!(axis.cmp(&Quat::new(), EPSILON));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by near-zero")]
+    fn normalize_zero() {
+        Quat::of(0.0, 0.0, 0.0, 0.0).normalize();
+    }
+
+    #[test]
+    fn rotate_vec() {
+        {
+            let q = Quat::new();
+            let v = Vec3::new(1.0, 2.0, 3.0);
+            assert!(q.rotate_vec(v).cmp(v, EPSILON));
+        }
+
+        {
+            let q = Quat::from_axis(Vec3::new(0.0, 0.0, 1.0), FRAC_PI_2);
+            let v = Vec3::new(1.0, 0.0, 0.0);
+            let expected = Vec3::new(0.0, 1.0, 0.0);
+            assert!(q.rotate_vec(v).cmp(expected, EPSILON));
+        }
+
+        {
+            let q = Quat::from_axis(Vec3::new(0.0, 1.0, 0.0), FRAC_PI_2);
+            let v = Vec3::new(1.0, 0.0, 0.0);
+            let expected = Vec3::new(0.0, 0.0, -1.0);
+            assert!(q.rotate_vec(v).cmp(expected, EPSILON));
+        }
+
+        {
+            let q = Quat::from_axis(Vec3::new(1.0, 0.0, 0.0), FRAC_PI_2);
+            let v = Vec3::new(0.0, 1.0, 0.0);
+            let expected = Vec3::new(0.0, 0.0, 1.0);
+            assert!((q * v).cmp(expected, EPSILON));
+
+            let rotated = Mat4::rotate(q) * Vec4::new(v.x, v.y, v.z, 1.0);
+            assert!((q * v).cmp(rotated.truncate(), EPSILON));
+        }
+
+        {
+            // Non-normalized quaternions are normalized before rotating
+            let q = Quat::of(3.0, 0.0, 0.0, 3.0);
+            let v = Vec3::new(0.0, 1.0, 0.0);
+            assert!((q * v).cmp(Vec3::new(0.0, 0.0, 1.0), EPSILON));
+        }
+    }
+
+    #[test]
+    fn from_mat4() {
+        // A quaternion and its negation describe the same rotation
+        let same_rotation = |a: Quat, b: Quat| cmp_f32(a.dot(&b).abs(), 1.0, EPSILON);
+
+        let rotations = [
+            Quat::new(),
+            Quat::from_axis(Vec3::new(1.0, 2.0, -0.5).normalize(), FRAC_PI_4),
+            // Rotations of nearly 180 degrees about each axis hit the non-trace branches
+            Quat::from_axis(Vec3::new(1.0, 0.0, 0.0), 3.0),
+            Quat::from_axis(Vec3::new(0.0, 1.0, 0.0), 3.0),
+            Quat::from_axis(Vec3::new(0.0, 0.0, 1.0), -3.0),
+        ];
+        for q in rotations {
+            assert!(same_rotation(Quat::from_mat4(Mat4::rotate(q)), q));
+            assert!(same_rotation(
+                Quat::from_mat3(Mat3::from_mat4(Mat4::rotate(q))),
+                q
+            ));
+        }
+    }
+
+    #[test]
+    fn to_euler() {
+        let epsilon = 1e-2;
+        for pitch in [-1.2, -0.4, 0.0, 0.7, 1.3] {
+            for yaw in [-2.5, -0.3, 0.0, 1.1, 3.0] {
+                for roll in [-3.0, -1.0, 0.0, 0.5, 2.2] {
+                    let (p, y, r) = Quat::from_euler(pitch, yaw, roll).to_euler();
+                    assert!(cmp_f32(p, pitch, epsilon));
+                    assert!(cmp_f32(y, yaw, epsilon));
+                    assert!(cmp_f32(r, roll, epsilon));
+                }
+            }
+        }
+
+        {
+            // In gimbal lock the roll is folded into the yaw, which still describes the same rotation
+            let q = Quat::from_euler(FRAC_PI_2, 0.8, 0.3);
+            let (p, y, r) = q.to_euler();
+            assert!(cmp_f32(p, FRAC_PI_2, epsilon));
+            assert!(cmp_f32(r, 0.0, epsilon));
+            assert!(cmp_f32(
+                q.dot(&Quat::from_euler(p, y, r)).abs(),
+                1.0,
+                EPSILON
+            ));
+        }
+    }
+
+    #[test]
+    fn pow() {
+        let q = Quat::from_axis(Vec3::new(0.0, 0.0, 1.0), FRAC_PI_2);
+
+        {
+            assert!(q.pow(1.0).cmp(&q, EPSILON));
+        }
+
+        {
+            assert!(q.pow(0.0).cmp(&Quat::new(), EPSILON));
+        }
+
+        {
+            let expected = Quat::from_axis(Vec3::new(0.0, 0.0, 1.0), FRAC_PI_4);
+            assert!(q.pow(0.5).cmp(&expected, EPSILON));
+        }
+
+        {
+            assert!(q.ln().exp().cmp(&q, EPSILON));
+        }
+
+        {
+            // The identity has no rotation axis
+            assert!(Quat::new().ln().cmp(&Quat::of(0.0, 0.0, 0.0, 0.0), EPSILON));
+            assert!(Quat::new().pow(0.5).cmp(&Quat::new(), EPSILON));
+        }
+    }
+
+    #[test]
+    fn slerp() {
+        let axis = Vec3::new(0.0, 1.0, 0.0);
+        let a = Quat::from_axis(axis, 0.0);
+        let b = Quat::from_axis(axis, FRAC_PI_2);
+        {
+            assert!(a.slerp(b, 0.0).cmp(&a, EPSILON));
+            assert!(a.slerp(b, 1.0).cmp(&b, EPSILON));
+            assert!(
+                a.slerp(b, 0.5)
+                    .cmp(&Quat::from_axis(axis, FRAC_PI_4), EPSILON)
+            );
+        }
+
+        {
+            // The 2 halves of a 180 degree rotation meet in the middle
+            let c = Quat::from_axis(axis, -FRAC_PI_2);
+            assert!(c.slerp(b, 0.5).cmp(&a, EPSILON));
+        }
+
+        {
+            // A negated quaternion describes the same rotation, so the shorter arc is taken
+            let negated = Quat::of(-b.x, -b.y, -b.z, -b.w);
+            let mid = a.slerp(negated, 0.5);
+            let expected = Quat::from_axis(axis, FRAC_PI_4);
+            assert!(
+                mid.rotate_vec(Vec3::new(1.0, 0.0, 0.0))
+                    .cmp(expected.rotate_vec(Vec3::new(1.0, 0.0, 0.0)), EPSILON)
+            );
+        }
+
+        {
+            // Nearly identical rotations don't blow up
+            let nearly_a = Quat::from_axis(axis, 1e-6);
+            assert!(a.slerp(nearly_a, 0.5).cmp(&a, EPSILON));
+        }
+    }
+
+    #[test]
+    fn nlerp() {
+        let axis = Vec3::new(0.0, 1.0, 0.0);
+        let a = Quat::from_axis(axis, 0.0);
+        let b = Quat::from_axis(axis, FRAC_PI_2);
+        {
+            assert!(a.nlerp(b, 0.0).cmp(&a, EPSILON));
+            assert!(a.nlerp(b, 1.0).cmp(&b, EPSILON));
+            // The midpoint is symmetric, so it matches slerp
+            assert!(a.nlerp(b, 0.5).cmp(&a.slerp(b, 0.5), EPSILON));
+        }
+
+        {
+            let c = Quat::from_axis(Vec3::new(1.0, -1.0, 0.5).normalize(), 2.5);
+            let negated = Quat::of(-c.x, -c.y, -c.z, -c.w);
+            for i in 0..=10 {
+                let t = i as f32 / 10.0;
+                assert!(cmp_f32(a.nlerp(c, t).length(), 1.0, EPSILON));
+                assert!(cmp_f32(b.nlerp(negated, t).length(), 1.0, EPSILON));
+            }
+        }
+    }
+
+    #[test]
+    fn squad() {
+        let axis = Vec3::new(0.0, 1.0, 0.0);
+        let q0 = Quat::new();
+        let q1 = Quat::from_axis(axis, FRAC_PI_2);
+
+        {
+            assert!(Quat::squad(q0, q1, q0, q1, 0.0).cmp(&q0, EPSILON));
+            assert!(Quat::squad(q0, q1, q0, q1, 1.0).cmp(&q1, EPSILON));
+        }
+
+        {
+            // With the control points on the same arc, squad follows the arc
+            let expected = Quat::from_axis(axis, FRAC_PI_4);
+            assert!(Quat::squad(q0, q1, q0, q1, 0.5).cmp(&expected, EPSILON));
+        }
+    }
 }
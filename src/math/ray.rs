@@ -0,0 +1,179 @@
+use crate::math::{aabb::Aabb, vec3::Vec3};
+
+/// Represents a half-line that starts at an origin and extends infinitely in a direction
+///
+/// Rays are mostly used for picking, e.g. casting a ray from the camera through the cursor
+/// and testing which object it hits first
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    /// The point the ray starts at
+    pub origin: Vec3,
+    /// The direction the ray extends in, normalized
+    pub dir: Vec3,
+}
+
+impl Ray {
+    /// Creates a new ray
+    /// - `origin` -> the point the ray starts at
+    /// - `dir` -> the direction the ray extends in, it gets normalized
+    pub fn new(origin: Vec3, dir: Vec3) -> Self {
+        Self {
+            origin,
+            dir: dir.normalize(),
+        }
+    }
+
+    /// Returns the point at a distance along the ray
+    /// - `t` -> the distance from the origin
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.dir * t
+    }
+
+    /// Returns the closest distance between the ray and a line segment,
+    /// along with the distance along the ray at which it occurs
+    /// - `a` -> the start of the segment
+    /// - `b` -> the end of the segment
+    pub fn distance_to_segment(&self, a: Vec3, b: Vec3) -> (f32, f32) {
+        let segment = b - a;
+        let offset = self.origin - a;
+
+        let segment_sq = segment.length_sq();
+        let dir_dot_segment = self.dir.dot(segment);
+        let dir_dot_offset = self.dir.dot(offset);
+        let segment_dot_offset = segment.dot(offset);
+
+        // The ray direction is normalized, so the denominator is the squared sine of the angle
+        // between the ray and the segment (times the squared segment length)
+        let denominator = segment_sq - dir_dot_segment * dir_dot_segment;
+        let s = if denominator.abs() < f32::EPSILON || segment_sq < f32::EPSILON {
+            0.0
+        } else {
+            ((segment_dot_offset - dir_dot_segment * dir_dot_offset) / denominator).clamp(0.0, 1.0)
+        };
+
+        let point_on_segment = a + segment * s;
+        let t = (point_on_segment - self.origin).dot(self.dir).max(0.0);
+        (self.at(t).dist(point_on_segment), t)
+    }
+
+    /// Returns the distance along the ray at which it intersects a plane, if it does
+    /// - `point` -> any point on the plane
+    /// - `normal` -> the normal of the plane
+    pub fn intersect_plane(&self, point: Vec3, normal: Vec3) -> Option<f32> {
+        let denominator = self.dir.dot(normal);
+        if denominator.abs() < f32::EPSILON {
+            return None;
+        }
+
+        let t = (point - self.origin).dot(normal) / denominator;
+        (t >= 0.0).then_some(t)
+    }
+
+    /// Returns the distance along the ray at which it enters a bounding box, if it hits it
+    /// - `aabb` -> the bounding box to test
+    ///
+    /// The intersection is found with the slab method, clipping the ray against the pair of
+    /// planes on each axis, if the ray starts inside the box the distance is `0.0`
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        let mut near = 0.0_f32;
+        let mut far = f32::INFINITY;
+        for axis in 0..3 {
+            let (origin, dir) = (self.origin[axis], self.dir[axis]);
+            let (min, max) = (aabb.min[axis], aabb.max[axis]);
+
+            // A ray parallel to the slab either always or never lies between its planes
+            if dir.abs() < f32::EPSILON {
+                if origin < min || origin > max {
+                    return None;
+                }
+                continue;
+            }
+
+            let t1 = (min - origin) / dir;
+            let t2 = (max - origin) / dir;
+            near = near.max(t1.min(t2));
+            far = far.min(t1.max(t2));
+            if near > far {
+                return None;
+            }
+        }
+
+        Some(near)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{EPSILON, cmp_f32};
+
+    #[test]
+    fn distance_to_segment() {
+        let ray = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+
+        {
+            let (distance, t) =
+                ray.distance_to_segment(Vec3::new(-1.0, 2.0, 0.0), Vec3::new(1.0, 2.0, 0.0));
+            assert!(cmp_f32(distance, 2.0, EPSILON));
+            assert!(cmp_f32(t, 5.0, EPSILON));
+        }
+
+        {
+            let (distance, _) =
+                ray.distance_to_segment(Vec3::new(3.0, 0.0, 0.0), Vec3::new(6.0, 0.0, 0.0));
+            assert!(cmp_f32(distance, 3.0, EPSILON));
+        }
+    }
+
+    #[test]
+    fn intersect_plane() {
+        let ray = Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+
+        {
+            let t = ray.intersect_plane(Vec3::ZERO, Vec3::UP);
+            assert!(cmp_f32(t.unwrap(), 5.0, EPSILON));
+        }
+
+        {
+            let t = ray.intersect_plane(Vec3::new(0.0, 10.0, 0.0), Vec3::UP);
+            assert!(t.is_none());
+        }
+
+        {
+            let t = ray.intersect_plane(Vec3::ZERO, Vec3::RIGHT);
+            assert!(t.is_none());
+        }
+    }
+
+    #[test]
+    fn intersect_aabb() {
+        let aabb = Aabb::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+        {
+            let ray = Ray::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+            assert!(cmp_f32(ray.intersect_aabb(&aabb).unwrap(), 4.0, EPSILON));
+
+            let ray = Ray::new(Vec3::new(-3.0, -3.0, 0.5), Vec3::new(1.0, 1.0, 0.0));
+            let t = ray.intersect_aabb(&aabb).unwrap();
+            assert!(ray.at(t).cmp(Vec3::new(-1.0, -1.0, 0.5), EPSILON));
+        }
+
+        {
+            // Misses, including rays pointing away and parallel rays outside a slab
+            let ray = Ray::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+            assert!(ray.intersect_aabb(&aabb).is_none());
+
+            let ray = Ray::new(Vec3::new(-5.0, 2.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+            assert!(ray.intersect_aabb(&aabb).is_none());
+
+            let ray = Ray::new(Vec3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0));
+            assert!(ray.intersect_aabb(&aabb).is_none());
+        }
+
+        {
+            // Rays starting inside hit immediately
+            let ray = Ray::new(Vec3::new(0.5, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0));
+            assert!(cmp_f32(ray.intersect_aabb(&aabb).unwrap(), 0.0, EPSILON));
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::math::{mat4::Mat4, vec3::Vec3, vec4::Vec4};
+
+/// Represents a sphere that encloses a set of points, described by a center and a radius
+///
+/// A bounding sphere is a cheap bounding volume that is useful for culling,
+/// as testing a sphere against a plane or another sphere only takes a few operations
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingSphere {
+    /// The center of the sphere
+    pub center: Vec3,
+    /// The radius of the sphere
+    pub radius: f32,
+}
+
+impl BoundingSphere {
+    /// Creates a new bounding sphere
+    /// - `center` -> the center of the sphere
+    /// - `radius` -> the radius of the sphere
+    pub const fn new(center: Vec3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Creates a new bounding sphere that encloses all of the points
+    /// - `points` -> the points to enclose
+    ///
+    /// The sphere is fitted with Ritter's algorithm, which is fast but not minimal,
+    /// the resulting sphere is usually a few percent larger than the optimal one
+    ///
+    /// If `points` is empty, the caller thread panics
+    pub fn from_points(points: &[Vec3]) -> Self {
+        assert!(
+            !points.is_empty(),
+            "Cannot create a bounding sphere from an empty slice!"
+        );
+
+        // Find a rough diameter by hopping to the farthest point twice
+        let farthest = |from: Vec3| {
+            points.iter().copied().fold(from, |a, b| {
+                if from.dist_sq(b) > from.dist_sq(a) {
+                    b
+                } else {
+                    a
+                }
+            })
+        };
+        let a = farthest(points[0]);
+        let b = farthest(a);
+
+        let mut sphere = Self::new(a.lerp(b, 0.5), a.dist(b) / 2.0);
+        for &point in points {
+            sphere = sphere.enclose(point);
+        }
+
+        sphere
+    }
+
+    /// Returns whether the point lies within the sphere
+    /// - `point` -> the point to test
+    pub fn contains(&self, point: Vec3) -> bool {
+        self.center.dist_sq(point) <= self.radius * self.radius
+    }
+
+    /// Returns the smallest sphere that encloses both spheres
+    /// - `self` -> the first sphere
+    /// - `other` -> the second sphere
+    pub fn merge(&self, other: &Self) -> Self {
+        let offset = other.center - self.center;
+        let dist = offset.length();
+
+        // One of the spheres already encloses the other one
+        if dist + other.radius <= self.radius {
+            return *self;
+        }
+        if dist + self.radius <= other.radius {
+            return *other;
+        }
+
+        let radius = (dist + self.radius + other.radius) / 2.0;
+        let center = self.center + offset * ((radius - self.radius) / dist);
+        Self::new(center, radius)
+    }
+
+    /// Returns the sphere transformed by a matrix
+    /// - `mat` -> the transformation matrix
+    ///
+    /// The radius is scaled by the largest scale of the matrix axes,
+    /// so the result still encloses the transformed volume under non-uniform scaling
+    pub fn transformed(&self, mat: &Mat4) -> Self {
+        let center = *mat * Vec4::from(self.center);
+        let axis_length = |axis: Vec4| Vec3::new(axis.x, axis.y, axis.z).length();
+        let scale = axis_length(mat.x_axis)
+            .max(axis_length(mat.y_axis))
+            .max(axis_length(mat.z_axis));
+
+        Self::new(Vec3::new(center.x, center.y, center.z), self.radius * scale)
+    }
+
+    /// Returns the sphere grown just enough to enclose the point
+    fn enclose(self, point: Vec3) -> Self {
+        let dist = self.center.dist(point);
+        if dist <= self.radius {
+            return self;
+        }
+
+        let radius = (self.radius + dist) / 2.0;
+        let center = self.center + (point - self.center) * ((radius - self.radius) / dist);
+        Self::new(center, radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{EPSILON, cmp_f32};
+
+    #[test]
+    fn from_points() {
+        {
+            let points = [
+                Vec3::new(-1.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.5, 0.0),
+                Vec3::new(0.0, 0.0, -0.5),
+            ];
+            let sphere = BoundingSphere::from_points(&points);
+            assert!(sphere.center.cmp(Vec3::ZERO, EPSILON));
+            assert!(cmp_f32(sphere.radius, 1.0, EPSILON));
+        }
+
+        {
+            let points = [
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(4.0, 0.0, 0.0),
+                Vec3::new(2.0, 3.0, 0.0),
+                Vec3::new(2.0, -1.0, 2.0),
+            ];
+            let sphere = BoundingSphere::from_points(&points);
+            for point in points {
+                assert!(sphere.radius + EPSILON >= sphere.center.dist(point));
+            }
+        }
+    }
+
+    #[test]
+    fn merge() {
+        {
+            let a = BoundingSphere::new(Vec3::new(-2.0, 0.0, 0.0), 1.0);
+            let b = BoundingSphere::new(Vec3::new(3.0, 0.0, 0.0), 2.0);
+            let merged = a.merge(&b);
+            let expected = BoundingSphere::new(Vec3::new(1.0, 0.0, 0.0), 4.0);
+            assert!(merged.center.cmp(expected.center, EPSILON));
+            assert!(cmp_f32(merged.radius, expected.radius, EPSILON));
+            for sphere in [a, b] {
+                let reach = merged.center.dist(sphere.center) + sphere.radius;
+                assert!(reach <= merged.radius + EPSILON);
+            }
+        }
+
+        {
+            let a = BoundingSphere::new(Vec3::new(1.0, 1.0, 1.0), 5.0);
+            let b = BoundingSphere::new(Vec3::new(2.0, 1.0, 1.0), 1.0);
+            let merged = a.merge(&b);
+            assert!(merged.center.cmp(a.center, EPSILON));
+            assert!(cmp_f32(merged.radius, a.radius, EPSILON));
+        }
+    }
+
+    #[test]
+    fn transformed() {
+        {
+            let sphere = BoundingSphere::new(Vec3::new(1.0, 0.0, 0.0), 2.0);
+            let mat =
+                Mat4::translate(Vec3::new(0.0, 5.0, 0.0)) * Mat4::scale(Vec3::new(1.0, 3.0, 2.0));
+            let transformed = sphere.transformed(&mat);
+            assert!(transformed.center.cmp(Vec3::new(1.0, 5.0, 0.0), EPSILON));
+            assert!(cmp_f32(transformed.radius, 6.0, EPSILON));
+        }
+    }
+}
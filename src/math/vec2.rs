@@ -1,12 +1,18 @@
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
 use bytemuck::{Pod, Zeroable};
 
+use crate::math::{self, vec3::Vec3};
+
 /// Represents an arbitrary collection of 2 components
 ///
 /// More specifically, a [`Vec2`] is generally used for points and directions in 2D space
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq, Zeroable, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec2 {
     /// The X component of the vector
     pub x: f32,
@@ -17,6 +23,8 @@ pub struct Vec2 {
 impl Vec2 {
     /// A vector with both components of value 0.0, also known as a zero vector
     pub const ZERO: Self = Self::new(0.0, 0.0);
+    /// A vector with both components of value 1.0
+    pub const ONE: Self = Self::new(1.0, 1.0);
     /// A unit vector pointing to the right
     pub const RIGHT: Self = Self::new(1.0, 0.0);
     /// A unit vector pointing to the left
@@ -42,6 +50,25 @@ impl Vec2 {
         }
     }
 
+    /// Creates a new unit vector pointing in the direction of an angle
+    /// - `angle` -> the angle in radians, measured counter-clockwise from the positive X axis
+    pub fn from_angle(angle: f32) -> Self {
+        Self {
+            x: angle.cos(),
+            y: angle.sin(),
+        }
+    }
+
+    /// Returns the vector rotated counter-clockwise by 90 degrees, which is perpendicular to it
+    ///
+    /// This is the same as rotating the vector by `PI / 2`, but exact and cheaper
+    pub fn perp(self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
     /// Returns a new flipped vector from the original
     ///
     /// Flipping inverses the vectors direction while preserving its length
@@ -132,6 +159,43 @@ impl Vec2 {
         (self.x * other.x) + (self.y * other.y)
     }
 
+    /// Multiplies 2 vectors componentwise (the Hadamard product)
+    /// - `self` -> the first vector
+    /// - `other` -> the second vector
+    ///
+    /// This is useful for per-axis scaling, it's the same as `self * other`
+    pub fn mul_elem(self, other: Self) -> Self {
+        Self {
+            x: self.x * other.x,
+            y: self.y * other.y,
+        }
+    }
+
+    /// Returns the point on a line segment that is closest to this vector (as point)
+    /// - `self` -> the vector (as point)
+    /// - `a` -> the start of the segment
+    /// - `b` -> the end of the segment
+    ///
+    /// If the segment has no length, `a` is returned
+    pub fn closest_point_on_segment(self, a: Self, b: Self) -> Self {
+        let segment = b - a;
+        let length_sq = segment.length_sq();
+        if length_sq < f32::EPSILON {
+            return a;
+        }
+
+        let t = ((self - a).dot(segment) / length_sq).clamp(0.0, 1.0);
+        a + segment * t
+    }
+
+    /// Returns the distance between this vector (as point) and a line segment
+    /// - `self` -> the vector (as point)
+    /// - `a` -> the start of the segment
+    /// - `b` -> the end of the segment
+    pub fn distance_to_segment(self, a: Self, b: Self) -> f32 {
+        self.dist(self.closest_point_on_segment(a, b))
+    }
+
     /// Linearly interpolates between 2 vectors
     /// - `self` -> the source vector
     /// - `other` -> the target vector
@@ -143,6 +207,86 @@ impl Vec2 {
         }
     }
 
+    /// Returns the componentwise minimum of 2 vectors
+    /// - `self` -> the first vector
+    /// - `other` -> the second vector
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+        }
+    }
+
+    /// Returns the componentwise maximum of 2 vectors
+    /// - `self` -> the first vector
+    /// - `other` -> the second vector
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+        }
+    }
+
+    /// Clamps every component of the vector between the matching components of `lo` and `hi`
+    /// - `self` -> the vector to clamp
+    /// - `lo` -> the lower bound
+    /// - `hi` -> the upper bound
+    ///
+    /// Every component of `lo` is assumed to be smaller than or equal to the matching component of `hi`,
+    /// otherwise the result is `hi` for that component
+    pub fn clamp(self, lo: Self, hi: Self) -> Self {
+        self.max(lo).min(hi)
+    }
+
+    /// Returns the vector with the absolute value of every component
+    pub fn abs(self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+        }
+    }
+
+    /// Rotates the vector counter-clockwise about the origin
+    /// - `self` -> the vector
+    /// - `angle` -> the amount of rotation in radians
+    pub fn rotate(self, angle: f32) -> Self {
+        let cos = angle.cos();
+        let sin = angle.sin();
+        Self {
+            x: (self.x * cos) - (self.y * sin),
+            y: (self.x * sin) + (self.y * cos),
+        }
+    }
+
+    /// Rotates the vector (as point) counter-clockwise about a pivot point
+    /// - `self` -> the vector (as point)
+    /// - `pivot` -> the point to rotate about
+    /// - `angle` -> the amount of rotation in radians
+    pub fn rotate_around(self, pivot: Self, angle: f32) -> Self {
+        (self - pivot).rotate(angle) + pivot
+    }
+
+    /// Returns a [`Vec3`] made of the vector and a `z` component
+    /// - `z` -> `1.0` for 2D homogeneous points (affected by translation in a `Mat3`), `0.0` for directions
+    pub fn extend(self, z: f32) -> Vec3 {
+        Vec3::new(self.x, self.y, z)
+    }
+
+    /// Returns the `(y, x)` components of the vector as a [`Vec2`]
+    pub fn yx(self) -> Vec2 {
+        Vec2::new(self.y, self.x)
+    }
+
+    /// Returns the raw bit patterns of the components, which can be hashed and compared exactly
+    ///
+    /// This allows vectors to be used as `HashMap` keys (e.g. to weld vertices by their exact position),
+    /// with the following caveats:
+    /// - `0.0` and `-0.0` have different bit patterns, so they're considered different keys
+    /// - `NaN`s are considered equal only if their bit patterns match exactly
+    pub fn to_bits(self) -> [u32; 2] {
+        [self.x.to_bits(), self.y.to_bits()]
+    }
+
     /// Compares 2 vectors and returns if they're equal or not
     /// - `self` -> the first vector
     /// - `other` -> the second vector
@@ -160,6 +304,12 @@ impl Default for Vec2 {
     }
 }
 
+impl fmt::Display for Vec2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        math::fmt_components(f, &[self.x, self.y])
+    }
+}
+
 impl Add<Vec2> for Vec2 {
     type Output = Self;
     fn add(self, other: Self) -> Self {
@@ -190,6 +340,13 @@ impl Mul<f32> for Vec2 {
     }
 }
 
+impl Mul<Vec2> for Vec2 {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        self.mul_elem(other)
+    }
+}
+
 impl Div<f32> for Vec2 {
     type Output = Self;
     fn div(self, factor: f32) -> Self {
@@ -236,10 +393,75 @@ impl DivAssign<f32> for Vec2 {
     }
 }
 
+impl Neg for Vec2 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.flip()
+    }
+}
+
+impl Index<usize> for Vec2 {
+    type Output = f32;
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Index {} is out of range for a Vec2 (2 components)", index),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vec2 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Index {} is out of range for a Vec2 (2 components)", index),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::math::{EPSILON, cmp_f32};
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn constants() {
+        assert_eq!(Vec2::ZERO, Vec2 { x: 0.0, y: 0.0 });
+        assert_eq!(Vec2::ONE, Vec2 { x: 1.0, y: 1.0 });
+        assert_eq!(Vec2::ONE, Vec2::splat(1.0));
+    }
+
+    #[test]
+    fn index() {
+        {
+            let v = Vec2::new(1.0, 2.0);
+            assert!(cmp_f32(v[0], 1.0, EPSILON));
+            assert!(cmp_f32(v[1], 2.0, EPSILON));
+        }
+
+        {
+            let mut v = Vec2::splat(0.0);
+            v[0] = -1.0;
+            v[1] = -2.0;
+            assert!(v.cmp(Vec2::new(-1.0, -2.0), EPSILON));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 2 is out of range for a Vec2 (2 components)")]
+    fn index_out_of_range() {
+        let v = Vec2::splat(0.0);
+        let _ = v[2];
+    }
+
+    #[test]
+    fn swizzle() {
+        let v = Vec2::new(1.0, 2.0);
+        assert!(v.yx().cmp(Vec2::new(2.0, 1.0), EPSILON));
+    }
 
     #[test]
     fn add() {
@@ -292,6 +514,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mul_elem() {
+        {
+            let v1 = Vec2::new(2.0, 3.0);
+            let v2 = Vec2::new(5.0, -6.0);
+            let expected = Vec2::new(10.0, -18.0);
+            assert!(v1.mul_elem(v2).cmp(expected, EPSILON));
+            assert!((v1 * v2).cmp(expected, EPSILON));
+        }
+
+        {
+            let v = Vec2::new(2.0, 3.0);
+            assert!((v * Vec2::splat(1.0)).cmp(v, EPSILON));
+        }
+    }
+
     #[test]
     fn div() {
         {
@@ -322,6 +560,11 @@ mod tests {
             let expected = Vec2::new(-2.5, -4.0);
             assert!(v.flip().cmp(expected, EPSILON));
         }
+
+        {
+            let v = Vec2::new(2.5, 4.0);
+            assert!((-v).cmp(v.flip(), EPSILON));
+        }
     }
 
     #[test]
@@ -436,4 +679,103 @@ mod tests {
             assert!(v1.lerp(v2, 0.5).cmp(expected, EPSILON));
         }
     }
+
+    #[test]
+    fn min_max() {
+        {
+            let v1 = Vec2::new(-1.0, 4.0);
+            let v2 = Vec2::new(2.0, -3.0);
+            assert!(v1.min(v2).cmp(Vec2::new(-1.0, -3.0), EPSILON));
+            assert!(v1.max(v2).cmp(Vec2::new(2.0, 4.0), EPSILON));
+        }
+
+        {
+            let v = Vec2::new(-5.0, 0.5);
+            let expected = Vec2::new(-1.0, 0.5);
+            assert!(
+                v.clamp(Vec2::splat(-1.0), Vec2::splat(1.0))
+                    .cmp(expected, EPSILON)
+            );
+        }
+
+        {
+            let v = Vec2::new(-5.0, 0.5);
+            assert!(v.abs().cmp(Vec2::new(5.0, 0.5), EPSILON));
+        }
+    }
+
+    #[test]
+    fn rotate() {
+        {
+            let v = Vec2::new(1.0, 0.0);
+            let expected = Vec2::new(0.0, 1.0);
+            assert!(v.rotate(std::f32::consts::FRAC_PI_2).cmp(expected, EPSILON));
+        }
+
+        {
+            let v = Vec2::new(3.0, 1.0);
+            let pivot = Vec2::new(1.0, 1.0);
+            let expected = Vec2::new(-1.0, 1.0);
+            assert!(
+                v.rotate_around(pivot, std::f32::consts::PI)
+                    .cmp(expected, EPSILON)
+            );
+        }
+    }
+
+    #[test]
+    fn from_angle() {
+        {
+            let expected = Vec2::new(1.0, 0.0);
+            assert!(Vec2::from_angle(0.0).cmp(expected, EPSILON));
+        }
+
+        {
+            let expected = Vec2::new(0.0, 1.0);
+            assert!(Vec2::from_angle(FRAC_PI_2).cmp(expected, EPSILON));
+        }
+    }
+
+    #[test]
+    fn perp() {
+        {
+            let v = Vec2::new(3.0, 1.0);
+            let expected = Vec2::new(-1.0, 3.0);
+            assert!(v.perp().cmp(expected, EPSILON));
+            assert!(cmp_f32(v.perp().dot(v), 0.0, EPSILON));
+        }
+
+        {
+            let v = Vec2::new(2.0, -5.0);
+            assert!(v.perp().cmp(v.rotate(FRAC_PI_2), EPSILON));
+        }
+    }
+
+    #[test]
+    fn closest_point_on_segment() {
+        let a = Vec2::new(1.0, 1.0);
+        let b = Vec2::new(5.0, 1.0);
+
+        {
+            // The projection falls before `a`
+            let p = Vec2::new(-2.0, 5.0);
+            assert!(p.closest_point_on_segment(a, b).cmp(a, EPSILON));
+            assert!(cmp_f32(p.distance_to_segment(a, b), 5.0, EPSILON));
+        }
+
+        {
+            // The projection falls after `b`
+            let p = Vec2::new(8.0, -3.0);
+            assert!(p.closest_point_on_segment(a, b).cmp(b, EPSILON));
+            assert!(cmp_f32(p.distance_to_segment(a, b), 5.0, EPSILON));
+        }
+
+        {
+            // The projection falls between `a` and `b`
+            let p = Vec2::new(3.0, 4.0);
+            let expected = Vec2::new(3.0, 1.0);
+            assert!(p.closest_point_on_segment(a, b).cmp(expected, EPSILON));
+            assert!(cmp_f32(p.distance_to_segment(a, b), 3.0, EPSILON));
+        }
+    }
 }
@@ -1,14 +1,18 @@
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
 use bytemuck::{Pod, Zeroable};
 
-use crate::math::vec2::Vec2;
+use crate::math::{self, quat::Quat, vec2::Vec2, vec4::Vec4};
 
 /// Represents an arbitrary collection of 3 components
 ///
 /// More specifically, a [`Vec3`] is generally used for points and directions in 3D space
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq, Zeroable, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec3 {
     /// The X component of the vector
     pub x: f32,
@@ -21,6 +25,8 @@ pub struct Vec3 {
 impl Vec3 {
     /// A vector with all components of value 0.0, also known as a zero vector
     pub const ZERO: Self = Self::new(0.0, 0.0, 0.0);
+    /// A vector with all components of value 1.0
+    pub const ONE: Self = Self::new(1.0, 1.0, 1.0);
     /// A unit vector pointing to the right
     pub const RIGHT: Self = Self::new(1.0, 0.0, 0.0);
     /// A unit vector pointing to the left
@@ -147,6 +153,19 @@ impl Vec3 {
         (self.x * other.x) + (self.y * other.y) + (self.z * other.z)
     }
 
+    /// Multiplies 2 vectors componentwise (the Hadamard product)
+    /// - `self` -> the first vector
+    /// - `other` -> the second vector
+    ///
+    /// This is useful for per-axis scaling, it's the same as `self * other`
+    pub fn mul_elem(self, other: Self) -> Self {
+        Self {
+            x: self.x * other.x,
+            y: self.y * other.y,
+            z: self.z * other.z,
+        }
+    }
+
     /// Returns the cross product between 2 vectors
     /// - `self` -> the first vector
     /// - `other` -> the second vector
@@ -160,6 +179,103 @@ impl Vec3 {
         }
     }
 
+    /// Reflects the vector off a surface
+    /// - `self` -> the incident vector, pointing toward the surface
+    /// - `normal` -> the normal of the surface, which must be of unit length
+    ///
+    /// The reflected vector points away from the surface and has the same length as the incident vector
+    pub fn reflect(self, normal: Self) -> Self {
+        self - normal * (2.0 * self.dot(normal))
+    }
+
+    /// Projects the vector onto another vector, which is the part of the vector parallel to it
+    /// - `self` -> the vector to project
+    /// - `other` -> the vector to project onto, which doesn't have to be normalized
+    ///
+    /// If `other` has no length, there is no direction to project onto, so [`Vec3::ZERO`] is returned
+    pub fn project_onto(self, other: Self) -> Self {
+        let length_sq = other.dot(other);
+        if length_sq < f32::EPSILON {
+            return Self::ZERO;
+        }
+
+        other * (self.dot(other) / length_sq)
+    }
+
+    /// Rejects the vector from another vector, which is the part of the vector perpendicular to it
+    /// - `self` -> the vector to reject
+    /// - `other` -> the vector to reject from, which doesn't have to be normalized
+    ///
+    /// The projection and the rejection add up to the original vector
+    pub fn reject_from(self, other: Self) -> Self {
+        self - self.project_onto(other)
+    }
+
+    /// Returns the angle between 2 vectors in radians, in the range of `0 - PI`
+    /// - `self` -> the first vector
+    /// - `other` -> the second vector
+    ///
+    /// Neither vector has to be normalized, if either of them has no length
+    /// the angle is undefined, so `0.0` is returned
+    pub fn angle_between(self, other: Self) -> f32 {
+        if self.length() < f32::EPSILON || other.length() < f32::EPSILON {
+            return 0.0;
+        }
+
+        // Floating point drift can push the dot product of unit vectors slightly out of the domain of acos
+        self.normalize()
+            .dot(other.normalize())
+            .clamp(-1.0, 1.0)
+            .acos()
+    }
+
+    /// Returns the vector with its component into a surface removed
+    /// - `self` -> the vector (usually a velocity)
+    /// - `normal` -> the unit normal of the surface
+    ///
+    /// The result slides along the surface, which is useful for collision response
+    pub fn slide(self, normal: Self) -> Self {
+        self - normal * self.dot(normal)
+    }
+
+    /// Returns the vector bounced off a surface
+    /// - `self` -> the vector (usually a velocity) pointing towards the surface
+    /// - `normal` -> the unit normal of the surface
+    /// - `restitution` -> how much of the velocity into the surface is kept after the bounce
+    ///
+    /// The component along the surface is preserved, while the component into the surface
+    /// is reflected and scaled by `restitution`
+    /// - `restitution = 1.0` -> a perfectly elastic bounce (a pure reflection)
+    /// - `restitution = 0.0` -> no bounce at all (same as [`Vec3::slide()`])
+    pub fn bounce(self, normal: Self, restitution: f32) -> Self {
+        self.slide(normal) - normal * (self.dot(normal) * restitution)
+    }
+
+    /// Returns the point on a line segment that is closest to this vector (as point)
+    /// - `self` -> the vector (as point)
+    /// - `a` -> the start of the segment
+    /// - `b` -> the end of the segment
+    ///
+    /// If the segment has no length, `a` is returned
+    pub fn closest_point_on_segment(self, a: Self, b: Self) -> Self {
+        let segment = b - a;
+        let length_sq = segment.length_sq();
+        if length_sq < f32::EPSILON {
+            return a;
+        }
+
+        let t = ((self - a).dot(segment) / length_sq).clamp(0.0, 1.0);
+        a + segment * t
+    }
+
+    /// Returns the distance between this vector (as point) and a line segment
+    /// - `self` -> the vector (as point)
+    /// - `a` -> the start of the segment
+    /// - `b` -> the end of the segment
+    pub fn distance_to_segment(self, a: Self, b: Self) -> f32 {
+        self.dist(self.closest_point_on_segment(a, b))
+    }
+
     /// Linearly interpolates between 2 vectors
     /// - `self` -> the source vector
     /// - `other` -> the target vector
@@ -172,6 +288,87 @@ impl Vec3 {
         }
     }
 
+    /// Returns the componentwise minimum of 2 vectors
+    /// - `self` -> the first vector
+    /// - `other` -> the second vector
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+            z: self.z.min(other.z),
+        }
+    }
+
+    /// Returns the componentwise maximum of 2 vectors
+    /// - `self` -> the first vector
+    /// - `other` -> the second vector
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            x: self.x.max(other.x),
+            y: self.y.max(other.y),
+            z: self.z.max(other.z),
+        }
+    }
+
+    /// Clamps every component of the vector between the matching components of `lo` and `hi`
+    /// - `self` -> the vector to clamp
+    /// - `lo` -> the lower bound
+    /// - `hi` -> the upper bound
+    ///
+    /// Every component of `lo` is assumed to be smaller than or equal to the matching component of `hi`,
+    /// otherwise the result is `hi` for that component
+    pub fn clamp(self, lo: Self, hi: Self) -> Self {
+        self.max(lo).min(hi)
+    }
+
+    /// Returns the vector with the absolute value of every component
+    pub fn abs(self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+
+    /// Rotates the vector (as point) about a pivot point
+    /// - `self` -> the vector (as point)
+    /// - `pivot` -> the point to rotate about
+    /// - `rotation` -> the rotation to apply
+    pub fn rotate_around(self, pivot: Self, rotation: Quat) -> Self {
+        rotation.rotate_vec(self - pivot) + pivot
+    }
+
+    /// Returns a [`Vec4`] made of the vector and a `w` component
+    /// - `w` -> `1.0` for points (affected by translation), `0.0` for directions
+    pub fn extend(self, w: f32) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
+
+    /// Returns the `(x, y)` components of the vector as a [`Vec2`]
+    pub fn xy(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    /// Returns the `(x, z)` components of the vector as a [`Vec2`]
+    pub fn xz(self) -> Vec2 {
+        Vec2::new(self.x, self.z)
+    }
+
+    /// Returns the `(y, z)` components of the vector as a [`Vec2`]
+    pub fn yz(self) -> Vec2 {
+        Vec2::new(self.y, self.z)
+    }
+
+    /// Returns the raw bit patterns of the components, which can be hashed and compared exactly
+    ///
+    /// This allows vectors to be used as `HashMap` keys (e.g. to weld vertices by their exact position),
+    /// with the following caveats:
+    /// - `0.0` and `-0.0` have different bit patterns, so they're considered different keys
+    /// - `NaN`s are considered equal only if their bit patterns match exactly
+    pub fn to_bits(self) -> [u32; 3] {
+        [self.x.to_bits(), self.y.to_bits(), self.z.to_bits()]
+    }
+
     /// Compares 2 vectors and returns if they're equal or not
     /// - `self` -> the first vector
     /// - `other` -> the second vector
@@ -184,9 +381,18 @@ impl Vec3 {
     }
 }
 
+/// Converts a [`Vec2`] into a [`Vec3`] with `z` set to `0.0`, which keeps directions intact
+///
+/// For a 2D homogeneous point (`z` set to `1.0`, e.g. to be transformed by a `Mat3`), use [`Vec2::extend()`]
 impl From<Vec2> for Vec3 {
     fn from(value: Vec2) -> Self {
-        Self::new(value.x, value.y, 1.0)
+        value.extend(0.0)
+    }
+}
+
+impl From<Vec4> for Vec3 {
+    fn from(value: Vec4) -> Self {
+        value.truncate()
     }
 }
 
@@ -196,6 +402,12 @@ impl Default for Vec3 {
     }
 }
 
+impl fmt::Display for Vec3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        math::fmt_components(f, &[self.x, self.y, self.z])
+    }
+}
+
 impl Add<Vec3> for Vec3 {
     type Output = Self;
     fn add(self, other: Self) -> Self {
@@ -229,6 +441,13 @@ impl Mul<f32> for Vec3 {
     }
 }
 
+impl Mul<Vec3> for Vec3 {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        self.mul_elem(other)
+    }
+}
+
 impl Div<f32> for Vec3 {
     type Output = Self;
     fn div(self, scalar: f32) -> Self {
@@ -280,10 +499,109 @@ impl DivAssign<f32> for Vec3 {
     }
 }
 
+impl Neg for Vec3 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.flip()
+    }
+}
+
+impl Index<usize> for Vec3 {
+    type Output = f32;
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Index {} is out of range for a Vec3 (3 components)", index),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vec3 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Index {} is out of range for a Vec3 (3 components)", index),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::math::{EPSILON, cmp_f32};
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn constants() {
+        assert_eq!(
+            Vec3::ZERO,
+            Vec3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0
+            }
+        );
+        assert_eq!(
+            Vec3::ONE,
+            Vec3 {
+                x: 1.0,
+                y: 1.0,
+                z: 1.0
+            }
+        );
+        assert_eq!(Vec3::ONE, Vec3::splat(1.0));
+    }
+
+    #[test]
+    fn index() {
+        {
+            let v = Vec3::new(1.0, 2.0, 3.0);
+            assert!(cmp_f32(v[0], 1.0, EPSILON));
+            assert!(cmp_f32(v[1], 2.0, EPSILON));
+            assert!(cmp_f32(v[2], 3.0, EPSILON));
+        }
+
+        {
+            let mut v = Vec3::splat(0.0);
+            v[0] = -1.0;
+            v[1] = -2.0;
+            v[2] = -3.0;
+            assert!(v.cmp(Vec3::new(-1.0, -2.0, -3.0), EPSILON));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 3 is out of range for a Vec3 (3 components)")]
+    fn index_out_of_range() {
+        let v = Vec3::splat(0.0);
+        let _ = v[3];
+    }
+
+    #[test]
+    fn extend() {
+        {
+            let v = Vec2::new(1.0, 2.0);
+            assert!(Vec3::from(v).cmp(Vec3::new(1.0, 2.0, 0.0), EPSILON));
+            assert!(v.extend(1.0).cmp(Vec3::new(1.0, 2.0, 1.0), EPSILON));
+        }
+
+        {
+            let v = Vec3::new(1.0, 2.0, 3.0);
+            assert!(v.extend(0.0).cmp(Vec4::new(1.0, 2.0, 3.0, 0.0), EPSILON));
+        }
+    }
+
+    #[test]
+    fn swizzle() {
+        let v = Vec3::new(1.0, 2.0, 3.0);
+        assert!(v.xy().cmp(Vec2::new(1.0, 2.0), EPSILON));
+        assert!(v.xz().cmp(Vec2::new(1.0, 3.0), EPSILON));
+        assert!(v.yz().cmp(Vec2::new(2.0, 3.0), EPSILON));
+    }
 
     #[test]
     fn add() {
@@ -340,6 +658,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mul_elem() {
+        {
+            let v1 = Vec3::new(2.0, 3.0, 4.0);
+            let v2 = Vec3::new(5.0, 6.0, 7.0);
+            let expected = Vec3::new(10.0, 18.0, 28.0);
+            assert!(v1.mul_elem(v2).cmp(expected, EPSILON));
+            assert!((v1 * v2).cmp(expected, EPSILON));
+        }
+
+        {
+            let v = Vec3::new(2.0, 3.0, 4.0);
+            assert!((v * Vec3::splat(1.0)).cmp(v, EPSILON));
+        }
+    }
+
     #[test]
     fn div() {
         {
@@ -370,6 +704,11 @@ mod tests {
             let expected = Vec3::new(-2.5, -4.0, -6.0);
             assert!(v.flip().cmp(expected, EPSILON));
         }
+
+        {
+            let v = Vec3::new(2.5, 4.0, 6.0);
+            assert!((-v).cmp(v.flip(), EPSILON));
+        }
     }
 
     #[test]
@@ -485,6 +824,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn min_max() {
+        {
+            let v1 = Vec3::new(-1.0, 4.0, 0.0);
+            let v2 = Vec3::new(2.0, -3.0, -0.5);
+            assert!(v1.min(v2).cmp(Vec3::new(-1.0, -3.0, -0.5), EPSILON));
+            assert!(v1.max(v2).cmp(Vec3::new(2.0, 4.0, 0.0), EPSILON));
+        }
+
+        {
+            let v = Vec3::new(-5.0, 0.5, 7.0);
+            let lo = Vec3::new(-1.0, -1.0, -1.0);
+            let hi = Vec3::new(1.0, 1.0, 2.0);
+            let expected = Vec3::new(-1.0, 0.5, 2.0);
+            assert!(v.clamp(lo, hi).cmp(expected, EPSILON));
+        }
+
+        {
+            let v = Vec3::new(-5.0, 0.5, -0.0);
+            assert!(v.abs().cmp(Vec3::new(5.0, 0.5, 0.0), EPSILON));
+        }
+    }
+
     #[test]
     fn cross() {
         {
@@ -501,4 +863,175 @@ mod tests {
             assert!(v1.cross(v2).cmp(expected, EPSILON));
         }
     }
+
+    #[test]
+    fn reflect() {
+        {
+            let v = Vec3::new(1.0, -1.0, 0.0);
+            let normal = Vec3::new(0.0, 1.0, 0.0);
+            let expected = Vec3::new(1.0, 1.0, 0.0);
+            assert!(v.reflect(normal).cmp(expected, EPSILON));
+        }
+
+        {
+            // Hitting a surface head-on bounces straight back
+            let v = Vec3::new(0.0, 0.0, -2.0);
+            let normal = Vec3::new(0.0, 0.0, 1.0);
+            let expected = Vec3::new(0.0, 0.0, 2.0);
+            assert!(v.reflect(normal).cmp(expected, EPSILON));
+        }
+    }
+
+    #[test]
+    fn project_onto() {
+        {
+            let v = Vec3::new(3.0, 4.0, 5.0);
+            let onto = Vec3::new(2.0, 0.0, 0.0);
+            assert!(v.project_onto(onto).cmp(Vec3::new(3.0, 0.0, 0.0), EPSILON));
+            assert!(v.reject_from(onto).cmp(Vec3::new(0.0, 4.0, 5.0), EPSILON));
+        }
+
+        {
+            let v = Vec3::new(1.0, -2.0, 7.0);
+            let onto = Vec3::new(1.0, 1.0, 2.0);
+            let projection = v.project_onto(onto);
+            let rejection = v.reject_from(onto);
+            assert!((projection + rejection).cmp(v, EPSILON));
+            assert!(cmp_f32(rejection.dot(onto), 0.0, EPSILON));
+        }
+
+        {
+            let v = Vec3::new(1.0, 2.0, 3.0);
+            assert!(v.project_onto(Vec3::ZERO).cmp(Vec3::ZERO, EPSILON));
+            assert!(v.reject_from(Vec3::ZERO).cmp(v, EPSILON));
+        }
+    }
+
+    #[test]
+    fn angle_between() {
+        {
+            let v1 = Vec3::new(2.0, 0.0, 0.0);
+            let v2 = Vec3::new(0.0, 0.0, 3.0);
+            assert!(cmp_f32(v1.angle_between(v2), FRAC_PI_2, EPSILON));
+        }
+
+        {
+            let v = Vec3::new(1.0, 2.0, 3.0);
+            assert!(cmp_f32(v.angle_between(v), 0.0, EPSILON));
+            assert!(cmp_f32(v.angle_between(-v), PI, EPSILON));
+        }
+
+        {
+            let v = Vec3::new(1.0, 2.0, 3.0);
+            assert!(cmp_f32(v.angle_between(Vec3::ZERO), 0.0, EPSILON));
+        }
+    }
+
+    #[test]
+    fn slide() {
+        {
+            let velocity = Vec3::new(3.0, -2.0, 0.0);
+            let wall = Vec3::new(0.0, 1.0, 0.0);
+            let expected = Vec3::new(3.0, 0.0, 0.0);
+            assert!(velocity.slide(wall).cmp(expected, EPSILON));
+        }
+
+        {
+            let velocity = Vec3::new(-4.0, 1.0, 2.0);
+            let wall = Vec3::new(1.0, 0.0, 0.0);
+            let expected = Vec3::new(0.0, 1.0, 2.0);
+            assert!(velocity.slide(wall).cmp(expected, EPSILON));
+        }
+    }
+
+    #[test]
+    fn bounce() {
+        {
+            let velocity = Vec3::new(3.0, -2.0, 0.0);
+            let wall = Vec3::new(0.0, 1.0, 0.0);
+            let expected = Vec3::new(3.0, 2.0, 0.0);
+            assert!(velocity.bounce(wall, 1.0).cmp(expected, EPSILON));
+        }
+
+        {
+            let velocity = Vec3::new(3.0, -2.0, 0.0);
+            let wall = Vec3::new(0.0, 1.0, 0.0);
+            let expected = Vec3::new(3.0, 1.0, 0.0);
+            assert!(velocity.bounce(wall, 0.5).cmp(expected, EPSILON));
+        }
+    }
+
+    #[test]
+    fn to_bits() {
+        {
+            let positions = [
+                Vec3::new(1.0, 2.0, 3.0),
+                Vec3::new(0.0, 1.0, 0.0),
+                Vec3::new(1.0, 2.0, 3.0),
+            ];
+            let unique: std::collections::HashSet<_> =
+                positions.iter().map(|p| p.to_bits()).collect();
+            assert_eq!(unique.len(), 2);
+        }
+
+        {
+            let v1 = Vec3::new(0.0, 0.0, 0.0);
+            let v2 = Vec3::new(-0.0, 0.0, 0.0);
+            assert_ne!(v1.to_bits(), v2.to_bits());
+        }
+    }
+
+    #[test]
+    fn rotate_around() {
+        {
+            let v = Vec3::new(3.0, 1.0, 0.0);
+            let pivot = Vec3::new(1.0, 1.0, 0.0);
+            let rotation = Quat::from_axis(Vec3::new(0.0, 0.0, 1.0), std::f32::consts::PI);
+            let expected = Vec3::new(-1.0, 1.0, 0.0);
+            assert!(v.rotate_around(pivot, rotation).cmp(expected, EPSILON));
+        }
+
+        {
+            let v = Vec3::new(1.0, 2.0, 5.0);
+            let pivot = Vec3::new(1.0, 2.0, 3.0);
+            let rotation = Quat::from_axis(Vec3::new(0.0, 1.0, 0.0), std::f32::consts::PI);
+            let expected = Vec3::new(1.0, 2.0, 1.0);
+            assert!(v.rotate_around(pivot, rotation).cmp(expected, EPSILON));
+        }
+    }
+
+    #[test]
+    fn closest_point_on_segment() {
+        let a = Vec3::new(1.0, 1.0, 1.0);
+        let b = Vec3::new(5.0, 1.0, 1.0);
+
+        {
+            // The projection falls before `a`
+            let p = Vec3::new(-2.0, 5.0, 1.0);
+            assert!(p.closest_point_on_segment(a, b).cmp(a, EPSILON));
+            assert!(cmp_f32(p.distance_to_segment(a, b), 5.0, EPSILON));
+        }
+
+        {
+            // The projection falls after `b`
+            let p = Vec3::new(8.0, -3.0, 1.0);
+            assert!(p.closest_point_on_segment(a, b).cmp(b, EPSILON));
+            assert!(cmp_f32(p.distance_to_segment(a, b), 5.0, EPSILON));
+        }
+
+        {
+            // The projection falls between `a` and `b`
+            let p = Vec3::new(3.0, 4.0, 1.0);
+            let expected = Vec3::new(3.0, 1.0, 1.0);
+            assert!(p.closest_point_on_segment(a, b).cmp(expected, EPSILON));
+            assert!(cmp_f32(p.distance_to_segment(a, b), 3.0, EPSILON));
+        }
+    }
+
+    #[test]
+    fn display() {
+        let v = Vec3::new(1.0, -2.5, 1.0 / 3.0);
+        assert_eq!(v.to_string(), "(1.00, -2.50, 0.33)");
+        assert_eq!(format!("{:.3}", v), "(1.000, -2.500, 0.333)");
+    }
 }
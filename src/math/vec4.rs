@@ -1,6 +1,11 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
 use bytemuck::{Pod, Zeroable};
 
-use crate::math::vec3::Vec3;
+use crate::math::{self, vec2::Vec2, vec3::Vec3};
 
 /// Represents an arbitrary collection of 4 components
 ///
@@ -12,7 +17,8 @@ use crate::math::vec3::Vec3;
 /// - Locking translation by setting `w` to `0.0` (now it represents a direction, not a point)
 /// - Perspective division (when the GPU performs the vertex shader, the vertex (as a `Vec4`) is divided by its `w` coordinate, hence the name perspective division)
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Zeroable, Pod)]
+#[derive(Debug, Clone, Copy, PartialEq, Zeroable, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec4 {
     /// The X component of the vector
     pub x: f32,
@@ -25,6 +31,11 @@ pub struct Vec4 {
 }
 
 impl Vec4 {
+    /// A vector with all components (including `w`) of value 0.0, also known as a zero vector
+    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+    /// A vector with all components of value 1.0
+    pub const ONE: Self = Self::new(1.0, 1.0, 1.0, 1.0);
+
     /// Creates a new vector
     /// - `x` -> the first component of the vector
     /// - `y` -> the second component of the vector
@@ -45,6 +56,104 @@ impl Vec4 {
         }
     }
 
+    /// Returns the normalized vector (a vector with a length of 1)
+    ///
+    /// All 4 components take part in the normalization, if the vector has no length
+    /// a zero vector is returned
+    pub fn normalize(self) -> Self {
+        let len = self.length();
+        if len < f32::EPSILON {
+            return Self::splat(0.0);
+        }
+
+        Self {
+            x: self.x / len,
+            y: self.y / len,
+            z: self.z / len,
+            w: self.w / len,
+        }
+    }
+
+    /// Returns the length (magnitude) of the vector
+    pub fn length(self) -> f32 {
+        self.length_sq().sqrt()
+    }
+
+    /// Returns the squared length of the vector
+    pub fn length_sq(self) -> f32 {
+        self.dot(self)
+    }
+
+    /// Returns the dot product between 2 vectors
+    /// - `self` -> the first vector
+    /// - `other` -> the second vector
+    ///
+    /// All 4 components take part in the dot product, for the dot product of 2 directions
+    /// `w` should be `0.0` in both
+    pub fn dot(self, other: Self) -> f32 {
+        (self.x * other.x) + (self.y * other.y) + (self.z * other.z) + (self.w * other.w)
+    }
+
+    /// Linearly interpolates between 2 vectors
+    /// - `self` -> the source vector
+    /// - `other` -> the target vector
+    /// - `factor` -> the interpolation factor
+    pub fn lerp(self, other: Self, factor: f32) -> Self {
+        Self {
+            x: self.x + (other.x - self.x) * factor,
+            y: self.y + (other.y - self.y) * factor,
+            z: self.z + (other.z - self.z) * factor,
+            w: self.w + (other.w - self.w) * factor,
+        }
+    }
+
+    /// Performs the perspective division, dividing `x`, `y` and `z` by `w`
+    ///
+    /// This is exactly what the GPU does to clip space positions after the vertex shader,
+    /// which turns them into normalized device coordinates
+    ///
+    /// If `w` is near zero the vector represents a direction (or a point at infinity),
+    /// which can't be divided, so `x`, `y` and `z` are returned unchanged
+    pub fn homogenize(self) -> Vec3 {
+        if self.w.abs() < f32::EPSILON {
+            return Vec3::new(self.x, self.y, self.z);
+        }
+
+        Vec3::new(self.x / self.w, self.y / self.w, self.z / self.w)
+    }
+
+    /// Returns the vector without its `w` component, the inverse of converting a [`Vec3`] into a [`Vec4`]
+    ///
+    /// Unlike [`Vec4::homogenize()`], no perspective division takes place
+    pub fn truncate(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    /// Returns the `(x, y)` components of the vector as a [`Vec2`]
+    pub fn xy(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    /// Returns the `(x, y, z)` components of the vector as a [`Vec3`]
+    pub fn xyz(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    /// Returns the raw bit patterns of the components, which can be hashed and compared exactly
+    ///
+    /// This allows vectors to be used as `HashMap` keys (e.g. to weld vertices by their exact position),
+    /// with the following caveats:
+    /// - `0.0` and `-0.0` have different bit patterns, so they're considered different keys
+    /// - `NaN`s are considered equal only if their bit patterns match exactly
+    pub fn to_bits(self) -> [u32; 4] {
+        [
+            self.x.to_bits(),
+            self.y.to_bits(),
+            self.z.to_bits(),
+            self.w.to_bits(),
+        ]
+    }
+
     /// Compares 2 vectors and returns if they're equal or not
     /// - `self` -> the first vector
     /// - `other` -> the second vector
@@ -70,7 +179,365 @@ impl Default for Vec4 {
     }
 }
 
+impl fmt::Display for Vec4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        math::fmt_components(f, &[self.x, self.y, self.z, self.w])
+    }
+}
+
+impl Add<Vec4> for Vec4 {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+            w: self.w + other.w,
+        }
+    }
+}
+
+impl Sub<Vec4> for Vec4 {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+            w: self.w - other.w,
+        }
+    }
+}
+
+impl Mul<f32> for Vec4 {
+    type Output = Self;
+    fn mul(self, scalar: f32) -> Self {
+        Self {
+            x: self.x * scalar,
+            y: self.y * scalar,
+            z: self.z * scalar,
+            w: self.w * scalar,
+        }
+    }
+}
+
+impl Div<f32> for Vec4 {
+    type Output = Self;
+    fn div(self, scalar: f32) -> Self {
+        if scalar.abs() < f32::EPSILON {
+            panic!("Division by near-zero value");
+        }
+
+        Self {
+            x: self.x / scalar,
+            y: self.y / scalar,
+            z: self.z / scalar,
+            w: self.w / scalar,
+        }
+    }
+}
+
+impl AddAssign<Vec4> for Vec4 {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+        self.w += other.w;
+    }
+}
+
+impl SubAssign<Vec4> for Vec4 {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+        self.w -= other.w;
+    }
+}
+
+impl MulAssign<f32> for Vec4 {
+    fn mul_assign(&mut self, scalar: f32) {
+        self.x *= scalar;
+        self.y *= scalar;
+        self.z *= scalar;
+        self.w *= scalar;
+    }
+}
+
+impl DivAssign<f32> for Vec4 {
+    fn div_assign(&mut self, scalar: f32) {
+        if scalar.abs() < f32::EPSILON {
+            panic!("Division by near-zero value");
+        }
+
+        self.x /= scalar;
+        self.y /= scalar;
+        self.z /= scalar;
+        self.w /= scalar;
+    }
+}
+
+impl Neg for Vec4 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: -self.w,
+        }
+    }
+}
+
+impl Index<usize> for Vec4 {
+    type Output = f32;
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("Index {} is out of range for a Vec4 (4 components)", index),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vec4 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            3 => &mut self.w,
+            _ => panic!("Index {} is out of range for a Vec4 (4 components)", index),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    // Implement unit tests
+    use super::*;
+    use crate::math::{EPSILON, cmp_f32, mat4::Mat4};
+
+    #[test]
+    fn constants() {
+        let Vec4 { x, y, z, w } = Vec4::ZERO;
+        assert_eq!((x, y, z, w), (0.0, 0.0, 0.0, 0.0));
+        let Vec4 { x, y, z, w } = Vec4::ONE;
+        assert_eq!((x, y, z, w), (1.0, 1.0, 1.0, 1.0));
+        assert_eq!(Vec4::ONE, Vec4::splat(1.0));
+    }
+
+    #[test]
+    fn index() {
+        {
+            let v = Vec4::new(1.0, 2.0, 3.0, 4.0);
+            assert!(cmp_f32(v[0], 1.0, EPSILON));
+            assert!(cmp_f32(v[1], 2.0, EPSILON));
+            assert!(cmp_f32(v[2], 3.0, EPSILON));
+            assert!(cmp_f32(v[3], 4.0, EPSILON));
+        }
+
+        {
+            let mut v = Vec4::splat(0.0);
+            v[0] = -1.0;
+            v[1] = -2.0;
+            v[2] = -3.0;
+            v[3] = -4.0;
+            assert!(v.cmp(Vec4::new(-1.0, -2.0, -3.0, -4.0), EPSILON));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 4 is out of range for a Vec4 (4 components)")]
+    fn index_out_of_range() {
+        let v = Vec4::splat(0.0);
+        let _ = v[4];
+    }
+
+    #[test]
+    fn truncate() {
+        {
+            let v = Vec4::new(1.0, 2.0, 3.0, 4.0);
+            assert!(v.truncate().cmp(Vec3::new(1.0, 2.0, 3.0), EPSILON));
+            assert!(Vec3::from(v).cmp(Vec3::new(1.0, 2.0, 3.0), EPSILON));
+        }
+
+        {
+            let v = Vec3::new(1.0, 2.0, 3.0);
+            assert!(Vec4::from(v).truncate().cmp(v, EPSILON));
+        }
+    }
+
+    #[test]
+    fn swizzle() {
+        let v = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        assert!(v.xy().cmp(Vec2::new(1.0, 2.0), EPSILON));
+        assert!(v.xyz().cmp(Vec3::new(1.0, 2.0, 3.0), EPSILON));
+    }
+
+    #[test]
+    fn add() {
+        {
+            let v1 = Vec4::new(1.0, 0.0, 0.0, 0.0);
+            let v2 = Vec4::new(0.0, 1.0, 0.0, 0.0);
+            let v3 = Vec4::new(0.0, 0.0, 1.0, 1.0);
+            let expected = Vec4::new(1.0, 1.0, 1.0, 1.0);
+            assert!((v1 + v2 + v3).cmp(expected, EPSILON));
+        }
+
+        {
+            let mut v = Vec4::new(5.0, 1.0, -7.0, 1.0);
+            v += Vec4::new(-3.0, 0.0, 2.0, 0.0);
+            let expected = Vec4::new(2.0, 1.0, -5.0, 1.0);
+            assert!(v.cmp(expected, EPSILON));
+        }
+    }
+
+    #[test]
+    fn sub() {
+        {
+            let v1 = Vec4::new(1.0, 0.0, 0.0, 1.0);
+            let v2 = Vec4::new(0.0, 1.0, 0.0, 0.0);
+            let v3 = Vec4::new(0.0, 0.0, 1.0, 1.0);
+            let expected = Vec4::new(1.0, -1.0, -1.0, 0.0);
+            assert!((v1 - v2 - v3).cmp(expected, EPSILON));
+        }
+
+        {
+            let mut v = Vec4::new(5.0, 1.0, -7.0, 1.0);
+            v -= Vec4::new(-3.0, 0.0, 2.0, 1.0);
+            let expected = Vec4::new(8.0, 1.0, -9.0, 0.0);
+            assert!(v.cmp(expected, EPSILON));
+        }
+    }
+
+    #[test]
+    fn mul() {
+        {
+            let v = Vec4::new(3.0, 5.0, 7.0, 1.0);
+            let f = 5.0;
+            let expected = Vec4::new(15.0, 25.0, 35.0, 5.0);
+            assert!((v * f).cmp(expected, EPSILON));
+        }
+
+        {
+            let mut v = Vec4::splat(1.0);
+            v *= 5.0;
+            assert!(v.cmp(Vec4::splat(5.0), EPSILON));
+        }
+    }
+
+    #[test]
+    fn div() {
+        {
+            let v = Vec4::new(3.0, 5.0, 7.0, 1.0);
+            let f = 5.0;
+            let expected = Vec4::new(0.6, 1.0, 1.4, 0.2);
+            assert!((v / f).cmp(expected, EPSILON));
+        }
+
+        {
+            let mut v = Vec4::splat(1.0);
+            v /= 5.0;
+            assert!(v.cmp(Vec4::splat(0.2), EPSILON));
+        }
+    }
+
+    #[test]
+    fn neg() {
+        let v = Vec4::new(1.0, -2.0, 3.0, 1.0);
+        let expected = Vec4::new(-1.0, 2.0, -3.0, -1.0);
+        assert!((-v).cmp(expected, EPSILON));
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by near-zero value")]
+    fn div_by_zero() {
+        let _ = Vec4::splat(1.0) / 0.0;
+    }
+
+    #[test]
+    fn normalize() {
+        {
+            let v = Vec4::new(0.0, 3.0, 0.0, 4.0);
+            let expected = Vec4::new(0.0, 0.6, 0.0, 0.8);
+            assert!(v.normalize().cmp(expected, EPSILON));
+            assert!(cmp_f32(v.normalize().length(), 1.0, EPSILON));
+        }
+
+        {
+            let v = Vec4::splat(0.0);
+            assert!(v.normalize().cmp(Vec4::splat(0.0), EPSILON));
+        }
+    }
+
+    #[test]
+    fn length() {
+        let v = Vec4::new(1.0, 1.0, 1.0, 1.0);
+        assert!(cmp_f32(v.length(), 2.0, EPSILON));
+        assert!(cmp_f32(v.length_sq(), 4.0, EPSILON));
+    }
+
+    #[test]
+    fn dot() {
+        {
+            let v1 = Vec4::new(1.0, 2.0, 3.0, 4.0);
+            let v2 = Vec4::new(5.0, 6.0, 7.0, 8.0);
+            let expected = 70.0;
+            assert!(cmp_f32(v1.dot(v2), expected, EPSILON));
+        }
+
+        {
+            let v1 = Vec4::new(1.0, 0.0, 0.0, 0.0);
+            let v2 = Vec4::new(0.0, 1.0, 0.0, 0.0);
+            assert!(cmp_f32(v1.dot(v2), 0.0, EPSILON));
+        }
+    }
+
+    #[test]
+    fn lerp() {
+        {
+            let v1 = Vec4::new(5.0, 10.0, 15.0, 0.0);
+            let v2 = Vec4::new(10.0, 20.0, 30.0, 1.0);
+            let expected = Vec4::new(7.5, 15.0, 22.5, 0.5);
+            assert!(v1.lerp(v2, 0.5).cmp(expected, EPSILON));
+        }
+
+        {
+            let v1 = Vec4::splat(2.0);
+            let v2 = Vec4::splat(4.0);
+            assert!(v1.lerp(v2, 0.0).cmp(v1, EPSILON));
+            assert!(v1.lerp(v2, 1.0).cmp(v2, EPSILON));
+        }
+    }
+
+    #[test]
+    fn homogenize() {
+        {
+            let v = Vec4::new(2.0, 4.0, -6.0, 2.0);
+            let expected = Vec3::new(1.0, 2.0, -3.0);
+            assert!(v.homogenize().cmp(expected, EPSILON));
+        }
+
+        {
+            let v = Vec4::new(2.0, 4.0, -6.0, 0.0);
+            let expected = Vec3::new(2.0, 4.0, -6.0);
+            assert!(v.homogenize().cmp(expected, EPSILON));
+        }
+
+        {
+            // A 90 degree vertical fov puts the top of the frustum at y == -z
+            let projection = Mat4::perspective(std::f32::consts::FRAC_PI_2, 1.0, 1.0, 100.0);
+
+            let top = projection.multiply_vec(Vec4::new(0.0, 5.0, -5.0, 1.0));
+            assert!(cmp_f32(top.homogenize().y, 1.0, EPSILON));
+
+            let near = projection.multiply_vec(Vec4::new(0.0, 0.0, -1.0, 1.0));
+            assert!(cmp_f32(near.homogenize().z, -1.0, EPSILON));
+
+            let far = projection.multiply_vec(Vec4::new(0.0, 0.0, -100.0, 1.0));
+            assert!(cmp_f32(far.homogenize().z, 1.0, EPSILON));
+        }
+    }
 }
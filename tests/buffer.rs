@@ -0,0 +1,336 @@
+mod common;
+
+use std::num::NonZeroU64;
+
+use common::{block_on, context};
+use whirl::graphics::{
+    batch::WriteBatch,
+    buffer::{BufferError, BufferHandle, BufferUsage},
+    group::{BindGroupBuilder, BindGroupLayoutBuilder, ResourceAccess},
+    uniforms::{Globals, Uniform},
+};
+
+#[test]
+fn read_back() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    {
+        let mut buffer = BufferHandle::<u32>::allocate(
+            device,
+            4,
+            BufferUsage::Storage {
+                is_writable: true,
+                is_readable: true,
+            },
+            Some("readable buffer"),
+        );
+        buffer.extend_with_item_list_and_flush(device, queue, &[1, 2, 3, 0xDEADBEEF, 5]);
+        // The whole capacity is read back, which grew to hold the items
+        assert_eq!(
+            buffer.read(device, queue).unwrap(),
+            [1, 2, 3, 0xDEADBEEF, 5, 0, 0, 0]
+        );
+        assert_eq!(
+            buffer.read_range(device, queue, 0..5).unwrap(),
+            [1, 2, 3, 0xDEADBEEF, 5]
+        );
+        assert_eq!(
+            buffer.read_range(device, queue, 3..4).unwrap(),
+            [0xDEADBEEF]
+        );
+        assert_eq!(buffer.read_range(device, queue, 2..2).unwrap(), []);
+    }
+
+    {
+        // Unaligned ranges of small items are read back exactly
+        let mut buffer = BufferHandle::<u8>::allocate(
+            device,
+            12,
+            BufferUsage::Readback,
+            Some("byte readback buffer"),
+        );
+        buffer.overwrite_and_flush(device, queue, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+        assert_eq!(buffer.read_range(device, queue, 9..11).unwrap(), [9, 10]);
+    }
+
+    {
+        let mut buffer = BufferHandle::<u32>::allocate(
+            device,
+            3,
+            BufferUsage::Readback,
+            Some("readback buffer"),
+        );
+        buffer.extend_with_item_list_and_flush(device, queue, &[7, 8, 9]);
+        assert_eq!(buffer.read(device, queue).unwrap(), [7, 8, 9]);
+    }
+
+    {
+        let buffer = BufferHandle::<u32>::create(
+            device,
+            &[1, 2, 3],
+            BufferUsage::Vertex {
+                is_writable: true,
+                is_readable: false,
+            },
+            Some("unreadable buffer"),
+        );
+        assert!(matches!(
+            buffer.read(device, queue),
+            Err(BufferError::NotReadable { .. })
+        ));
+    }
+}
+
+#[test]
+fn copy_to() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    let src = BufferHandle::<u32>::create(
+        device,
+        &[1, 2, 3, 4, 5, 6, 7, 8],
+        BufferUsage::Storage {
+            is_writable: false,
+            is_readable: true,
+        },
+        Some("copy source"),
+    );
+    let mut dst =
+        BufferHandle::<u32>::allocate(device, 8, BufferUsage::Readback, Some("copy destination"));
+    dst.overwrite_and_flush(device, queue, &[0; 8]);
+
+    // The second half of the source goes into the first half of the destination
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    src.copy_to(&mut encoder, &dst, 16, 0, 16);
+    queue.submit([encoder.finish()]);
+    assert_eq!(dst.read(device, queue).unwrap(), [5, 6, 7, 8, 0, 0, 0, 0]);
+}
+
+#[test]
+fn read_gpu_written() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let usage = BufferUsage::Storage {
+        is_writable: true,
+        is_readable: true,
+    };
+
+    // Only the GPU writes to this buffer, so the handle never holds any items
+    let buffer = BufferHandle::<u32>::allocate(device, 4, usage, Some("gpu written buffer"));
+    let src = BufferHandle::<u32>::create(device, &[1, 2, 3, 4], usage, Some("gpu source"));
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    src.copy_to(&mut encoder, &buffer, 0, 0, 16);
+    queue.submit([encoder.finish()]);
+
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.read(device, queue).unwrap(), [1, 2, 3, 4]);
+    assert_eq!(buffer.read_range(device, queue, 1..3).unwrap(), [2, 3]);
+}
+
+#[test]
+fn with_mapped() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let usage = BufferUsage::Storage {
+        is_writable: false,
+        is_readable: true,
+    };
+
+    let buffer = BufferHandle::<u32>::with_mapped(device, 8, usage, Some("mapped"), |items| {
+        assert!(items.iter().all(|&item| item == 0));
+        for (index, item) in items.iter_mut().enumerate().skip(2) {
+            *item = index as u32 * 10;
+        }
+    });
+    assert_eq!(buffer.item_capacity(), 8);
+    assert_eq!(
+        buffer.read(device, queue).unwrap(),
+        [0, 0, 20, 30, 40, 50, 60, 70]
+    );
+}
+
+#[test]
+fn write_batch() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let usage = BufferUsage::Storage {
+        is_writable: true,
+        is_readable: true,
+    };
+    let first = BufferHandle::<u32>::allocate(device, 8, usage, Some("first batched"));
+    let second = BufferHandle::<u32>::allocate(device, 4, usage, Some("second batched"));
+
+    let mut batch = WriteBatch::new();
+    batch.write(&first, 0, &[1u32, 2]);
+    batch.write(&first, 8, &[3u32, 4]);
+    batch.write(&second, 4, &[5u32]);
+    batch.write(&first, 24, &[6u32, 7]);
+    // Overwrites the second item of the first write, the last write wins
+    batch.write(&first, 4, &[8u32]);
+    assert_eq!(batch.write_count(), 4);
+    batch.flush(queue);
+    assert!(batch.is_empty());
+
+    assert_eq!(first.read(device, queue).unwrap(), [1, 8, 3, 4, 0, 0, 6, 7]);
+    assert_eq!(second.read(device, queue).unwrap(), [0, 5, 0, 0]);
+}
+
+#[test]
+fn min_binding_size() {
+    let Some(context) = context() else {
+        return;
+    };
+    let device = context.device();
+
+    let layout = BindGroupLayoutBuilder::new()
+        .add_uniform_buffer_sized(ResourceAccess::Either, NonZeroU64::new(64).unwrap())
+        .build(device);
+    let usage = BufferUsage::Uniform {
+        is_writable: false,
+        is_readable: false,
+    };
+    let small = BufferHandle::<u32>::allocate(device, 4, usage, Some("small"));
+    let large = BufferHandle::<u32>::allocate(device, 16, usage, Some("large"));
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let _ = BindGroupBuilder::new()
+        .add_buffer(&large)
+        .build(&layout, device);
+    assert!(block_on(device.pop_error_scope()).is_none());
+
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let _ = BindGroupBuilder::new()
+        .add_buffer(&small)
+        .build(&layout, device);
+    assert!(block_on(device.pop_error_scope()).is_some());
+}
+
+#[test]
+fn grow_with_offset() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let usage = BufferUsage::Storage {
+        is_writable: true,
+        is_readable: true,
+    };
+
+    {
+        // Growing past the capacity keeps the items written before the growth
+        let mut buffer = BufferHandle::<u32>::allocate(device, 2, usage, Some("growing buffer"));
+        buffer.skip_and_write_item_list_and_flush(device, queue, 0, &[1, 2]);
+        buffer.skip_and_write_item_list_and_flush(device, queue, 5, &[6, 7]);
+        assert_eq!(buffer.item_capacity(), 8);
+        assert_eq!(
+            buffer.read(device, queue).unwrap(),
+            [1, 2, 0, 0, 0, 6, 7, 0]
+        );
+    }
+    {
+        // Skipping past a truncated end zeroes the stale items in between
+        let mut buffer = BufferHandle::<u32>::allocate(device, 8, usage, Some("truncated buffer"));
+        buffer.extend_with_item_list_and_flush(device, queue, &[1, 2, 3, 4]);
+        buffer.truncate_and_flush(device, queue, 1);
+        buffer.skip_and_write_item_and_flush(device, queue, 3, 9);
+        assert_eq!(
+            buffer.read(device, queue).unwrap(),
+            [1, 0, 0, 9, 0, 0, 0, 0]
+        );
+    }
+    {
+        // Writing a middle item only flushes that item, so the GPU contents after it survive
+        let mut buffer =
+            BufferHandle::<u32>::create(device, &[1, 2, 3, 4, 5, 6, 7, 8], usage, Some("middle"));
+        let src = BufferHandle::<u32>::create(device, &[50, 60, 70, 80], usage, Some("tail"));
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        src.copy_to(&mut encoder, &buffer, 0, 16, 16);
+        queue.submit([encoder.finish()]);
+
+        buffer.skip_and_write_item_and_flush(device, queue, 2, 9);
+        assert_eq!(
+            buffer.read(device, queue).unwrap(),
+            [1, 2, 9, 4, 50, 60, 70, 80]
+        );
+    }
+}
+
+#[test]
+fn label() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let usage = BufferUsage::Vertex {
+        is_writable: true,
+        is_readable: false,
+    };
+
+    {
+        let mut buffer = BufferHandle::<u32>::allocate(device, 1, usage, Some("labeled buffer"));
+        assert_eq!(buffer.label(), Some("labeled buffer"));
+        // Growing recreates the GPU buffer, which has to keep the label
+        buffer.extend_with_item_list_and_flush(device, queue, &[1, 2, 3]);
+        assert_eq!(buffer.label(), Some("labeled buffer"));
+    }
+    {
+        let buffer = BufferHandle::create(device, &[1u32], usage, None);
+        assert_eq!(buffer.label(), None);
+    }
+}
+
+#[test]
+fn uniform_read_back() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    let mut uniform = Uniform::new(device, [1.0_f32, 2.0, 3.0, 4.0], Some("uniform"));
+    assert_eq!(
+        uniform.buffer().read(device, queue).unwrap(),
+        [[1.0, 2.0, 3.0, 4.0]]
+    );
+
+    // Setting the value alone doesn't reach the GPU, uploading it does
+    uniform.set([5.0, 6.0, 7.0, 8.0]);
+    assert_eq!(
+        uniform.buffer().read(device, queue).unwrap(),
+        [[1.0, 2.0, 3.0, 4.0]]
+    );
+    uniform.upload(queue);
+    assert_eq!(uniform.get(), [5.0, 6.0, 7.0, 8.0]);
+    assert_eq!(
+        uniform.buffer().read(device, queue).unwrap(),
+        [[5.0, 6.0, 7.0, 8.0]]
+    );
+}
+
+#[test]
+fn globals_upload() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    let mut globals = Globals::new(device, 800.0, 600.0);
+    globals.tick(0.5);
+    globals.tick(0.25);
+    globals.resize(1024.0, 768.0);
+    globals.upload(queue);
+
+    let uploaded: Vec<f32> =
+        bytemuck::cast_slice(&globals.uniform().buffer().read(device, queue).unwrap()).to_vec();
+    assert_eq!(uploaded, [0.75, 0.25, 1024.0, 768.0]);
+}
@@ -0,0 +1,39 @@
+//! Helpers shared by the GPU integration tests
+
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use whirl::graphics::context::{Backend, ContextDescriptor};
+
+/// Polls a future to completion on the current thread
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        std::thread::yield_now();
+    }
+}
+
+/// Creates a headless context on any backend, or `None` (skipping the test) if there's no adapter
+pub fn context() -> Option<whirl::graphics::context::Context> {
+    match block_on(
+        ContextDescriptor {
+            label: Some("test device"),
+            backend: Backend::All,
+            features: wgpu::Features::empty(),
+        }
+        .build(None),
+    ) {
+        Ok(context) => Some(context),
+        Err(error) => {
+            eprintln!("Skipping, {}", error);
+            None
+        }
+    }
+}
@@ -0,0 +1,1300 @@
+mod common;
+
+use common::{block_on, context};
+use whirl::graphics::{
+    buffer::{AnyBufferHandle, BufferHandle, BufferUsage},
+    color::Color,
+    group::{
+        BindGroupBuilder, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutBuilder, Resource,
+        ResourceAccess,
+    },
+    layout::{BufferAttribute, BufferAttributeFormat, create_geometry_layout},
+    line::LineRenderer,
+    mask::StencilMask,
+    pass::{DepthLoadOp, LoadOp, RenderPassDescriptor, StoreOp},
+    pipeline::{Blend, Cull, Draw, IndexFormat, PipelineBuilder, PipelineLayoutBuilder, Stencil},
+    sampler::{SamplerDescriptor, TextureFiltering, TextureWrapping},
+    shader::Shader,
+    texture::{
+        MipLevels, Texture, TextureDescriptor, TextureDimension, TextureError, TextureFormat,
+        TextureSource, TextureUsage,
+    },
+};
+use whirl::math::{mat4::Mat4, vec2::Vec2, vec3::Vec3};
+
+fn target(device: &wgpu::Device, queue: &wgpu::Queue) -> Texture {
+    TextureDescriptor {
+        label: Some("headless target"),
+        dimension: TextureDimension::D2,
+        usage: TextureUsage::Attachment {
+            is_writable: false,
+            is_readable: true,
+        },
+        source: TextureSource::Blank {
+            width: 16,
+            height: 16,
+            format: TextureFormat::Standard,
+        },
+        layers: 1,
+        mip_levels: MipLevels::None,
+        samples: 1,
+    }
+    .build(device, queue)
+    .unwrap()
+}
+
+#[test]
+fn headless_triangle() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    // A triangle covering the center of the target, leaving the corners untouched,
+    // drawn from the vertex index alone by a pipeline without any vertex buffers
+    let shader = Shader::from_source(
+        device,
+        "@vertex
+        fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+            var positions = array<vec2<f32>, 3>(
+                vec2<f32>(-0.5, -0.5),
+                vec2<f32>(0.5, -0.5),
+                vec2<f32>(0.0, 0.5),
+            );
+            return vec4<f32>(positions[index], 0.0, 1.0);
+        }
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {
+            return vec4<f32>(1.0, 0.0, 0.0, 1.0);
+        }",
+        Some("headless shader"),
+    );
+    let target = target(device, queue);
+    let pipeline = PipelineBuilder::new()
+        .shader(&shader)
+        .layout(&PipelineLayoutBuilder::new().build(device))
+        .draw(Draw::Fill)
+        .cull(Cull::None)
+        .blend(Blend::Replace)
+        .build(device);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = RenderPassDescriptor {
+            label: Some("headless pass"),
+            color_load: LoadOp::Clear(Color::BLACK),
+            resolve_target: None,
+            depth_stencil_attachment: None,
+            depth_load: DepthLoadOp::default(),
+            depth_store: StoreOp::default(),
+        }
+        .build(target.view(), &mut encoder);
+        pass.use_pipeline(&pipeline);
+        pass.draw(3, 1);
+    }
+    queue.submit([encoder.finish()]);
+
+    let pixels = target.read_pixels(device, queue).unwrap();
+    assert_eq!(pixels.len(), 16 * 16 * 4);
+    let pixel = |x: usize, y: usize| &pixels[(y * 16 + x) * 4..][..4];
+    assert_eq!(pixel(8, 8), [255, 0, 0, 255]);
+    assert_eq!(pixel(0, 0), [0, 0, 0, 255]);
+    assert_eq!(pixel(15, 15), [0, 0, 0, 255]);
+}
+
+#[test]
+fn dynamic_uniform_offsets() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    // Each draw fills one half of the target with its own color
+    let shader = Shader::from_source(
+        device,
+        "struct Draw {
+            color: vec4<f32>,
+            x_offset: f32,
+        }
+        @group(0) @binding(0) var<uniform> draw: Draw;
+
+        @vertex
+        fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+            var positions = array<vec2<f32>, 6>(
+                vec2<f32>(-0.5, -1.0),
+                vec2<f32>(0.5, -1.0),
+                vec2<f32>(0.5, 1.0),
+                vec2<f32>(-0.5, -1.0),
+                vec2<f32>(0.5, 1.0),
+                vec2<f32>(-0.5, 1.0),
+            );
+            return vec4<f32>(positions[index].x + draw.x_offset, positions[index].y, 0.0, 1.0);
+        }
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {
+            return draw.color;
+        }",
+        Some("dynamic uniform shader"),
+    );
+    let target = target(device, queue);
+
+    // The uniforms of the 2 draws, 256 bytes apart to satisfy the offset alignment
+    let stride = device.limits().min_uniform_buffer_offset_alignment;
+    let items_per_draw = stride as usize / size_of::<[f32; 4]>();
+    let mut uniforms = vec![[0.0_f32; 4]; items_per_draw * 2];
+    uniforms[0] = [1.0, 0.0, 0.0, 1.0];
+    uniforms[1] = [-0.5, 0.0, 0.0, 0.0];
+    uniforms[items_per_draw] = [0.0, 1.0, 0.0, 1.0];
+    uniforms[items_per_draw + 1] = [0.5, 0.0, 0.0, 0.0];
+    let buffer = BufferHandle::create(
+        device,
+        &uniforms,
+        BufferUsage::Uniform {
+            is_writable: false,
+            is_readable: false,
+        },
+        Some("dynamic uniforms"),
+    );
+
+    let group_layout = BindGroupLayoutBuilder::new()
+        .add_dynamic_uniform_buffer(ResourceAccess::Either)
+        .build(device);
+    let group = BindGroupDescriptor {
+        label: Some("dynamic uniform group"),
+        layout: &group_layout,
+        entries: &[BindGroupEntry {
+            binding: 0,
+            resource: Resource::DynamicBuffer {
+                buffer: &buffer,
+                size: 32,
+            },
+        }],
+    }
+    .build(device);
+    let pipeline = PipelineBuilder::new()
+        .shader(&shader)
+        .layout(
+            &PipelineLayoutBuilder::new()
+                .layout(&group_layout)
+                .build(device),
+        )
+        .draw(Draw::Fill)
+        .cull(Cull::None)
+        .blend(Blend::Replace)
+        .build(device);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = RenderPassDescriptor {
+            label: Some("dynamic uniform pass"),
+            color_load: LoadOp::Clear(Color::BLACK),
+            resolve_target: None,
+            depth_stencil_attachment: None,
+            depth_load: DepthLoadOp::default(),
+            depth_store: StoreOp::default(),
+        }
+        .build(target.view(), &mut encoder);
+        pass.use_pipeline(&pipeline);
+        pass.use_bind_group_with_offsets(0, &group, &[0]);
+        pass.draw(6, 1);
+        pass.use_bind_group_with_offsets(0, &group, &[stride]);
+        pass.draw(6, 1);
+    }
+    queue.submit([encoder.finish()]);
+
+    let pixels = target.read_pixels(device, queue).unwrap();
+    let pixel = |x: usize, y: usize| &pixels[(y * 16 + x) * 4..][..4];
+    assert_eq!(pixel(4, 8), [255, 0, 0, 255]);
+    assert_eq!(pixel(11, 8), [0, 255, 0, 255]);
+}
+
+#[test]
+fn reflected_layouts() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    // A fragment-only uniform in group 0 and a vertex-only uniform in group 1
+    let shader = Shader::from_source(
+        device,
+        "@group(0) @binding(0) var<uniform> color: vec4<f32>;
+        @group(1) @binding(0) var<uniform> scale: vec4<f32>;
+
+        @vertex
+        fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+            var positions = array<vec2<f32>, 3>(
+                vec2<f32>(-1.0, -1.0),
+                vec2<f32>(3.0, -1.0),
+                vec2<f32>(-1.0, 3.0),
+            );
+            return vec4<f32>(positions[index] * scale.xy, 0.0, 1.0);
+        }
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {
+            return color;
+        }",
+        Some("reflected shader"),
+    );
+    let layouts = shader.reflect_layouts(device).unwrap();
+    assert_eq!(layouts.len(), 2);
+
+    let usage = BufferUsage::Uniform {
+        is_writable: false,
+        is_readable: false,
+    };
+    let color = BufferHandle::create(device, &[[0.0_f32, 0.0, 1.0, 1.0]], usage, Some("color"));
+    let scale = BufferHandle::create(device, &[[1.0_f32, 1.0, 0.0, 0.0]], usage, Some("scale"));
+
+    // The reflected layouts must be compatible with both the shader and the bind groups
+    device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let color_group = BindGroupBuilder::new()
+        .add_buffer(&color)
+        .build(&layouts[0], device);
+    let scale_group = BindGroupBuilder::new()
+        .add_buffer(&scale)
+        .build(&layouts[1], device);
+    let pipeline = PipelineBuilder::new()
+        .shader(&shader)
+        .layout(
+            &PipelineLayoutBuilder::new()
+                .layout(&layouts[0])
+                .layout(&layouts[1])
+                .build(device),
+        )
+        .draw(Draw::Fill)
+        .cull(Cull::None)
+        .blend(Blend::Replace)
+        .build(device);
+    let target = target(device, queue);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = RenderPassDescriptor {
+            label: Some("reflected pass"),
+            color_load: LoadOp::Clear(Color::BLACK),
+            resolve_target: None,
+            depth_stencil_attachment: None,
+            depth_load: DepthLoadOp::default(),
+            depth_store: StoreOp::default(),
+        }
+        .build(target.view(), &mut encoder);
+        pass.use_pipeline(&pipeline);
+        pass.use_bind_groups(&[&color_group, &scale_group]);
+        pass.draw(3, 1);
+    }
+    queue.submit([encoder.finish()]);
+    assert!(block_on(device.pop_error_scope()).is_none());
+
+    let pixels = target.read_pixels(device, queue).unwrap();
+    assert!(
+        pixels
+            .chunks_exact(4)
+            .all(|pixel| pixel == [0, 0, 255, 255])
+    );
+}
+
+#[test]
+fn record_parallel() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    // More passes than most machines have cores, each clearing its own target
+    let colors = [
+        Color::RED,
+        Color::GREEN,
+        Color::BLUE,
+        Color::WHITE,
+        Color::YELLOW,
+        Color::CYAN,
+        Color::MAGENTA,
+        Color::BLACK,
+    ];
+    let targets: Vec<_> = colors.iter().map(|_| target(device, queue)).collect();
+    let passes = targets
+        .iter()
+        .zip(colors)
+        .map(|(target, color)| {
+            move |encoder: &mut wgpu::CommandEncoder| {
+                RenderPassDescriptor {
+                    label: Some("parallel pass"),
+                    color_load: LoadOp::Clear(color),
+                    resolve_target: None,
+                    depth_stencil_attachment: None,
+                    depth_load: DepthLoadOp::default(),
+                    depth_store: StoreOp::default(),
+                }
+                .build(target.view(), encoder);
+            }
+        })
+        .collect();
+    context.record_parallel(passes);
+
+    for (target, color) in targets.iter().zip(colors) {
+        let pixels = target.read_pixels(device, queue).unwrap();
+        let expected = [
+            (color.red() * 255.0) as u8,
+            (color.green() * 255.0) as u8,
+            (color.blue() * 255.0) as u8,
+            255,
+        ];
+        assert!(pixels.chunks_exact(4).all(|pixel| pixel == expected));
+    }
+
+    // The command buffers are submitted in order, so the last pass clearing a target wins
+    let target = target(device, queue);
+    let passes = [Color::RED, Color::GREEN]
+        .map(|color| {
+            let target = &target;
+            move |encoder: &mut wgpu::CommandEncoder| {
+                RenderPassDescriptor {
+                    label: Some("ordered pass"),
+                    color_load: LoadOp::Clear(color),
+                    resolve_target: None,
+                    depth_stencil_attachment: None,
+                    depth_load: DepthLoadOp::default(),
+                    depth_store: StoreOp::default(),
+                }
+                .build(target.view(), encoder);
+            }
+        })
+        .to_vec();
+    context.record_parallel(passes);
+    let pixels = target.read_pixels(device, queue).unwrap();
+    assert!(
+        pixels
+            .chunks_exact(4)
+            .all(|pixel| pixel == [0, 255, 0, 255])
+    );
+}
+
+#[test]
+fn stencil_mask() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    // Draws 2 triangles between the x coordinates `left` and `right` in the given color
+    let shader = |left: f32, right: f32, color: [f32; 3]| {
+        Shader::from_source(
+            device,
+            &format!(
+                "@vertex
+                fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {{
+                    var positions = array<vec2<f32>, 6>(
+                        vec2<f32>({left:?}, -1.0),
+                        vec2<f32>({right:?}, -1.0),
+                        vec2<f32>({right:?}, 1.0),
+                        vec2<f32>({left:?}, -1.0),
+                        vec2<f32>({right:?}, 1.0),
+                        vec2<f32>({left:?}, 1.0),
+                    );
+                    return vec4<f32>(positions[index], 0.0, 1.0);
+                }}
+
+                @fragment
+                fn fs_main() -> @location(0) vec4<f32> {{
+                    return vec4<f32>({:?}, {:?}, {:?}, 1.0);
+                }}",
+                color[0], color[1], color[2]
+            ),
+            Some("stencil shader"),
+        )
+    };
+    let layout = PipelineLayoutBuilder::new().build(device);
+    let pipeline = |shader: &Shader, stencil| {
+        PipelineBuilder::new()
+            .shader(shader)
+            .layout(&layout)
+            .draw(Draw::Fill)
+            .cull(Cull::None)
+            .blend(Blend::Replace)
+            .stencil(stencil)
+            .build(device)
+    };
+    // The mask covers the left half, the content covers everything
+    let mask_shader = shader(-1.0, 0.0, [1.0, 1.0, 1.0]);
+    let red_shader = shader(-1.0, 1.0, [1.0, 0.0, 0.0]);
+    let green_shader = shader(-1.0, 1.0, [0.0, 1.0, 0.0]);
+    let mask_pipeline = pipeline(&mask_shader, Stencil::Write);
+    let inside_pipeline = pipeline(&red_shader, Stencil::Inside);
+    let outside_pipeline = pipeline(&green_shader, Stencil::Outside);
+
+    let target = target(device, queue);
+    let depth_stencil = TextureDescriptor {
+        label: Some("stencil buffer"),
+        dimension: TextureDimension::D2,
+        usage: TextureUsage::Attachment {
+            is_writable: false,
+            is_readable: false,
+        },
+        source: TextureSource::DepthStencil {
+            width: 16,
+            height: 16,
+        },
+        layers: 1,
+        mip_levels: MipLevels::None,
+        samples: 1,
+    }
+    .build(device, queue)
+    .unwrap();
+
+    let mask = StencilMask::new(1);
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = RenderPassDescriptor {
+            label: Some("stencil pass"),
+            color_load: LoadOp::Clear(Color::BLACK),
+            resolve_target: None,
+            depth_stencil_attachment: Some(&depth_stencil),
+            depth_load: DepthLoadOp::Clear(1.0),
+            depth_store: StoreOp::default(),
+        }
+        .build(target.view(), &mut encoder);
+        mask.begin_mask(&mut pass, &mask_pipeline);
+        pass.draw(6, 1);
+        mask.draw_masked(&mut pass, &inside_pipeline);
+        pass.draw(6, 1);
+        mask.draw_masked(&mut pass, &outside_pipeline);
+        pass.draw(6, 1);
+    }
+    queue.submit([encoder.finish()]);
+
+    // The mask shape itself writes no color
+    let pixels = target.read_pixels(device, queue).unwrap();
+    let pixel = |x: usize, y: usize| &pixels[(y * 16 + x) * 4..][..4];
+    assert_eq!(pixel(2, 8), [255, 0, 0, 255]);
+    assert_eq!(pixel(7, 2), [255, 0, 0, 255]);
+    assert_eq!(pixel(8, 8), [0, 255, 0, 255]);
+    assert_eq!(pixel(13, 13), [0, 255, 0, 255]);
+}
+
+/// Draws a quad covering the center of the target from 4 vertices and 6 indices,
+/// returning the pixels of the target
+fn indexed_quad(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    indices: &dyn AnyBufferHandle,
+    format: IndexFormat,
+) -> Vec<u8> {
+    let shader = Shader::from_source(
+        device,
+        "@vertex
+        fn vs_main(@location(0) position: vec2<f32>) -> @builtin(position) vec4<f32> {
+            return vec4<f32>(position, 0.0, 1.0);
+        }
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {
+            return vec4<f32>(0.0, 0.0, 1.0, 1.0);
+        }",
+        Some("indexed shader"),
+    );
+    let target = target(device, queue);
+    let pipeline = PipelineBuilder::new()
+        .shader(&shader)
+        .layout(&PipelineLayoutBuilder::new().build(device))
+        .geometry_layout(create_geometry_layout(&[BufferAttribute {
+            location: 0,
+            size: 2,
+            format: BufferAttributeFormat::F32,
+        }]))
+        .draw(Draw::Fill)
+        .cull(Cull::None)
+        .blend(Blend::Replace)
+        .build(device);
+    let vertices = BufferHandle::create(
+        device,
+        &[[-0.5f32, -0.5], [0.5, -0.5], [-0.5, 0.5], [0.5, 0.5]],
+        BufferUsage::Vertex {
+            is_writable: false,
+            is_readable: false,
+        },
+        Some("indexed vertices"),
+    );
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = RenderPassDescriptor {
+            label: Some("indexed pass"),
+            color_load: LoadOp::Clear(Color::BLACK),
+            resolve_target: None,
+            depth_stencil_attachment: None,
+            depth_load: DepthLoadOp::default(),
+            depth_store: StoreOp::default(),
+        }
+        .build(target.view(), &mut encoder);
+        pass.use_pipeline(&pipeline);
+        pass.use_geometry_buffer(0, &vertices);
+        pass.use_index_buffer_with_format(indices, format);
+        pass.draw_indexed(6, 1);
+    }
+    queue.submit([encoder.finish()]);
+    target.read_pixels(device, queue).unwrap()
+}
+
+#[test]
+fn indexed_geometry() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let usage = BufferUsage::Index {
+        is_writable: false,
+        is_readable: false,
+    };
+
+    let wide = BufferHandle::create(device, &[0u32, 1, 2, 2, 1, 3], usage, Some("u32 indices"));
+    let narrow = BufferHandle::create(device, &[0u16, 1, 2, 2, 1, 3], usage, Some("u16 indices"));
+    // Both index buffers are bound through the type-erased trait object
+    let cases: [(&dyn AnyBufferHandle, IndexFormat); 2] =
+        [(&wide, IndexFormat::U32), (&narrow, IndexFormat::U16)];
+    for (indices, format) in cases {
+        let pixels = indexed_quad(device, queue, indices, format);
+        let pixel = |x: usize, y: usize| &pixels[(y * 16 + x) * 4..][..4];
+        assert_eq!(pixel(5, 5), [0, 0, 255, 255], "{:?}", format);
+        assert_eq!(pixel(10, 10), [0, 0, 255, 255], "{:?}", format);
+        assert_eq!(pixel(0, 0), [0, 0, 0, 255], "{:?}", format);
+        assert_eq!(pixel(15, 15), [0, 0, 0, 255], "{:?}", format);
+    }
+}
+
+/// Draws the target full of the texture sampled through its view at `uv` and the given mip level,
+/// returning the center pixel of the target
+fn sample(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &Texture,
+    uv: [f32; 2],
+    mip_level: f32,
+) -> [u8; 4] {
+    let shader = Shader::from_source(
+        device,
+        &format!(
+            "@group(0) @binding(0) var image: texture_2d<f32>;
+            @group(0) @binding(1) var image_sampler: sampler;
+
+            @vertex
+            fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {{
+                var positions = array<vec2<f32>, 3>(
+                    vec2<f32>(-1.0, -1.0),
+                    vec2<f32>(3.0, -1.0),
+                    vec2<f32>(-1.0, 3.0),
+                );
+                return vec4<f32>(positions[index], 0.0, 1.0);
+            }}
+
+            @fragment
+            fn fs_main() -> @location(0) vec4<f32> {{
+                return textureSampleLevel(image, image_sampler, vec2<f32>({:?}, {:?}), {:?});
+            }}",
+            uv[0], uv[1], mip_level
+        ),
+        Some("sampling shader"),
+    );
+    let bind_group_layout = BindGroupLayoutBuilder::new()
+        .add_texture_2d(ResourceAccess::Fragment)
+        .add_linear_sampler(ResourceAccess::Fragment)
+        .build(device);
+    let sampler = SamplerDescriptor {
+        label: Some("sampling sampler"),
+        wrapping: TextureWrapping::ClampToEdge,
+        filtering: TextureFiltering::Linear,
+        mip_filter: None,
+        anisotropy: 1,
+    }
+    .build(device);
+    let bind_group = BindGroupBuilder::new()
+        .add_texture(texture)
+        .add_sampler(&sampler)
+        .build(&bind_group_layout, device);
+    let pipeline = PipelineBuilder::new()
+        .shader(&shader)
+        .layout(
+            &PipelineLayoutBuilder::new()
+                .layout(&bind_group_layout)
+                .build(device),
+        )
+        .draw(Draw::Fill)
+        .cull(Cull::None)
+        .blend(Blend::Replace)
+        .build(device);
+    let target = target(device, queue);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = RenderPassDescriptor {
+            label: Some("sampling pass"),
+            color_load: LoadOp::Clear(Color::BLACK),
+            resolve_target: None,
+            depth_stencil_attachment: None,
+            depth_load: DepthLoadOp::default(),
+            depth_store: StoreOp::default(),
+        }
+        .build(target.view(), &mut encoder);
+        pass.use_pipeline(&pipeline);
+        pass.use_bind_group(&bind_group);
+        pass.draw(3, 1);
+    }
+    queue.submit([encoder.finish()]);
+
+    let pixels = target.read_pixels(device, queue).unwrap();
+    let center = (8 * 16 + 8) * 4;
+    pixels[center..center + 4].try_into().unwrap()
+}
+
+#[test]
+fn line_renderer() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let target = target(device, queue);
+
+    // A horizontal line 4 pixels wide across the middle of the target
+    let mut lines = LineRenderer::new(device, None);
+    lines.add_line(
+        Vec3::new(-1.0, 0.0, 0.0),
+        Vec3::new(1.0, 0.0, 0.0),
+        Color::RED,
+        4.0,
+    );
+    lines.prepare(device, queue, Mat4::IDENTITY, Vec2::new(16.0, 16.0));
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = RenderPassDescriptor {
+            label: Some("line pass"),
+            color_load: LoadOp::Clear(Color::BLACK),
+            resolve_target: None,
+            depth_stencil_attachment: None,
+            depth_load: DepthLoadOp::default(),
+            depth_store: StoreOp::default(),
+        }
+        .build(target.view(), &mut encoder);
+        lines.draw(&mut pass);
+    }
+    queue.submit([encoder.finish()]);
+
+    let pixels = target.read_pixels(device, queue).unwrap();
+    let pixel = |x: usize, y: usize| &pixels[(y * 16 + x) * 4..][..4];
+    for x in [0, 8, 15] {
+        for y in 6..10 {
+            assert_eq!(pixel(x, y), [255, 0, 0, 255], "({}, {})", x, y);
+        }
+        assert_eq!(pixel(x, 2), [0, 0, 0, 255]);
+        assert_eq!(pixel(x, 13), [0, 0, 0, 255]);
+    }
+}
+
+#[test]
+fn solid_textures() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    {
+        let texture = Texture::solid(device, queue, Color::MAGENTA).unwrap();
+        assert_eq!((texture.size().width, texture.size().height), (1, 1));
+        assert_eq!(
+            sample(device, queue, &texture, [0.5, 0.5], 0.0),
+            [255, 0, 255, 255]
+        );
+    }
+
+    {
+        let white = Texture::white(device, queue).unwrap();
+        assert_eq!(
+            sample(device, queue, &white, [0.5, 0.5], 0.0),
+            [255, 255, 255, 255]
+        );
+        let black = Texture::black(device, queue).unwrap();
+        assert_eq!(
+            sample(device, queue, &black, [0.5, 0.5], 0.0),
+            [0, 0, 0, 255]
+        );
+    }
+
+    {
+        // The linear 0.5 of the normal is sRGB encoded by the target
+        let normal = Texture::default_normal(device, queue).unwrap();
+        let pixel = sample(device, queue, &normal, [0.5, 0.5], 0.0);
+        for channel in &pixel[..2] {
+            assert!((186..=189).contains(channel), "{:?}", pixel);
+        }
+        assert_eq!(pixel[2..], [255, 255]);
+    }
+}
+
+#[test]
+fn mipmaps() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let texture = |size: u32, bytes: Vec<u8>| {
+        TextureDescriptor {
+            label: Some("mipmapped texture"),
+            dimension: TextureDimension::D2,
+            usage: TextureUsage::Image {
+                is_writable: true,
+                is_readable: true,
+            },
+            source: TextureSource::Bytes {
+                width: size,
+                height: size,
+                format: TextureFormat::UnsignedNormalized,
+                bytes,
+            },
+            layers: 1,
+            mip_levels: MipLevels::Auto,
+            samples: 1,
+        }
+        .build(device, queue)
+        .unwrap()
+    };
+
+    {
+        let texture = texture(256, vec![255; 256 * 256 * 4]);
+        assert_eq!(texture.mip_level_count(), 9);
+    }
+
+    {
+        // A 2x2 checkerboard averages out to gray in its 1x1 level
+        let white = [255, 255, 255, 255];
+        let black = [0, 0, 0, 255];
+        let texture = texture(2, [white, black, black, white].concat());
+        assert_eq!(texture.mip_level_count(), 2);
+
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("mipmap readback buffer"),
+            size: 4,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: texture.raw(),
+                mip_level: 1,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &staging,
+                layout: wgpu::TexelCopyBufferLayout::default(),
+            },
+            wgpu::Extent3d::default(),
+        );
+        queue.submit([encoder.finish()]);
+        staging
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, |result| result.unwrap());
+        device.poll(wgpu::PollType::wait_indefinitely()).unwrap();
+
+        let pixel = staging.slice(..).get_mapped_range().to_vec();
+        for channel in &pixel[..3] {
+            assert!((127..=128).contains(channel), "{:?}", pixel);
+        }
+        assert_eq!(pixel[3], 255);
+
+        // The view spans every level, so the top left texel is white in the first level
+        // and the average of the checkerboard in the second one, sRGB encoded by the target
+        assert_eq!(
+            sample(device, queue, &texture, [0.25, 0.25], 0.0),
+            [255, 255, 255, 255]
+        );
+        let pixel = sample(device, queue, &texture, [0.25, 0.25], 1.0);
+        for channel in &pixel[..3] {
+            assert!((186..=189).contains(channel), "{:?}", pixel);
+        }
+        assert_eq!(pixel[3], 255);
+    }
+}
+
+#[test]
+fn multisampled_triangle() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    let shader = Shader::from_source(
+        device,
+        "@vertex
+        fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+            var positions = array<vec2<f32>, 3>(
+                vec2<f32>(-0.5, -0.5),
+                vec2<f32>(0.5, -0.5),
+                vec2<f32>(0.0, 0.5),
+            );
+            return vec4<f32>(positions[index], 0.0, 1.0);
+        }
+
+        @fragment
+        fn fs_main() -> @location(0) vec4<f32> {
+            return vec4<f32>(1.0, 0.0, 0.0, 1.0);
+        }",
+        Some("multisampled shader"),
+    );
+    let multisampled = TextureDescriptor {
+        label: Some("multisampled target"),
+        dimension: TextureDimension::D2,
+        usage: TextureUsage::Attachment {
+            is_writable: false,
+            is_readable: false,
+        },
+        source: TextureSource::Blank {
+            width: 16,
+            height: 16,
+            format: TextureFormat::Standard,
+        },
+        layers: 1,
+        mip_levels: MipLevels::None,
+        samples: 4,
+    }
+    .build(device, queue)
+    .unwrap();
+    assert_eq!(multisampled.sample_count(), 4);
+    let target = target(device, queue);
+    let pipeline = PipelineBuilder::new()
+        .shader(&shader)
+        .layout(&PipelineLayoutBuilder::new().build(device))
+        .draw(Draw::Fill)
+        .cull(Cull::None)
+        .blend(Blend::Replace)
+        .samples(4)
+        .build(device);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = RenderPassDescriptor {
+            label: Some("multisampled pass"),
+            color_load: LoadOp::Clear(Color::BLACK),
+            resolve_target: Some(target.view()),
+            depth_stencil_attachment: None,
+            depth_load: DepthLoadOp::default(),
+            depth_store: StoreOp::default(),
+        }
+        .build(multisampled.view(), &mut encoder);
+        pass.use_pipeline(&pipeline);
+        pass.draw(3, 1);
+    }
+    queue.submit([encoder.finish()]);
+
+    let pixels = target.read_pixels(device, queue).unwrap();
+    let pixel = |x: usize, y: usize| &pixels[(y * 16 + x) * 4..][..4];
+    assert_eq!(pixel(8, 8), [255, 0, 0, 255]);
+    assert_eq!(pixel(0, 0), [0, 0, 0, 255]);
+    // The pixels the slanted edges cross are only partially covered, which blends them
+    let blended = pixels
+        .chunks_exact(4)
+        .any(|pixel| pixel[0] > 0 && pixel[0] < 255);
+    assert!(blended);
+}
+
+#[test]
+fn cubemap() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+
+    // Each face is a solid color, in the order +X, -X, +Y, -Y, +Z, -Z
+    let colors = [
+        [255, 0, 0, 255],
+        [0, 255, 0, 255],
+        [0, 0, 255, 255],
+        [255, 255, 0, 255],
+        [0, 255, 255, 255],
+        [255, 0, 255, 255],
+    ];
+    let directory = std::env::temp_dir().join(format!("whirl_cubemap_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let faces = colors.map(|color| {
+        let path = directory.join(format!("{:?}.png", color));
+        image::RgbaImage::from_pixel(4, 4, image::Rgba(color))
+            .save(&path)
+            .unwrap();
+        path
+    });
+    let cubemap = TextureDescriptor {
+        label: Some("cubemap"),
+        dimension: TextureDimension::D2,
+        usage: TextureUsage::Image {
+            is_writable: true,
+            is_readable: false,
+        },
+        source: TextureSource::Cubemap { faces },
+        // Derived from the 6 faces
+        layers: 1,
+        mip_levels: MipLevels::Auto,
+        samples: 1,
+    }
+    .build(device, queue)
+    .unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert_eq!(cubemap.size().depth, 6);
+    assert_eq!(cubemap.mip_level_count(), 3);
+
+    // The left half of the target samples the +X face, the right half the -Z face
+    let shader = Shader::from_source(
+        device,
+        "@group(0) @binding(0)
+        var cubemap: texture_cube<f32>;
+        @group(0) @binding(1)
+        var cubemap_sampler: sampler;
+
+        @vertex
+        fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+            let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+            return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+        }
+
+        @fragment
+        fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+            let direction = select(vec3<f32>(0.0, 0.0, -1.0), vec3<f32>(1.0, 0.0, 0.0), position.x < 8.0);
+            return textureSample(cubemap, cubemap_sampler, direction);
+        }",
+        Some("cubemap shader"),
+    );
+    let bind_group_layout = BindGroupLayoutBuilder::new()
+        .add_texture_cubemap(ResourceAccess::Fragment)
+        .add_linear_sampler(ResourceAccess::Fragment)
+        .build(device);
+    let sampler = SamplerDescriptor {
+        label: Some("cubemap sampler"),
+        wrapping: TextureWrapping::ClampToEdge,
+        filtering: TextureFiltering::Linear,
+        mip_filter: None,
+        anisotropy: 1,
+    }
+    .build(device);
+    let bind_group = BindGroupBuilder::new()
+        .add_texture(&cubemap)
+        .add_sampler(&sampler)
+        .build(&bind_group_layout, device);
+    let target = target(device, queue);
+    let pipeline = PipelineBuilder::new()
+        .shader(&shader)
+        .layout(
+            &PipelineLayoutBuilder::new()
+                .layout(&bind_group_layout)
+                .build(device),
+        )
+        .draw(Draw::Fill)
+        .cull(Cull::None)
+        .blend(Blend::Replace)
+        .build(device);
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = RenderPassDescriptor {
+            label: Some("cubemap pass"),
+            color_load: LoadOp::Clear(Color::BLACK),
+            resolve_target: None,
+            depth_stencil_attachment: None,
+            depth_load: DepthLoadOp::default(),
+            depth_store: StoreOp::default(),
+        }
+        .build(target.view(), &mut encoder);
+        pass.use_pipeline(&pipeline);
+        pass.use_bind_group(&bind_group);
+        pass.draw(3, 1);
+    }
+    queue.submit([encoder.finish()]);
+
+    let pixels = target.read_pixels(device, queue).unwrap();
+    let pixel = |x: usize, y: usize| &pixels[(y * 16 + x) * 4..][..4];
+    assert_eq!(pixel(2, 8), colors[0]);
+    assert_eq!(pixel(13, 8), colors[5]);
+}
+
+#[test]
+fn bytes_round_trip() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let texture = |bytes: Vec<u8>| {
+        TextureDescriptor {
+            label: Some("bytes texture"),
+            dimension: TextureDimension::D2,
+            usage: TextureUsage::Image {
+                is_writable: true,
+                is_readable: true,
+            },
+            source: TextureSource::Bytes {
+                width: 3,
+                height: 2,
+                format: TextureFormat::UnsignedNormalized,
+                bytes,
+            },
+            layers: 1,
+            mip_levels: MipLevels::None,
+            samples: 1,
+        }
+        .build(device, queue)
+    };
+
+    {
+        let bytes: Vec<u8> = (0..3 * 2 * 4).collect();
+        let texture = texture(bytes.clone()).unwrap();
+        assert_eq!(texture.read_pixels(device, queue).unwrap(), bytes);
+    }
+
+    {
+        let result = texture(vec![0; 3 * 2]);
+        assert!(matches!(
+            result,
+            Err(TextureError::DataSizeMismatch {
+                expected: 24,
+                actual: 6
+            })
+        ));
+    }
+}
+
+#[test]
+fn formats() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let texture = |format, source| {
+        TextureDescriptor {
+            label: Some("formatted texture"),
+            dimension: TextureDimension::D2,
+            usage: TextureUsage::Image {
+                is_writable: true,
+                is_readable: true,
+            },
+            source,
+            layers: 1,
+            mip_levels: MipLevels::None,
+            samples: 1,
+        }
+        .build(device, queue)
+        .unwrap_or_else(|error| panic!("{:?}: {}", format, error))
+    };
+
+    for format in [
+        TextureFormat::R8Unorm,
+        TextureFormat::Rg8Unorm,
+        TextureFormat::R16Float,
+        TextureFormat::Rg16Float,
+        TextureFormat::Rgba16Float,
+        TextureFormat::Rgba32Float,
+    ] {
+        let blank = TextureSource::Blank {
+            width: 5,
+            height: 3,
+            format,
+        };
+        let texture = texture(format, blank);
+        assert_eq!(texture.raw().format(), format.raw());
+        let size = 5 * 3 * format.bytes_per_pixel() as usize;
+        assert_eq!(texture.read_pixels(device, queue).unwrap(), vec![0; size]);
+    }
+
+    {
+        // A single byte per pixel makes rows that are far off the copy alignment
+        let bytes: Vec<u8> = (0..5 * 3).collect();
+        let r8 = TextureSource::Bytes {
+            width: 5,
+            height: 3,
+            format: TextureFormat::R8Unorm,
+            bytes: bytes.clone(),
+        };
+        let texture = texture(TextureFormat::R8Unorm, r8);
+        assert_eq!(texture.read_pixels(device, queue).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn hdr_file() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let directory = std::env::temp_dir().join(format!("whirl_hdr_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let texture = |path| {
+        TextureDescriptor {
+            label: Some("hdr texture"),
+            dimension: TextureDimension::D2,
+            usage: TextureUsage::Image {
+                is_writable: true,
+                is_readable: true,
+            },
+            source: TextureSource::HdrFile { path },
+            layers: 1,
+            mip_levels: MipLevels::None,
+            samples: 1,
+        }
+        .build(device, queue)
+        .unwrap()
+    };
+
+    {
+        // Values past 1.0 survive, and these are exactly representable in the RGBE encoding
+        let path = directory.join("bright.hdr");
+        image::Rgb32FImage::from_pixel(2, 2, image::Rgb([4.0, 0.5, 0.25]))
+            .save(&path)
+            .unwrap();
+        let texture = texture(path);
+        assert!(matches!(texture.format(), TextureFormat::Rgba32Float));
+        let pixels = texture.read_pixels(device, queue).unwrap();
+        let channels: &[f32] = bytemuck::cast_slice(&pixels);
+        assert_eq!(channels[..4], [4.0, 0.5, 0.25, 1.0]);
+    }
+
+    {
+        let path = directory.join("plain.png");
+        image::RgbaImage::from_pixel(2, 2, image::Rgba([255, 0, 0, 255]))
+            .save(&path)
+            .unwrap();
+        let texture = texture(path);
+        assert!(matches!(texture.format(), TextureFormat::Rgba16Float));
+        let pixels = texture.read_pixels(device, queue).unwrap();
+        // 1.0 and 0.0 as 16-bit floats
+        assert_eq!(
+            pixels[..8],
+            [0x00, 0x3C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3C]
+        );
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn save_png() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let directory = std::env::temp_dir().join(format!("whirl_png_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    {
+        // A solid color survives the round trip through the staging buffer and the PNG encoder
+        let target = target(device, queue);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        RenderPassDescriptor {
+            label: Some("clear pass"),
+            color_load: LoadOp::Clear(Color::GREEN),
+            resolve_target: None,
+            depth_stencil_attachment: None,
+            depth_load: DepthLoadOp::default(),
+            depth_store: StoreOp::default(),
+        }
+        .build(target.view(), &mut encoder);
+        queue.submit([encoder.finish()]);
+
+        let path = directory.join("green.png");
+        target.save_png(device, queue, &path).unwrap();
+        let image = image::open(&path).unwrap().into_rgba8();
+        assert_eq!(image.dimensions(), (16, 16));
+        assert!(image.pixels().all(|pixel| pixel.0 == [0, 255, 0, 255]));
+    }
+
+    {
+        // Textures without a readable usage can't be copied back
+        let texture = TextureDescriptor {
+            label: Some("unreadable texture"),
+            dimension: TextureDimension::D2,
+            usage: TextureUsage::Attachment {
+                is_writable: false,
+                is_readable: false,
+            },
+            source: TextureSource::Blank {
+                width: 4,
+                height: 4,
+                format: TextureFormat::Standard,
+            },
+            layers: 1,
+            mip_levels: MipLevels::None,
+            samples: 1,
+        }
+        .build(device, queue)
+        .unwrap();
+        let path = directory.join("unreadable.png");
+        assert!(matches!(
+            texture.read_pixels(device, queue),
+            Err(TextureError::ReadFailure { .. })
+        ));
+        assert!(matches!(
+            texture.save_png(device, queue, &path),
+            Err(TextureError::ReadFailure { .. })
+        ));
+        assert!(!path.exists());
+    }
+
+    {
+        // Only RGBA8 textures map onto a PNG
+        let texture = TextureDescriptor {
+            label: Some("float texture"),
+            dimension: TextureDimension::D2,
+            usage: TextureUsage::Attachment {
+                is_writable: false,
+                is_readable: true,
+            },
+            source: TextureSource::Blank {
+                width: 4,
+                height: 4,
+                format: TextureFormat::Rgba16Float,
+            },
+            layers: 1,
+            mip_levels: MipLevels::None,
+            samples: 1,
+        }
+        .build(device, queue)
+        .unwrap();
+        assert!(matches!(
+            texture.save_png(device, queue, directory.join("float.png")),
+            Err(TextureError::SaveFailure { .. })
+        ));
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn load_async() {
+    let Some(context) = context() else {
+        return;
+    };
+    let (device, queue) = (context.device(), context.queue());
+    let directory = std::env::temp_dir().join(format!("whirl_async_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    {
+        let path = directory.join("blue.png");
+        image::RgbaImage::from_pixel(4, 2, image::Rgba([0, 0, 255, 255]))
+            .save(&path)
+            .unwrap();
+        let texture = block_on(Texture::load_async(device, queue, &path)).unwrap();
+        assert_eq!((texture.size().width, texture.size().height), (4, 2));
+        assert!(matches!(texture.format(), TextureFormat::Standard));
+        assert_eq!(
+            sample(device, queue, &texture, [0.5, 0.5], 0.0),
+            [0, 0, 255, 255]
+        );
+    }
+
+    {
+        let path = directory.join("missing.png");
+        let result = block_on(Texture::load_async(device, queue, &path));
+        assert!(
+            matches!(&result, Err(TextureError::OpenFailure { file, .. }) if *file == path),
+            "{:?}",
+            result
+        );
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "derive")]
+
+use bytemuck::{Pod, Zeroable};
+use whirl::{
+    graphics::layout::Vertex,
+    math::{vec2::Vec2, vec3::Vec3},
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod, Vertex)]
+struct MeshVertex {
+    #[location(0)]
+    position: Vec3,
+    #[location(2)]
+    uv: Vec2,
+    #[location(1)]
+    color: [f32; 4],
+    #[location(3)]
+    material: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Zeroable, Pod, Vertex)]
+struct TupleVertex(#[location(0)] [i32; 2]);
+
+#[test]
+fn derived_layout() {
+    let layout = MeshVertex::geometry_layout().raw();
+    assert_eq!(layout.array_stride, size_of::<MeshVertex>() as u64);
+    assert_eq!(layout.step_mode, wgpu::VertexStepMode::Vertex);
+    let attributes: Vec<_> = layout
+        .attributes
+        .iter()
+        .map(|attribute| {
+            (
+                attribute.shader_location,
+                attribute.offset,
+                attribute.format,
+            )
+        })
+        .collect();
+    assert_eq!(
+        attributes,
+        [
+            (0, 0, wgpu::VertexFormat::Float32x3),
+            (2, 12, wgpu::VertexFormat::Float32x2),
+            (1, 20, wgpu::VertexFormat::Float32x4),
+            (3, 36, wgpu::VertexFormat::Uint32),
+        ]
+    );
+
+    let layout = TupleVertex::instance_layout().raw();
+    assert_eq!(layout.array_stride, 8);
+    assert_eq!(layout.step_mode, wgpu::VertexStepMode::Instance);
+    assert_eq!(layout.attributes[0].format, wgpu::VertexFormat::Sint32x2);
+}
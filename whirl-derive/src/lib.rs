@@ -0,0 +1,108 @@
+//! # Whirl derive
+//! Derive macros for the whirl graphics toolkit.
+//!
+//! These are re-exported by whirl when its `derive` feature is enabled,
+//! so they shouldn't be depended on directly.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitInt, parse_macro_input};
+
+/// Derives `whirl::graphics::layout::Vertex` for a `#[repr(C)]` struct
+///
+/// Every field must be annotated with `#[location(n)]`, the shader location of the attribute,
+/// and its type must implement `whirl::graphics::layout::VertexAttribute`,
+/// which is where the format of the attribute is inferred from (e.g. `Vec3` -> F32 x 3)
+///
+/// The attributes are laid out in the order of the fields
+#[proc_macro_derive(Vertex, attributes(location))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_vertex(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand_vertex(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Vertex can only be derived for structs",
+        ));
+    };
+    if !is_repr_c(&input) {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Vertex requires #[repr(C)], otherwise the field order in memory isn't guaranteed",
+        ));
+    }
+    let fields = match &data.fields {
+        Fields::Named(fields) => &fields.named,
+        Fields::Unnamed(fields) => &fields.unnamed,
+        Fields::Unit => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Vertex can't be derived for a struct without fields",
+            ));
+        }
+    };
+
+    let mut locations: Vec<u32> = Vec::new();
+    let mut attributes = Vec::new();
+    for field in fields {
+        let Some(attribute) = field
+            .attrs
+            .iter()
+            .find(|attribute| attribute.path().is_ident("location"))
+        else {
+            return Err(Error::new_spanned(
+                field,
+                "Expected a #[location(n)] attribute on every field of a Vertex",
+            ));
+        };
+        let literal: LitInt = attribute.parse_args()?;
+        let location: u32 = literal.base10_parse()?;
+        if locations.contains(&location) {
+            return Err(Error::new_spanned(
+                literal,
+                format!("Location {} is used by more than one field", location),
+            ));
+        }
+        locations.push(location);
+
+        let ty = &field.ty;
+        attributes.push(quote! {
+            ::whirl::graphics::layout::BufferAttribute {
+                location: #location,
+                size: <#ty as ::whirl::graphics::layout::VertexAttribute>::SIZE,
+                format: <#ty as ::whirl::graphics::layout::VertexAttribute>::FORMAT,
+            }
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::whirl::graphics::layout::Vertex for #name #type_generics #where_clause {
+            const ATTRIBUTES: &'static [::whirl::graphics::layout::BufferAttribute] = &[#(#attributes),*];
+        }
+    })
+}
+
+/// Checks whether the struct is annotated with `#[repr(C)]` (possibly among other representation hints)
+fn is_repr_c(input: &DeriveInput) -> bool {
+    let mut repr_c = false;
+    for attribute in &input.attrs {
+        if !attribute.path().is_ident("repr") {
+            continue;
+        }
+        let _ = attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            }
+            Ok(())
+        });
+    }
+    repr_c
+}